@@ -21,12 +21,18 @@ pub fn bench(c: &mut Criterion) {
             String::from("extracted_var"),
             Extractor {
                 from: String::from("${event.payload.body}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
         // Add action
-        let mut action = Action { id: "log".to_owned(), payload: HashMap::new() };
+        let mut action =
+            Action { id: "log".to_owned(), payload: HashMap::new(), when: None, template: None };
 
         action
             .payload
@@ -60,5 +66,6 @@ fn new_rule(name: &str, operator: Operator) -> Rule {
         actions: vec![],
         description: "".to_owned(),
         constraint,
+        tests: vec![],
     }
 }
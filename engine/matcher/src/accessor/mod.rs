@@ -3,13 +3,18 @@
 use crate::error::MatcherError;
 use crate::model::InternalEvent;
 use crate::validator::id::IdValidator;
+use chrono::Utc;
 use log::*;
 use regex::Regex as RustRegex;
 use std::borrow::Cow;
 use std::collections::HashMap;
-use tornado_common_api::Value;
+use tornado_common_api::{Number, Value};
 
+#[derive(Clone)]
 pub struct AccessorBuilder {
+    constants: Value,
+    env_vars: HashMap<String, String>,
+    item_allowed: bool,
     id_validator: IdValidator,
     start_delimiter: &'static str,
     end_delimiter: &'static str,
@@ -19,6 +24,11 @@ pub struct AccessorBuilder {
 impl Default for AccessorBuilder {
     fn default() -> Self {
         AccessorBuilder {
+            constants: Value::Map(HashMap::new()),
+            env_vars: std::env::vars()
+                .filter(|(name, _)| name.starts_with(ENV_VAR_PREFIX))
+                .collect(),
+            item_allowed: false,
             id_validator: IdValidator::new(),
             start_delimiter: "${",
             end_delimiter: "}",
@@ -28,15 +38,23 @@ impl Default for AccessorBuilder {
     }
 }
 
+const CONSTANTS_KEY: &str = "constants";
 const CURRENT_RULE_EXTRACTED_VAR_SUFFIX: &str = "_variables.";
+const ENV_SUFFIX: &str = "env.";
+/// Only the environment variables with this prefix can be read by the "${env.*}" accessors.
+const ENV_VAR_PREFIX: &str = "TORNADO_";
 const EVENT_KEY: &str = "event";
 const EVENT_TYPE_KEY: &str = "event.type";
 const EVENT_CREATED_MS_KEY: &str = "event.created_ms";
 const EVENT_PAYLOAD_SUFFIX: &str = "event.payload";
+const ITEM_KEY: &str = "item";
+const NOW_MS_KEY: &str = "now.ms";
 const PAYLOAD_KEY_PARSE_REGEX: &str = r#"("[^"]+"|[^\.^\[]+|\[[^\]]+\])"#;
 const PAYLOAD_MAP_KEY_PARSE_TRAILING_DELIMITER: char = '"';
 const PAYLOAD_ARRAY_KEY_START_DELIMITER: char = '[';
 const PAYLOAD_ARRAY_KEY_END_DELIMITER: char = ']';
+const PAYLOAD_ARRAY_ALL_ITEMS_KEY: &str = "*";
+const PAYLOAD_ARRAY_FROM_END_PREFIX: char = '-';
 
 /// A builder for the Event Accessors
 impl AccessorBuilder {
@@ -44,6 +62,26 @@ impl AccessorBuilder {
         Default::default()
     }
 
+    /// Returns a new AccessorBuilder that resolves the "${constants.*}" accessors
+    /// with the given global constants.
+    pub fn with_constants(constants: HashMap<String, Value>) -> AccessorBuilder {
+        AccessorBuilder { constants: Value::Map(constants), ..Default::default() }
+    }
+
+    /// Sets the environment variables used to resolve the "${env.*}" accessors.
+    /// By default, they are the variables of the Tornado process prefixed by "TORNADO_".
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> AccessorBuilder {
+        self.env_vars = env_vars;
+        self
+    }
+
+    /// Returns a copy of this builder that also builds the "${item}" accessors.
+    /// It is used for the operands of the 'any' and 'all' operators, the only ones
+    /// where the current item is defined.
+    pub(crate) fn with_item_allowed(&self) -> AccessorBuilder {
+        AccessorBuilder { item_allowed: true, ..self.clone() }
+    }
+
     pub fn build_from_value(
         &self,
         rule_name: &str,
@@ -62,6 +100,8 @@ impl AccessorBuilder {
     /// - "${event.created_ms}": returns an instance of Accessor::CreatedTs
     /// - "${event.payload}": returns the entire Payload of the Event
     /// - "${event.payload.body}": returns an instance of Accessor::Payload that returns the value of the entry with the key "body" from the event payload
+    /// - "${item}": returns the current element of the array iterated by an 'any' or 'all' operator
+    /// - "${item.body}": returns an instance of Accessor::Item that returns the value of the entry with the key "body" from the current element
     /// - "event.type": returns an instance of Accessor::Constant that always returns the String "event.type"
     pub fn build(&self, rule_name: &str, input: &str) -> Result<Accessor, MatcherError> {
         trace!("AccessorBuilder - build: build accessor [{}] for rule [{}]", input, rule_name);
@@ -83,44 +123,44 @@ impl AccessorBuilder {
                         let keys = self.parse_payload_key(key, value, rule_name)?;
                         Ok(Accessor::Payload { keys })
                     }
-                    val if val.starts_with(CURRENT_RULE_EXTRACTED_VAR_SUFFIX) => {
-                        let key = val[CURRENT_RULE_EXTRACTED_VAR_SUFFIX.len()..].trim();
-
-                        let keys: Vec<&str> = key.split('.').collect();
-                        if keys.len() == 1 {
-                            let variable_name = keys[0];
-                            self.id_validator.validate_extracted_var_from_accessor(
-                                variable_name,
-                                value,
-                                rule_name,
-                            )?;
-                            Ok(Accessor::ExtractedVar {
-                                key: format!("{}.{}", rule_name, variable_name),
-                            })
-                        } else if keys.len() == 2 {
-                            let variable_rule = keys[0];
-                            let variable_name = keys[1];
-                            self.id_validator.validate_extracted_var_from_accessor(
-                                variable_name,
-                                value,
-                                rule_name,
-                            )?;
-                            self.id_validator.validate_extracted_var_from_accessor(
-                                variable_rule,
-                                value,
-                                rule_name,
-                            )?;
-                            Ok(Accessor::ExtractedVar {
-                                key: format!("{}.{}", variable_rule, variable_name),
-                            })
-                        } else {
-                            Err(MatcherError::NotValidIdOrNameError {
+                    val if (val.starts_with(&format!("{}.", ITEM_KEY)) || val.eq(ITEM_KEY)) => {
+                        if !self.item_allowed {
+                            return Err(MatcherError::ConfigurationError {
                                 message: format!(
-                                    "Invalid extracted variables accessor [{}] for rule [{}]",
+                                    "The accessor [{}] used by rule [{}] can only be used inside an 'any' or 'all' operator",
                                     value, rule_name
                                 ),
-                            })
+                            });
                         }
+                        let key = val[ITEM_KEY.len()..].trim();
+                        let keys = self.parse_payload_key(key, value, rule_name)?;
+                        Ok(Accessor::Item { keys })
+                    }
+                    NOW_MS_KEY => Ok(Accessor::NowMs {}),
+                    val if val.starts_with(ENV_SUFFIX) => {
+                        let name = val[ENV_SUFFIX.len()..].trim();
+                        self.build_env_var(name, value, rule_name)
+                    }
+                    val if (val.starts_with(&format!("{}.", CONSTANTS_KEY))
+                        || val.eq(CONSTANTS_KEY)) =>
+                    {
+                        let key = val[CONSTANTS_KEY.len()..].trim();
+                        let keys = self.parse_payload_key(key, value, rule_name)?;
+                        match ValueGetter::get_all(&self.constants, &keys) {
+                            Some(constant) => {
+                                Ok(Accessor::Constant { value: constant.into_owned() })
+                            }
+                            None => Err(MatcherError::ConfigurationError {
+                                message: format!(
+                                    "The constant [{}] used by rule [{}] is not defined",
+                                    value, rule_name
+                                ),
+                            }),
+                        }
+                    }
+                    val if val.starts_with(CURRENT_RULE_EXTRACTED_VAR_SUFFIX) => {
+                        let key = val[CURRENT_RULE_EXTRACTED_VAR_SUFFIX.len()..].trim();
+                        self.build_extracted_var(key, value, rule_name)
                     }
                     _ => Err(MatcherError::UnknownAccessorError { accessor: value.to_owned() }),
                 }
@@ -136,6 +176,92 @@ impl AccessorBuilder {
         result
     }
 
+    // The environment variables are resolved when the Rules are loaded, like the constants,
+    // and only the ones with the ENV_VAR_PREFIX can be read.
+    fn build_env_var(
+        &self,
+        name: &str,
+        full_accessor: &str,
+        rule_name: &str,
+    ) -> Result<Accessor, MatcherError> {
+        if !name.starts_with(ENV_VAR_PREFIX) {
+            return Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The environment variable accessor [{}] used by rule [{}] is not valid. Only the variables prefixed by [{}] can be read.",
+                    full_accessor, rule_name, ENV_VAR_PREFIX
+                ),
+            });
+        }
+        match self.env_vars.get(name) {
+            Some(value) => Ok(Accessor::Constant { value: Value::Text(value.to_owned()) }),
+            None => Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The environment variable [{}] used by rule [{}] is not defined",
+                    full_accessor, rule_name
+                ),
+            }),
+        }
+    }
+
+    /// Returns the Accessor for a path of the extracted variables. E.g.:
+    /// - "key": the variable "key" of the current rule
+    /// - "key.group" or "key[0]": an inner entry of the variable "key" of the current rule
+    /// - "rule.key": the variable "key" of the rule "rule"
+    ///
+    /// As a path like "first.second" can refer both to the entry "second" of the variable "first"
+    /// of the current rule and to the variable "second" of the rule "first", the former is
+    /// evaluated first, so that the variables of other rules never shadow the current rule's ones.
+    fn build_extracted_var(
+        &self,
+        key: &str,
+        full_accessor: &str,
+        rule_name: &str,
+    ) -> Result<Accessor, MatcherError> {
+        let mut keys = self.parse_payload_key(key, full_accessor, rule_name)?.into_iter();
+        let variable_name = match keys.next() {
+            Some(ValueGetter::Map { key }) => key,
+            _ => {
+                return Err(MatcherError::NotValidIdOrNameError {
+                    message: format!(
+                        "Invalid extracted variables accessor [{}] for rule [{}]",
+                        full_accessor, rule_name
+                    ),
+                })
+            }
+        };
+        self.id_validator.validate_extracted_var_from_accessor(
+            &variable_name,
+            full_accessor,
+            rule_name,
+        )?;
+        let inner_keys: Vec<ValueGetter> = keys.collect();
+
+        let current_rule_var = Accessor::ExtractedVar {
+            key: format!("{}.{}", rule_name, variable_name),
+            keys: inner_keys.clone(),
+        };
+
+        match inner_keys.split_first() {
+            Some((ValueGetter::Map { key: other_variable_name }, other_keys))
+                if self
+                    .id_validator
+                    .validate_extracted_var_from_accessor(
+                        other_variable_name,
+                        full_accessor,
+                        rule_name,
+                    )
+                    .is_ok() =>
+            {
+                let other_rule_var = Accessor::ExtractedVar {
+                    key: format!("{}.{}", variable_name, other_variable_name),
+                    keys: other_keys.to_vec(),
+                };
+                Ok(Accessor::FirstOf { accessors: vec![current_rule_var, other_rule_var] })
+            }
+            _ => Ok(current_rule_var),
+        }
+    }
+
     fn parse_payload_key(
         &self,
         key: &str,
@@ -162,6 +288,16 @@ impl AccessorBuilder {
                     if result.starts_with(PAYLOAD_ARRAY_KEY_START_DELIMITER) &&
                         result.ends_with(PAYLOAD_ARRAY_KEY_END_DELIMITER) {
                         result = result[1..(result.len() - 1)].to_string();
+                        if result == PAYLOAD_ARRAY_ALL_ITEMS_KEY {
+                            return Ok(ValueGetter::AllItems)
+                        }
+                        if result.starts_with(PAYLOAD_ARRAY_FROM_END_PREFIX) {
+                            let index = result[1..].parse::<usize>()
+                                .ok()
+                                .filter(|index| *index > 0)
+                                .ok_or_else(|| MatcherError::ParseOperatorError { message: format!("Cannot parse value [{}] to a negative index", &result) })?;
+                            return Ok(ValueGetter::ArrayFromEnd {index})
+                        }
                         let index = usize::from_str_radix(&result, 10)
                             .map_err(|err| MatcherError::ParseOperatorError { message: format!("Cannot parse value [{}] to number: {}", &result, err) })?;
                         return Ok(ValueGetter::Array {index})
@@ -183,44 +319,79 @@ impl AccessorBuilder {
 /// The following Accessors are defined:
 /// - Constant: returns a constant value regardless of the Event;
 /// - CreatedTs: returns the value of the "created_ms" field of an Event
-/// - ExtractedVar: returns the value of one extracted variable
+/// - ExtractedVar: returns the value of one extracted variable, or of an inner entry of it
+/// - FirstOf: returns the value of the first of a list of Accessors that returns a value
+/// - NowMs: returns the current time in milliseconds since the epoch
 /// - Payload: returns the value of an entry in the payload of an Event
 /// - Type: returns the value of the "type" field of an Event
 /// - Event: returns the entire Event
+/// - Item: returns the value of an entry in the current element of an 'any' or 'all' operator
 #[derive(PartialEq, Debug)]
 pub enum Accessor {
     Constant { value: Value },
     CreatedMs,
-    ExtractedVar { key: String },
+    ExtractedVar { key: String, keys: Vec<ValueGetter> },
+    FirstOf { accessors: Vec<Accessor> },
+    Item { keys: Vec<ValueGetter> },
+    NowMs,
     Payload { keys: Vec<ValueGetter> },
     Type,
     Event,
 }
 
+/// The variables read by the Accessors: the extracted variables and the current element
+/// of the array iterated by an 'any' or 'all' operator, read by the "${item}" accessor.
+/// Both are borrowed, so that the item can change without copying the variables.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractedVars<'o> {
+    vars: Option<&'o HashMap<String, Value>>,
+    item: Option<&'o Value>,
+}
+
+impl<'o> ExtractedVars<'o> {
+    pub fn new(vars: &'o HashMap<String, Value>) -> ExtractedVars<'o> {
+        ExtractedVars { vars: Some(vars), item: None }
+    }
+
+    /// Returns the given variables, if any, with a new current item that replaces the previous one.
+    pub fn with_item(
+        extracted_vars: Option<&ExtractedVars<'o>>,
+        item: &'o Value,
+    ) -> ExtractedVars<'o> {
+        ExtractedVars { vars: extracted_vars.and_then(|vars| vars.vars), item: Some(item) }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'o Value> {
+        self.vars.and_then(|vars| vars.get(key))
+    }
+
+    pub fn item(&self) -> Option<&'o Value> {
+        self.item
+    }
+}
+
 impl Accessor {
     pub fn get<'o>(
         &'o self,
         event: &'o InternalEvent,
-        extracted_vars: Option<&'o HashMap<String, Value>>,
+        extracted_vars: Option<&'o ExtractedVars<'o>>,
     ) -> Option<Cow<'o, Value>> {
         match &self {
             Accessor::Constant { value } => Some(Cow::Borrowed(&value)),
             Accessor::CreatedMs => Some(Cow::Borrowed(&event.created_ms)),
-            Accessor::ExtractedVar { key } => extracted_vars
+            Accessor::ExtractedVar { key, keys } => extracted_vars
                 .and_then(|vars| vars.get(key.as_str()))
-                .map(|value| Cow::Borrowed(value)),
-            Accessor::Payload { keys } => {
-                let mut value = Some(&event.payload);
-
-                let mut count = 0;
-
-                while count < keys.len() && value.is_some() {
-                    value = value.and_then(|val| keys[count].get(val));
-                    count += 1;
-                }
-
-                value.map(|value| Cow::Borrowed(value))
+                .and_then(|value| ValueGetter::get_all(value, keys)),
+            Accessor::FirstOf { accessors } => {
+                accessors.iter().filter_map(|accessor| accessor.get(event, extracted_vars)).next()
             }
+            Accessor::Payload { keys } => ValueGetter::get_all(&event.payload, keys),
+            Accessor::Item { keys } => extracted_vars
+                .and_then(|vars| vars.item())
+                .and_then(|value| ValueGetter::get_all(value, keys)),
+            Accessor::NowMs => Some(Cow::Owned(Value::Number(Number::PosInt(
+                Utc::now().timestamp_millis() as u64,
+            )))),
             Accessor::Type => Some(Cow::Borrowed(&event.event_type)),
             Accessor::Event => {
                 let event_value: Value = event.clone().into();
@@ -228,19 +399,73 @@ impl Accessor {
             }
         }
     }
+
+    /// Returns the keys of the extracted variables read by this accessor.
+    pub fn extracted_var_keys(&self) -> Vec<&str> {
+        match &self {
+            Accessor::ExtractedVar { key, .. } => vec![key.as_str()],
+            Accessor::FirstOf { accessors } => {
+                accessors.iter().flat_map(|accessor| accessor.extracted_var_keys()).collect()
+            }
+            _ => vec![],
+        }
+    }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum ValueGetter {
-    Map { key: String },
-    Array { index: usize },
+    Map {
+        key: String,
+    },
+    Array {
+        index: usize,
+    },
+    /// An element of an array counted from its end, where 1 is the last element
+    ArrayFromEnd {
+        index: usize,
+    },
+    /// All the elements of an array
+    AllItems,
 }
 
 impl ValueGetter {
+    /// Returns the single value selected by this getter.
+    /// As it selects many values, the 'AllItems' getter always returns None;
+    /// paths that can contain it are resolved by 'get_all'.
     pub fn get<'o>(&self, value: &'o Value) -> Option<&'o Value> {
         match self {
             ValueGetter::Map { key } => value.get_from_map(key),
             ValueGetter::Array { index } => value.get_from_array(*index),
+            ValueGetter::ArrayFromEnd { index } => match value {
+                Value::Array(array) => {
+                    array.len().checked_sub(*index).and_then(|index| array.get(index))
+                }
+                _ => None,
+            },
+            ValueGetter::AllItems => None,
+        }
+    }
+
+    /// Returns the value found by following the path of getters.
+    /// After an 'AllItems' getter, the rest of the path is applied to each element of the array
+    /// and the result is an array with the values found; the elements where the rest of the
+    /// path does not exist are skipped.
+    pub fn get_all<'o>(value: &'o Value, keys: &[ValueGetter]) -> Option<Cow<'o, Value>> {
+        match keys.split_first() {
+            None => Some(Cow::Borrowed(value)),
+            Some((ValueGetter::AllItems, other_keys)) => match value {
+                Value::Array(array) => Some(Cow::Owned(Value::Array(
+                    array
+                        .iter()
+                        .filter_map(|item| ValueGetter::get_all(item, other_keys))
+                        .map(Cow::into_owned)
+                        .collect(),
+                ))),
+                _ => None,
+            },
+            Some((getter, other_keys)) => {
+                getter.get(value).and_then(|value| ValueGetter::get_all(value, other_keys))
+            }
         }
     }
 }
@@ -494,13 +719,14 @@ mod test {
 
     #[test]
     fn should_return_value_from_extracted_var() {
-        let accessor = Accessor::ExtractedVar { key: "rule1.body".to_owned() };
+        let accessor = Accessor::ExtractedVar { key: "rule1.body".to_owned(), keys: vec![] };
 
         let event = InternalEvent::new(Event::new("event_type_string"));
         let mut extracted_vars = HashMap::new();
         extracted_vars.insert("rule1.body".to_owned(), Value::Text("body_value".to_owned()));
         extracted_vars.insert("rule1.subject".to_owned(), Value::Text("subject_value".to_owned()));
 
+        let extracted_vars = ExtractedVars::new(&extracted_vars);
         let result = accessor.get(&event, Some(&extracted_vars)).unwrap();
 
         assert_eq!("body_value", result.as_ref());
@@ -528,6 +754,7 @@ mod test {
             Value::Text("custom_subject".to_owned()),
         );
 
+        let extracted_vars = ExtractedVars::new(&extracted_vars);
         let result = accessor.get(&event, Some(&extracted_vars)).unwrap();
 
         assert_eq!("current_body", result.as_ref());
@@ -555,14 +782,182 @@ mod test {
             Value::Text("custom_subject".to_owned()),
         );
 
+        let extracted_vars = ExtractedVars::new(&extracted_vars);
         let result = accessor.get(&event, Some(&extracted_vars)).unwrap();
 
         assert_eq!("custom_body", result.as_ref());
     }
 
+    #[test]
+    fn should_return_inner_value_from_extracted_var_of_current_rule() {
+        let builder = AccessorBuilder::new();
+        let value = "${_variables.log.host}".to_owned();
+
+        let accessor = builder.build("current_rule_name", &value).unwrap();
+
+        let event = InternalEvent::new(Event::new("event_type_string"));
+        let mut log = HashMap::new();
+        log.insert("host".to_owned(), Value::Text("neteye".to_owned()));
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert("current_rule_name.log".to_owned(), Value::Map(log));
+
+        let extracted_vars = ExtractedVars::new(&extracted_vars);
+        let result = accessor.get(&event, Some(&extracted_vars)).unwrap();
+
+        assert_eq!("neteye", result.as_ref());
+    }
+
+    #[test]
+    fn should_return_inner_value_from_extracted_var_array() {
+        let builder = AccessorBuilder::new();
+        let value = "${_variables.hosts[1]}".to_owned();
+
+        let accessor = builder.build("current_rule_name", &value).unwrap();
+
+        let event = InternalEvent::new(Event::new("event_type_string"));
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert(
+            "current_rule_name.hosts".to_owned(),
+            Value::Array(vec![Value::Text("host1".to_owned()), Value::Text("host2".to_owned())]),
+        );
+
+        let extracted_vars = ExtractedVars::new(&extracted_vars);
+        let result = accessor.get(&event, Some(&extracted_vars)).unwrap();
+
+        assert_eq!("host2", result.as_ref());
+    }
+
+    #[test]
+    fn should_return_array_element_counted_from_the_end() {
+        let builder = AccessorBuilder::new();
+        let accessor = builder.build("rule", "${event.payload.items[-1]}").unwrap();
+        let first_accessor = builder.build("rule", "${event.payload.items[-3]}").unwrap();
+        let out_of_bounds_accessor = builder.build("rule", "${event.payload.items[-4]}").unwrap();
+
+        let mut event = Event::new("event_type_string");
+        event.payload.insert(
+            "items".to_owned(),
+            Value::Array(vec![
+                Value::Text("first".to_owned()),
+                Value::Text("second".to_owned()),
+                Value::Text("last".to_owned()),
+            ]),
+        );
+        let event = InternalEvent::new(event);
+
+        assert_eq!("last", accessor.get(&event, None).unwrap().as_ref());
+        assert_eq!("first", first_accessor.get(&event, None).unwrap().as_ref());
+        assert!(out_of_bounds_accessor.get(&event, None).is_none());
+    }
+
+    #[test]
+    fn should_return_an_array_with_the_values_of_all_the_elements() {
+        let builder = AccessorBuilder::new();
+        let accessor = builder.build("rule", "${event.payload.alerts[*].name}").unwrap();
+
+        let mut first_alert = HashMap::new();
+        first_alert.insert("name".to_owned(), Value::Text("disk_full".to_owned()));
+        let mut second_alert = HashMap::new();
+        second_alert.insert("severity".to_owned(), Value::Text("critical".to_owned()));
+        let mut third_alert = HashMap::new();
+        third_alert.insert("name".to_owned(), Value::Text("host_down".to_owned()));
+
+        let mut event = Event::new("event_type_string");
+        event.payload.insert(
+            "alerts".to_owned(),
+            Value::Array(vec![
+                Value::Map(first_alert),
+                Value::Map(second_alert),
+                Value::Map(third_alert),
+            ]),
+        );
+        let event = InternalEvent::new(event);
+
+        assert_eq!(
+            &Value::Array(vec![
+                Value::Text("disk_full".to_owned()),
+                Value::Text("host_down".to_owned())
+            ]),
+            accessor.get(&event, None).unwrap().as_ref()
+        );
+    }
+
+    #[test]
+    fn should_resolve_nested_wildcards() {
+        let builder = AccessorBuilder::new();
+        let accessor = builder.build("rule", "${_variables.matrix[*][*]}").unwrap();
+        let last_accessor = builder.build("rule", "${_variables.matrix[*][-1]}").unwrap();
+
+        let event = InternalEvent::new(Event::new("event_type_string"));
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert(
+            "rule.matrix".to_owned(),
+            Value::Array(vec![
+                Value::Array(vec![Value::Text("a".to_owned()), Value::Text("b".to_owned())]),
+                Value::Array(vec![Value::Text("c".to_owned())]),
+            ]),
+        );
+
+        assert_eq!(
+            &Value::Array(vec![
+                Value::Array(vec![Value::Text("a".to_owned()), Value::Text("b".to_owned())]),
+                Value::Array(vec![Value::Text("c".to_owned())]),
+            ]),
+            accessor.get(&event, Some(&ExtractedVars::new(&extracted_vars))).unwrap().as_ref()
+        );
+        assert_eq!(
+            &Value::Array(vec![Value::Text("b".to_owned()), Value::Text("c".to_owned())]),
+            last_accessor.get(&event, Some(&ExtractedVars::new(&extracted_vars))).unwrap().as_ref()
+        );
+    }
+
+    #[test]
+    fn should_return_none_if_wildcard_is_not_applied_to_an_array() {
+        let builder = AccessorBuilder::new();
+        let accessor = builder.build("rule", "${event.payload.alerts[*]}").unwrap();
+
+        let mut event = Event::new("event_type_string");
+        event.payload.insert("alerts".to_owned(), Value::Text("disk_full".to_owned()));
+
+        assert!(accessor.get(&InternalEvent::new(event), None).is_none());
+    }
+
+    #[test]
+    fn should_return_value_from_item() {
+        let accessor = Accessor::Item { keys: vec!["filename".into()] };
+
+        let event = InternalEvent::new(Event::new("event_type_string"));
+
+        let mut item = HashMap::new();
+        item.insert("filename".to_owned(), Value::Text("report.pdf".to_owned()));
+        let mut vars = HashMap::new();
+        vars.insert(ITEM_KEY.to_owned(), Value::Text("not the item".to_owned()));
+        let item = Value::Map(item);
+        let extracted_vars = ExtractedVars::new(&vars);
+
+        let vars_with_item = ExtractedVars::with_item(Some(&extracted_vars), &item);
+
+        let result = accessor.get(&event, Some(&vars_with_item)).unwrap();
+
+        assert_eq!("report.pdf", result.as_ref());
+    }
+
+    #[test]
+    fn should_return_none_if_no_item() {
+        let accessor = Accessor::Item { keys: vec![] };
+
+        let event = InternalEvent::new(Event::new("event_type_string"));
+
+        let mut vars = HashMap::new();
+        vars.insert(ITEM_KEY.to_owned(), Value::Text("not the item".to_owned()));
+
+        assert!(accessor.get(&event, None).is_none());
+        assert!(accessor.get(&event, Some(&ExtractedVars::new(&vars))).is_none());
+    }
+
     #[test]
     fn should_return_none_if_no_match() {
-        let accessor = Accessor::ExtractedVar { key: "rule1.body".to_owned() };
+        let accessor = Accessor::ExtractedVar { key: "rule1.body".to_owned(), keys: vec![] };
 
         let event = InternalEvent::new(Event::new("event_type_string"));
 
@@ -636,6 +1031,88 @@ mod test {
         )
     }
 
+    #[test]
+    fn builder_should_return_item_accessor() {
+        let builder = AccessorBuilder::new().with_item_allowed();
+
+        assert_eq!(Accessor::Item { keys: vec![] }, builder.build("", "${item}").unwrap());
+        assert_eq!(
+            Accessor::Item { keys: vec!["attachment".into(), 0.into(), "filename".into()] },
+            builder.build("", "${item.attachment[0].filename}").unwrap()
+        );
+    }
+
+    #[test]
+    fn builder_should_not_return_item_accessor_if_not_allowed() {
+        let builder = AccessorBuilder::new();
+
+        assert!(builder.build("", "${item}").is_err());
+        assert!(builder.build("", "${item.filename}").is_err());
+    }
+
+    #[test]
+    fn builder_should_resolve_env_vars() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("TORNADO_SITE_ID".to_owned(), "site_01".to_owned());
+        env_vars.insert("SITE_ID".to_owned(), "site_02".to_owned());
+        let builder = AccessorBuilder::new().with_env_vars(env_vars);
+
+        assert_eq!(
+            Accessor::Constant { value: Value::Text("site_01".to_owned()) },
+            builder.build("", "${env.TORNADO_SITE_ID}").unwrap()
+        );
+        assert!(builder.build("", "${env.TORNADO_MISSING}").is_err());
+        assert!(builder.build("", "${env.SITE_ID}").is_err());
+        assert!(builder.build("", "${env.}").is_err());
+    }
+
+    #[test]
+    fn should_return_the_current_time() {
+        let accessor = AccessorBuilder::new().build("", "${now.ms}").unwrap();
+        assert_eq!(Accessor::NowMs, accessor);
+
+        let before = Utc::now().timestamp_millis() as u64;
+        let now = accessor
+            .get(&InternalEvent::new(Event::new("event_type_string")), None)
+            .unwrap()
+            .get_number()
+            .and_then(|number| number.as_u64())
+            .unwrap();
+        let after = Utc::now().timestamp_millis() as u64;
+
+        assert!(before <= now && now <= after);
+    }
+
+    #[test]
+    fn builder_should_resolve_constants() {
+        let mut site = HashMap::new();
+        site.insert("id".to_owned(), Value::Text("site_01".to_owned()));
+        site.insert("hosts".to_owned(), Value::Array(vec![Value::Text("host_01".to_owned())]));
+        let mut constants = HashMap::new();
+        constants.insert("site".to_owned(), Value::Map(site));
+        constants.insert("port".to_owned(), Value::Number(Number::PosInt(4747)));
+        let builder = AccessorBuilder::with_constants(constants);
+
+        assert_eq!(
+            Accessor::Constant { value: Value::Text("site_01".to_owned()) },
+            builder.build("", "${constants.site.id}").unwrap()
+        );
+        assert_eq!(
+            Accessor::Constant { value: Value::Text("host_01".to_owned()) },
+            builder.build("", "${constants.site.hosts[-1]}").unwrap()
+        );
+        assert_eq!(
+            Accessor::Constant { value: Value::Number(Number::PosInt(4747)) },
+            builder.build("", "${constants.port}").unwrap()
+        );
+    }
+
+    #[test]
+    fn builder_should_fail_if_constant_is_not_defined() {
+        let builder = AccessorBuilder::new();
+        assert!(builder.build("", "${constants.site_id}").is_err());
+    }
+
     #[test]
     fn builder_should_return_current_rule_extracted_var_accessor() {
         let builder = AccessorBuilder::new();
@@ -643,7 +1120,10 @@ mod test {
 
         let accessor = builder.build("current_rule_name", &value).unwrap();
 
-        assert_eq!(Accessor::ExtractedVar { key: "current_rule_name.key".to_owned() }, accessor)
+        assert_eq!(
+            Accessor::ExtractedVar { key: "current_rule_name.key".to_owned(), keys: vec![] },
+            accessor
+        )
     }
 
     #[test]
@@ -653,7 +1133,82 @@ mod test {
 
         let accessor = builder.build("current_rule_name", &value).unwrap();
 
-        assert_eq!(Accessor::ExtractedVar { key: "custom_rule.key".to_owned() }, accessor)
+        assert_eq!(
+            Accessor::FirstOf {
+                accessors: vec![
+                    Accessor::ExtractedVar {
+                        key: "current_rule_name.custom_rule".to_owned(),
+                        keys: vec!["key".into()]
+                    },
+                    Accessor::ExtractedVar { key: "custom_rule.key".to_owned(), keys: vec![] },
+                ]
+            },
+            accessor
+        )
+    }
+
+    #[test]
+    fn should_prefer_the_current_rule_variable_to_the_other_rule_one() {
+        let builder = AccessorBuilder::new();
+        let accessor = builder.build("current_rule_name", "${_variables.log.host}").unwrap();
+        let event = InternalEvent::new(Event::new("event_type_string"));
+
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert("log.host".to_owned(), Value::Text("other_rule".to_owned()));
+        assert_eq!(
+            "other_rule",
+            accessor.get(&event, Some(&ExtractedVars::new(&extracted_vars))).unwrap().as_ref()
+        );
+
+        let mut log = HashMap::new();
+        log.insert("host".to_owned(), Value::Text("current_rule".to_owned()));
+        extracted_vars.insert("current_rule_name.log".to_owned(), Value::Map(log));
+        assert_eq!(
+            "current_rule",
+            accessor.get(&event, Some(&ExtractedVars::new(&extracted_vars))).unwrap().as_ref()
+        );
+    }
+
+    #[test]
+    fn builder_should_return_extracted_var_accessor_with_inner_keys() {
+        let builder = AccessorBuilder::new();
+
+        assert_eq!(
+            Accessor::ExtractedVar {
+                key: "current_rule_name.key".to_owned(),
+                keys: vec![0.into(), "group".into()]
+            },
+            builder.build("current_rule_name", "${_variables.key[0].group}").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_return_the_keys_of_the_extracted_vars_read_by_the_accessor() {
+        let builder = AccessorBuilder::new();
+
+        assert_eq!(
+            vec!["current_rule_name.custom_rule", "custom_rule.key"],
+            builder
+                .build("current_rule_name", "${_variables.custom_rule.key}")
+                .unwrap()
+                .extracted_var_keys()
+        );
+        assert_eq!(
+            vec!["current_rule_name.key"],
+            builder.build("current_rule_name", "${_variables.key}").unwrap().extracted_var_keys()
+        );
+        assert!(builder
+            .build("current_rule_name", "${event.payload.key}")
+            .unwrap()
+            .extracted_var_keys()
+            .is_empty());
+    }
+
+    #[test]
+    fn builder_should_fail_if_extracted_var_accessor_starts_with_an_index() {
+        let builder = AccessorBuilder::new();
+        assert!(builder.build("current_rule_name", "${_variables.[0]}").is_err());
+        assert!(builder.build("current_rule_name", "${_variables.key!}").is_err());
     }
 
     #[test]
@@ -751,7 +1306,7 @@ mod test {
     #[test]
     fn builder_should_return_error_if_wrong_extracted_var_name() {
         let builder = AccessorBuilder::new();
-        let value = "${_variables.not.valid.at.all}";
+        let value = "${_variables.not!valid.at.all}";
 
         let accessor = builder.build("", value);
 
@@ -759,7 +1314,7 @@ mod test {
 
         match accessor.err().unwrap() {
             MatcherError::NotValidIdOrNameError { message } => {
-                assert!(message.contains("${_variables.not.valid.at.all}"));
+                assert!(message.contains("${_variables.not!valid.at.all}"));
             }
             _ => assert!(false),
         };
@@ -868,4 +1423,24 @@ mod test {
             vec!["hello".into(), "world".into(), 11.into(), "inner".into(), 0.into()];
         assert_eq!(expected, builder.parse_payload_key("hello.world[11].inner[0]", "", "").unwrap())
     }
+
+    #[test]
+    fn builder_parser_should_return_wildcard_and_negative_index_readers() {
+        let builder = AccessorBuilder::new();
+        let expected: Vec<ValueGetter> = vec![
+            "alerts".into(),
+            ValueGetter::AllItems,
+            "labels".into(),
+            ValueGetter::ArrayFromEnd { index: 2 },
+        ];
+        assert_eq!(expected, builder.parse_payload_key("alerts[*].labels[-2]", "", "").unwrap())
+    }
+
+    #[test]
+    fn builder_parser_should_fail_if_not_valid_negative_index() {
+        let builder = AccessorBuilder::new();
+        assert!(builder.parse_payload_key("alerts[-0]", "", "").is_err());
+        assert!(builder.parse_payload_key("alerts[-]", "", "").is_err());
+        assert!(builder.parse_payload_key("alerts[--1]", "", "").is_err());
+    }
 }
@@ -0,0 +1,338 @@
+use crate::error::MatcherError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use yaml_rust::yaml::Hash;
+use yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+
+/// The formats supported for the configuration files.
+/// The format of a file is determined by its extension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Returns the format of a file and the length of its extension,
+    /// or None if the extension is not supported.
+    pub fn from_filename(filename: &str) -> Option<(ConfigFormat, usize)> {
+        let extensions = [
+            (".json", ConfigFormat::Json),
+            (".yaml", ConfigFormat::Yaml),
+            (".yml", ConfigFormat::Yaml),
+            (".toml", ConfigFormat::Toml),
+        ];
+        extensions
+            .iter()
+            .find(|(extension, _)| filename.ends_with(extension))
+            .map(|(extension, format)| (*format, extension.len()))
+    }
+
+    /// Deserializes the content of a file into a config struct.
+    pub fn deserialize<T: DeserializeOwned>(self, content: &str) -> Result<T, MatcherError> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(content)
+                .map_err(|e| MatcherError::JsonDeserializationError { message: format!("{}", e) }),
+            ConfigFormat::Yaml => yaml_to_json(content).and_then(|json| {
+                serde_json::from_value(json).map_err(|e| MatcherError::ConfigurationError {
+                    message: format!("Cannot deserialize the YAML content. Err: {}", e),
+                })
+            }),
+            ConfigFormat::Toml => {
+                toml::from_str(content).map_err(|e| MatcherError::ConfigurationError {
+                    message: format!("Cannot deserialize the TOML content. Err: {}", e),
+                })
+            }
+        }
+    }
+
+    /// Serializes a config struct into the content of a file.
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<String, MatcherError> {
+        match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value)
+                .map_err(|e| MatcherError::JsonDeserializationError { message: format!("{}", e) }),
+            ConfigFormat::Yaml => to_json_value(value).and_then(|json| json_to_yaml(&json)),
+            ConfigFormat::Toml => to_json_value(value).and_then(|json| {
+                let toml = json_value_to_toml(json)?
+                    .unwrap_or_else(|| toml::Value::Table(toml::value::Table::new()));
+                toml::to_string_pretty(&toml).map_err(|e| MatcherError::ConfigurationError {
+                    message: format!("Cannot serialize the TOML content. Err: {}", e),
+                })
+            }),
+        }
+    }
+}
+
+fn to_json_value<T: Serialize>(value: &T) -> Result<Value, MatcherError> {
+    serde_json::to_value(value)
+        .map_err(|e| MatcherError::JsonDeserializationError { message: format!("{}", e) })
+}
+
+// The YAML content is converted to a JSON value so that it can be deserialized
+// by serde into the same structs used for the JSON files.
+fn yaml_to_json(content: &str) -> Result<Value, MatcherError> {
+    let mut documents =
+        YamlLoader::load_from_str(content).map_err(|e| MatcherError::ConfigurationError {
+            message: format!("Cannot parse the YAML content. Err: {}", e),
+        })?;
+    if documents.len() != 1 {
+        return Err(MatcherError::ConfigurationError {
+            message: format!("Expected one YAML document, found {}", documents.len()),
+        });
+    }
+    yaml_value_to_json(documents.remove(0))
+}
+
+fn yaml_value_to_json(yaml: Yaml) -> Result<Value, MatcherError> {
+    Ok(match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(value) => Value::Bool(value),
+        Yaml::Integer(value) => Value::Number(value.into()),
+        Yaml::Real(value) => {
+            value.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number).ok_or_else(
+                || MatcherError::ConfigurationError {
+                    message: format!("[{}] is not a valid YAML number", value),
+                },
+            )?
+        }
+        Yaml::String(value) => Value::String(value),
+        Yaml::Array(array) => {
+            Value::Array(array.into_iter().map(yaml_value_to_json).collect::<Result<_, _>>()?)
+        }
+        Yaml::Hash(hash) => {
+            let mut map = Map::new();
+            for (key, value) in hash {
+                let key = match key {
+                    Yaml::String(key) => key,
+                    Yaml::Integer(key) => key.to_string(),
+                    Yaml::Real(key) => key,
+                    Yaml::Boolean(key) => key.to_string(),
+                    other => {
+                        return Err(MatcherError::ConfigurationError {
+                            message: format!("[{:?}] is not a valid YAML map key", other),
+                        })
+                    }
+                };
+                map.insert(key, yaml_value_to_json(value)?);
+            }
+            Value::Object(map)
+        }
+        Yaml::Alias(_) | Yaml::BadValue => {
+            return Err(MatcherError::ConfigurationError {
+                message: "YAML aliases and invalid values are not supported".to_owned(),
+            })
+        }
+    })
+}
+
+fn json_to_yaml(json: &Value) -> Result<String, MatcherError> {
+    let mut content = String::new();
+    YamlEmitter::new(&mut content).dump(&json_value_to_yaml(json)).map_err(|e| {
+        MatcherError::ConfigurationError {
+            message: format!("Cannot serialize the YAML content. Err: {:?}", e),
+        }
+    })?;
+    content.push('\n');
+    Ok(content)
+}
+
+fn json_value_to_yaml(json: &Value) -> Yaml {
+    match json {
+        Value::Null => Yaml::Null,
+        Value::Bool(value) => Yaml::Boolean(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => Yaml::Integer(value),
+            // The Debug format keeps the decimal point, so that the value is read back as a float
+            None => Yaml::Real(format!("{:?}", number.as_f64().unwrap_or_default())),
+        },
+        Value::String(value) => Yaml::String(value.clone()),
+        Value::Array(array) => Yaml::Array(array.iter().map(json_value_to_yaml).collect()),
+        Value::Object(map) => {
+            let mut hash = Hash::new();
+            for (key, value) in map {
+                hash.insert(Yaml::String(key.clone()), json_value_to_yaml(value));
+            }
+            Yaml::Hash(hash)
+        }
+    }
+}
+
+// TOML has no null value, so the null fields are omitted, as they would be by the
+// optional fields of the config structs. A null value inside an array is an error.
+fn json_value_to_toml(json: Value) -> Result<Option<toml::Value>, MatcherError> {
+    Ok(match json {
+        Value::Null => None,
+        Value::Bool(value) => Some(toml::Value::Boolean(value)),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => Some(toml::Value::Integer(value)),
+            None if number.is_u64() => {
+                return Err(MatcherError::ConfigurationError {
+                    message: format!("[{}] is too big to be written as a TOML integer", number),
+                })
+            }
+            None => Some(toml::Value::Float(number.as_f64().unwrap_or_default())),
+        },
+        Value::String(value) => Some(toml::Value::String(value)),
+        Value::Array(array) => {
+            let mut values = vec![];
+            for value in array {
+                values.push(json_value_to_toml(value)?.ok_or_else(|| {
+                    MatcherError::ConfigurationError {
+                        message: "A TOML array cannot contain null values".to_owned(),
+                    }
+                })?);
+            }
+            Some(toml::Value::Array(values))
+        }
+        Value::Object(map) => {
+            let mut table = toml::value::Table::new();
+            for (key, value) in map {
+                if let Some(value) = json_value_to_toml(value)? {
+                    table.insert(key, value);
+                }
+            }
+            Some(toml::Value::Table(table))
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::config::filter::Filter;
+    use crate::config::rule::Rule;
+
+    #[test]
+    fn should_detect_the_format_from_the_filename() {
+        assert_eq!(Some((ConfigFormat::Json, 5)), ConfigFormat::from_filename("001_rule.json"));
+        assert_eq!(Some((ConfigFormat::Yaml, 5)), ConfigFormat::from_filename("001_rule.yaml"));
+        assert_eq!(Some((ConfigFormat::Yaml, 4)), ConfigFormat::from_filename("001_rule.yml"));
+        assert_eq!(Some((ConfigFormat::Toml, 5)), ConfigFormat::from_filename("001_rule.toml"));
+        assert_eq!(None, ConfigFormat::from_filename("001_rule.json_disabled"));
+        assert_eq!(None, ConfigFormat::from_filename("001_rule"));
+    }
+
+    #[test]
+    fn should_deserialize_the_same_rule_from_every_format() {
+        let json = r#"{
+            "description": "emails",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WHERE": { "type": "equal", "first": "${event.type}", "second": "email" },
+                "WITH": {
+                    "temperature": {
+                        "from": "${event.payload.body}",
+                        "regex": { "match": "([0-9]+\\sDegrees)", "group_match_idx": 1 }
+                    }
+                }
+            },
+            "actions": [
+                { "id": "logger", "payload": { "threshold": 1.5, "retries": 3, "tags": ["a", "b"] } }
+            ]
+        }"#;
+        let yaml = r#"
+description: emails
+continue: true
+active: true
+constraint:
+  WHERE:
+    type: equal
+    first: "${event.type}"
+    second: email
+  WITH:
+    temperature:
+      from: "${event.payload.body}"
+      regex:
+        match: '([0-9]+\sDegrees)'
+        group_match_idx: 1
+actions:
+  - id: logger
+    payload:
+      threshold: 1.5
+      retries: 3
+      tags: [a, b]
+"#;
+        let toml = r#"
+description = "emails"
+continue = true
+active = true
+
+[constraint.WHERE]
+type = "equal"
+first = "${event.type}"
+second = "email"
+
+[constraint.WITH.temperature]
+from = "${event.payload.body}"
+regex = { match = '([0-9]+\sDegrees)', group_match_idx = 1 }
+
+[[actions]]
+id = "logger"
+payload = { threshold = 1.5, retries = 3, tags = ["a", "b"] }
+"#;
+
+        let from_json: Rule = ConfigFormat::Json.deserialize(json).unwrap();
+        let from_yaml: Rule = ConfigFormat::Yaml.deserialize(yaml).unwrap();
+        let from_toml: Rule = ConfigFormat::Toml.deserialize(toml).unwrap();
+
+        let expected = serde_json::to_value(&from_json).unwrap();
+        assert_eq!(expected, serde_json::to_value(&from_yaml).unwrap());
+        assert_eq!(expected, serde_json::to_value(&from_toml).unwrap());
+    }
+
+    #[test]
+    fn should_deserialize_a_filter_without_optional_fields() {
+        let filter: Filter = ConfigFormat::Yaml
+            .deserialize(
+                r#"
+description: only emails
+active: true
+"#,
+            )
+            .unwrap();
+        assert!(filter.active);
+        assert!(filter.filter.is_none());
+    }
+
+    #[test]
+    fn should_read_back_the_serialized_rule_in_every_format() {
+        let rule: Rule = serde_json::from_str(
+            r#"{
+            "name": "emails",
+            "description": "emails",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WHERE": { "type": "equal", "first": "${event.type}", "second": "true" },
+                "WITH": {}
+            },
+            "actions": [
+                { "id": "logger", "payload": { "threshold": 1.0, "retries": 3, "tags": ["a", "10"], "empty": null } }
+            ]
+        }"#,
+        )
+        .unwrap();
+
+        for format in &[ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml] {
+            let content = format.serialize(&rule).unwrap();
+            let read: Rule = format.deserialize(&content).unwrap();
+            let mut expected = serde_json::to_value(&rule).unwrap();
+            if *format == ConfigFormat::Toml {
+                expected["actions"][0]["payload"].as_object_mut().unwrap().remove("empty");
+            }
+            assert_eq!(expected, serde_json::to_value(&read).unwrap(), "format: {:?}", format);
+        }
+    }
+
+    #[test]
+    fn should_fail_if_the_content_is_not_valid() {
+        assert!(ConfigFormat::Yaml.deserialize::<Filter>("description: [unclosed").is_err());
+        assert!(ConfigFormat::Yaml.deserialize::<Filter>("description: missing_active").is_err());
+        assert!(ConfigFormat::Yaml.deserialize::<Filter>("---\na: 1\n---\nb: 2").is_err());
+        assert!(ConfigFormat::Toml.deserialize::<Filter>("description = ").is_err());
+    }
+}
@@ -1,22 +1,87 @@
 use crate::config::filter::Filter;
+use crate::config::format::ConfigFormat;
 use crate::config::rule::Rule;
-use crate::config::{MatcherConfig, MatcherConfigManager};
+use crate::config::template::{resolve_action_templates, ActionTemplate};
+use crate::config::{DraftEdit, MatcherConfig, MatcherConfigManager};
 use crate::error::MatcherError;
+use crate::matcher::operator::OperatorBuilder;
+use crate::matcher::Matcher;
+use crate::validator::MatcherConfigValidator;
 use log::*;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
 use std::fs::DirEntry;
+use std::os::unix::fs::symlink;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
 
 pub const ROOT_NODE_NAME: &str = "root";
-
+pub const DEFAULT_VERSIONS_TO_KEEP: usize = 5;
+
+const DRAFT_DIR_SUFFIX: &str = ".draft";
+const VERSIONS_DIR_SUFFIX: &str = ".versions";
+const TMP_SUFFIX: &str = ".tmp";
+const FILTER_FILENAME: &str = "filter";
+const DEFAULT_EXTENSION: &str = ".json";
+
+// The extensions of the files of a configuration, indexed by the path of the filter
+// directory or of the rule, relative to the root directory.
+type FileExtensions = HashMap<PathBuf, String>;
+
+/// A MatcherConfigManager that reads the processing tree from a directory of the file system.
+/// The draft and the deployed versions are saved in two directories beside the
+/// root one, respectively suffixed by '.draft' and '.versions'.
+/// The root path is a symbolic link to the active version, so that deploy and rollback
+/// replace the configuration with a single atomic rename. A root directory is converted
+/// into a link by prepare_versions.
 pub struct FsMatcherConfigManager {
     root_path: String,
+    action_templates_path: Option<String>,
+    operator_builder: OperatorBuilder,
+    versions_to_keep: usize,
+    write_lock: Mutex<()>,
 }
 
 impl FsMatcherConfigManager {
     pub fn new<P: Into<String>>(root_path: P) -> FsMatcherConfigManager {
-        FsMatcherConfigManager { root_path: root_path.into() }
+        FsMatcherConfigManager {
+            root_path: root_path.into(),
+            action_templates_path: None,
+            operator_builder: OperatorBuilder::new(),
+            versions_to_keep: DEFAULT_VERSIONS_TO_KEEP,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Returns a new FsMatcherConfigManager that resolves the action templates referenced
+    /// by the Rules with the ones defined in the action_templates_path folder.
+    /// A missing action_templates_path folder is equivalent to an empty one.
+    pub fn with_action_templates<P: Into<String>, A: Into<String>>(
+        root_path: P,
+        action_templates_path: A,
+    ) -> FsMatcherConfigManager {
+        FsMatcherConfigManager {
+            action_templates_path: Some(action_templates_path.into()),
+            ..FsMatcherConfigManager::new(root_path)
+        }
+    }
+
+    /// Sets the OperatorBuilder used to build a Matcher from the drafts before they are
+    /// saved or deployed, so that only the drafts that can be loaded are accepted.
+    pub fn with_operator_builder(
+        mut self,
+        operator_builder: OperatorBuilder,
+    ) -> FsMatcherConfigManager {
+        self.operator_builder = operator_builder;
+        self
+    }
+
+    /// Sets how many of the previously deployed configurations are kept for rollback.
+    pub fn with_versions_to_keep(mut self, versions_to_keep: usize) -> FsMatcherConfigManager {
+        self.versions_to_keep = versions_to_keep;
+        self
     }
 }
 
@@ -28,7 +93,130 @@ pub enum DirType {
 
 impl MatcherConfigManager for FsMatcherConfigManager {
     fn read(&self) -> Result<MatcherConfig, MatcherError> {
-        FsMatcherConfigManager::read_from_root_dir(&self.root_path)
+        let config = FsMatcherConfigManager::read_from_root_dir(&self.root_path)?;
+        self.resolve_action_templates(config)
+    }
+
+    // Moves the root directory into the versions directory and replaces it with a link.
+    // The root path does not exist between the two renames, which is why this is done
+    // only once at startup.
+    fn prepare_versions(&self) -> Result<(), MatcherError> {
+        let _lock = self.lock()?;
+        self.recover_root_link()?;
+        let root_path = Path::new(&self.root_path);
+        if !root_path.exists() || self.linked_version()?.is_some() {
+            return Ok(());
+        }
+
+        let versions_path = self.versions_path()?;
+        FsMatcherConfigManager::create_dir_all(&versions_path)?;
+        let version = FsMatcherConfigManager::next_version(&versions_path)?;
+        let version_path = versions_path.join(version.to_string());
+        // The link is created before moving the root path, so that recover_root_link
+        // can complete the operation if it is interrupted
+        let link_path = self.create_version_link(version)?;
+        FsMatcherConfigManager::rename(root_path, &version_path)?;
+        FsMatcherConfigManager::rename(&link_path, root_path)?;
+        info!("Configuration in [{}] moved to [{}]", root_path.display(), version_path.display());
+        Ok(())
+    }
+
+    fn get_draft(&self) -> Result<MatcherConfig, MatcherError> {
+        let _lock = self.lock()?;
+        self.read_draft()
+    }
+
+    fn update_draft(&self, config: &MatcherConfig) -> Result<(), MatcherError> {
+        let _lock = self.lock()?;
+        self.validate(config.clone())?;
+        self.write_draft(config)
+    }
+
+    fn edit_draft(&self, edit: DraftEdit<'_>) -> Result<MatcherConfig, MatcherError> {
+        let _lock = self.lock()?;
+        let mut draft = self.read_draft()?;
+        edit(&mut draft)?;
+        self.validate(draft.clone())?;
+        self.write_draft(&draft)?;
+        Ok(draft)
+    }
+
+    fn delete_draft(&self) -> Result<(), MatcherError> {
+        let _lock = self.lock()?;
+        let draft_path = self.draft_path()?;
+        if draft_path.exists() {
+            info!("Deleting the draft in [{}]", draft_path.display());
+            FsMatcherConfigManager::remove_dir(&draft_path)?;
+        }
+        Ok(())
+    }
+
+    fn deploy_draft(&self) -> Result<MatcherConfig, MatcherError> {
+        let _lock = self.lock()?;
+        let draft_path = self.draft_path()?;
+        if !draft_path.exists() {
+            return Err(MatcherError::ConfigurationError {
+                message: format!("There is no draft to deploy in [{}]", draft_path.display()),
+            });
+        }
+        let config = self.validate(FsMatcherConfigManager::read_from_root_dir(&draft_path)?)?;
+
+        // Makes sure that the current configuration is kept among the versions
+        self.active_version()?;
+        let versions_path = self.versions_path()?;
+        FsMatcherConfigManager::create_dir_all(&versions_path)?;
+        let version = FsMatcherConfigManager::next_version(&versions_path)?;
+        let version_path = versions_path.join(version.to_string());
+        FsMatcherConfigManager::rename(&draft_path, &version_path)?;
+        self.link_root_to_version(version)?;
+        info!("Draft deployed in [{}]", version_path.display());
+
+        self.remove_old_versions()?;
+        Ok(config)
+    }
+
+    fn rollback(&self) -> Result<MatcherConfig, MatcherError> {
+        let _lock = self.lock()?;
+        let active_version = self.active_version()?;
+        let versions_path = self.versions_path()?;
+        let previous_version = FsMatcherConfigManager::read_versions(&versions_path)?
+            .into_iter()
+            .rev()
+            .find(|version| match active_version {
+                Some(active_version) => *version < active_version,
+                None => true,
+            });
+        let previous_version = match previous_version {
+            Some(version) => version,
+            None => {
+                return Err(MatcherError::ConfigurationError {
+                    message: format!(
+                        "There are no previous configurations in [{}] to roll back to",
+                        versions_path.display()
+                    ),
+                })
+            }
+        };
+        let previous_version_path = versions_path.join(previous_version.to_string());
+        let config = self.resolve_action_templates(FsMatcherConfigManager::read_from_root_dir(
+            &previous_version_path,
+        )?)?;
+
+        // The replaced configuration stays among the versions and it is also copied
+        // into the draft, unless a draft already exists
+        if let Some(active_version) = active_version {
+            if !self.draft_path()?.exists() {
+                self.copy_to_draft(versions_path.join(active_version.to_string()))?;
+            }
+        }
+        self.link_root_to_version(previous_version)?;
+        info!(
+            "Configuration in [{}] restored from [{}]",
+            self.root_path,
+            previous_version_path.display()
+        );
+
+        Ok(config)
     }
 }
 
@@ -60,7 +248,7 @@ impl FsMatcherConfigManager {
         let paths = FsMatcherConfigManager::read_dir_entries(dir.as_ref())?;
 
         let mut subdirectories_count = 0;
-        let mut json_files_count = 0;
+        let mut config_files_count = 0;
 
         for entry in paths {
             let path = entry.path();
@@ -69,29 +257,29 @@ impl FsMatcherConfigManager {
                 subdirectories_count += 1;
             } else {
                 let filename = FsMatcherConfigManager::filename(&path)?;
-                if filename.ends_with(".json") {
-                    json_files_count += 1;
+                if ConfigFormat::from_filename(filename).is_some() {
+                    config_files_count += 1;
                 }
             }
         }
         debug!(
             "Path {} contains {} file(s) and {} directories",
             dir.as_ref().display(),
-            json_files_count,
+            config_files_count,
             subdirectories_count
         );
 
         if subdirectories_count > 0 {
-            if json_files_count <= 1 {
+            if config_files_count <= 1 {
                 return Ok(DirType::Filter);
             }
             return Err(MatcherError::ConfigurationError {
                 message: format!(
                     r#"Path {} contains {} file(s) and {} directories. Expected:\n
-                 for a valid filter: max one json file and at least one directory;\n
-                 for a valid rule set: zero or more json files and no directories."#,
+                 for a valid filter: max one configuration file and at least one directory;\n
+                 for a valid rule set: zero or more configuration files and no directories."#,
                     dir.as_ref().display(),
-                    json_files_count,
+                    config_files_count,
                     subdirectories_count
                 ),
             });
@@ -111,12 +299,14 @@ impl FsMatcherConfigManager {
             let path = entry.path();
 
             let filename = FsMatcherConfigManager::filename(&path)?;
-            let extension = ".json";
 
-            if !filename.ends_with(extension) {
-                warn!("Configuration file [{}] is ignored.", path.display());
-                continue;
-            }
+            let (format, extension_len) = match ConfigFormat::from_filename(filename) {
+                Some(format) => format,
+                None => {
+                    warn!("Configuration file [{}] is ignored.", path.display());
+                    continue;
+                }
+            };
 
             debug!("Loading rule from file: [{}]", path.display());
             let rule_body =
@@ -125,8 +315,8 @@ impl FsMatcherConfigManager {
                 })?;
 
             trace!("Rule body: \n{}", rule_body);
-            let mut rule =
-                Rule::from_json(&rule_body).map_err(|e| MatcherError::ConfigurationError {
+            let mut rule: Rule =
+                format.deserialize(&rule_body).map_err(|e| MatcherError::ConfigurationError {
                     message: format!(
                         "Error building Rule from file [{}]. Err: {}",
                         path.display(),
@@ -134,8 +324,11 @@ impl FsMatcherConfigManager {
                     ),
                 })?;
             rule.name = FsMatcherConfigManager::rule_name_from_filename(
-                &FsMatcherConfigManager::truncate(filename, extension.len()),
-            )?
+                &FsMatcherConfigManager::truncate(filename, extension_len),
+            )
+            .map_err(|e| MatcherError::ConfigurationError {
+                message: format!("Error building Rule from file [{}]. Err: {}", path.display(), e),
+            })?
             .to_owned();
             rules.push(rule);
         }
@@ -167,11 +360,13 @@ impl FsMatcherConfigManager {
                 continue;
             }
 
-            let extension = ".json";
-            if !filename.ends_with(extension) {
-                info!("Configuration file [{}] is ignored.", path.display());
-                continue;
-            }
+            let format = match ConfigFormat::from_filename(filename) {
+                Some((format, _)) => format,
+                None => {
+                    info!("Configuration file [{}] is ignored.", path.display());
+                    continue;
+                }
+            };
 
             info!("Loading filter from file: [{}]", path.display());
             let filter_body =
@@ -180,8 +375,8 @@ impl FsMatcherConfigManager {
                 })?;
 
             trace!("Filter [{}] body: \n{}", filename, filter_body);
-            let filter =
-                Filter::from_json(&filter_body).map_err(|e| MatcherError::ConfigurationError {
+            let filter: Filter =
+                format.deserialize(&filter_body).map_err(|e| MatcherError::ConfigurationError {
                     message: format!(
                         "Error building Filter from file [{}]. Err: {}",
                         path.display(),
@@ -208,11 +403,465 @@ impl FsMatcherConfigManager {
         }
 
         Err(MatcherError::ConfigurationError {
-            message: format!("Config path [{}] contains {} json files and {} subdirectories. Expected exactly one json filter file and at least one subdirectory.",
+            message: format!("Config path [{}] contains {} filter files and {} subdirectories. Expected exactly one filter file and at least one subdirectory.",
                              dir.as_ref().display(), filters.len(), nodes.len()),
         })
     }
 
+    /// Reads the ActionTemplates from the configuration files of a directory.
+    /// The name of each template is the filename without the extension.
+    fn read_action_templates_from_dir<P: AsRef<Path>>(
+        dir: P,
+    ) -> Result<HashMap<String, ActionTemplate>, MatcherError> {
+        let mut templates = HashMap::new();
+
+        if !dir.as_ref().exists() {
+            info!(
+                "Action templates path [{}] does not exist. No templates are loaded.",
+                dir.as_ref().display()
+            );
+            return Ok(templates);
+        }
+
+        for entry in FsMatcherConfigManager::read_dir_entries(dir.as_ref())? {
+            let path = entry.path();
+
+            let filename = FsMatcherConfigManager::filename(&path)?;
+
+            let (format, extension_len) = match ConfigFormat::from_filename(filename) {
+                Some(format) if !path.is_dir() => format,
+                _ => {
+                    warn!("Configuration file [{}] is ignored.", path.display());
+                    continue;
+                }
+            };
+
+            debug!("Loading action template from file: [{}]", path.display());
+            let template_body =
+                fs::read_to_string(&path).map_err(|e| MatcherError::ConfigurationError {
+                    message: format!("Unable to open the file [{}]. Err: {}", path.display(), e),
+                })?;
+
+            trace!("Action template body: \n{}", template_body);
+            let template: ActionTemplate = format.deserialize(&template_body).map_err(|e| {
+                MatcherError::ConfigurationError {
+                    message: format!(
+                        "Error building ActionTemplate from file [{}]. Err: {}",
+                        path.display(),
+                        e
+                    ),
+                }
+            })?;
+            templates.insert(FsMatcherConfigManager::truncate(filename, extension_len), template);
+        }
+
+        MatcherConfigValidator::new().validate_action_templates(&templates)?;
+
+        info!("Loaded {} action template(s) from [{}]", templates.len(), dir.as_ref().display());
+
+        Ok(templates)
+    }
+
+    fn resolve_action_templates(
+        &self,
+        mut config: MatcherConfig,
+    ) -> Result<MatcherConfig, MatcherError> {
+        let templates = match &self.action_templates_path {
+            Some(path) => FsMatcherConfigManager::read_action_templates_from_dir(path)?,
+            None => HashMap::new(),
+        };
+        resolve_action_templates(&mut config, &templates)?;
+        Ok(config)
+    }
+
+    /// Resolves the action templates of the configuration and checks that a Matcher
+    /// can be built from the result.
+    fn validate(&self, config: MatcherConfig) -> Result<MatcherConfig, MatcherError> {
+        let config = self.resolve_action_templates(config)?;
+        Matcher::build_with_operator_builder(&config, &self.operator_builder)?;
+        Ok(config)
+    }
+
+    // Returns the current draft, creating it from the deployed configuration if it does not exist.
+    // It must be called while holding the write lock.
+    fn read_draft(&self) -> Result<MatcherConfig, MatcherError> {
+        let draft_path = self.draft_path()?;
+        if !draft_path.exists() {
+            info!("Creating a new draft in [{}]", draft_path.display());
+            self.copy_to_draft(&self.root_path)?;
+        }
+        FsMatcherConfigManager::read_from_root_dir(&draft_path)
+    }
+
+    // Collects the extensions of the files of the draft or, if it does not exist, of the
+    // deployed configuration, so that the modified files can be written in their original format.
+    fn file_extensions(&self) -> Result<FileExtensions, MatcherError> {
+        let draft_path = self.draft_path()?;
+        let dir = if draft_path.exists() { draft_path } else { PathBuf::from(&self.root_path) };
+        let mut extensions = HashMap::new();
+        if dir.exists() {
+            FsMatcherConfigManager::collect_file_extensions(&dir, Path::new(""), &mut extensions)?;
+        }
+        Ok(extensions)
+    }
+
+    fn collect_file_extensions(
+        dir: &Path,
+        relative_path: &Path,
+        extensions: &mut FileExtensions,
+    ) -> Result<(), MatcherError> {
+        let dir_type = FsMatcherConfigManager::detect_dir_type(dir)?;
+        for entry in FsMatcherConfigManager::read_dir_entries(dir)? {
+            let path = entry.path();
+            let filename = FsMatcherConfigManager::filename(&path)?;
+            if path.is_dir() {
+                FsMatcherConfigManager::collect_file_extensions(
+                    &path,
+                    &relative_path.join(filename),
+                    extensions,
+                )?;
+                continue;
+            }
+            let extension_len = match ConfigFormat::from_filename(filename) {
+                Some((_, extension_len)) => extension_len,
+                None => continue,
+            };
+            let extension = filename[filename.len() - extension_len..].to_owned();
+            match dir_type {
+                DirType::Filter => {
+                    extensions.insert(relative_path.to_path_buf(), extension);
+                }
+                DirType::Ruleset => {
+                    let name = FsMatcherConfigManager::truncate(filename, extension_len);
+                    let rule_name = FsMatcherConfigManager::rule_name_from_filename(&name)?;
+                    extensions.insert(relative_path.join(rule_name), extension);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, ()>, MatcherError> {
+        self.write_lock.lock().map_err(|e| MatcherError::ConfigurationError {
+            message: format!("Cannot acquire the configuration write lock. Err: {}", e),
+        })
+    }
+
+    fn draft_path(&self) -> Result<PathBuf, MatcherError> {
+        FsMatcherConfigManager::sibling_path(&self.root_path, DRAFT_DIR_SUFFIX)
+    }
+
+    fn versions_path(&self) -> Result<PathBuf, MatcherError> {
+        FsMatcherConfigManager::sibling_path(&self.root_path, VERSIONS_DIR_SUFFIX)
+    }
+
+    // Returns the version linked by the root path, or None if the root path does not exist.
+    // A root path that is not a link to a version must be converted by prepare_versions first.
+    fn active_version(&self) -> Result<Option<u64>, MatcherError> {
+        let root_path = Path::new(&self.root_path);
+        if fs::symlink_metadata(root_path).is_err() {
+            return Ok(None);
+        }
+        match self.linked_version()? {
+            Some(version) => Ok(Some(version)),
+            None => Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The configuration in [{}] is not a link to a deployed version. It is converted when Tornado starts.",
+                    root_path.display()
+                ),
+            }),
+        }
+    }
+
+    // Returns the version the root path links to, if it is a link to one of the versions.
+    fn linked_version(&self) -> Result<Option<u64>, MatcherError> {
+        let root_path = Path::new(&self.root_path);
+        let is_symlink = fs::symlink_metadata(root_path)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_symlink {
+            return Ok(None);
+        }
+        let target = fs::read_link(root_path).map_err(|e| MatcherError::ConfigurationError {
+            message: format!("Unable to read the link [{}]. Err: {}", root_path.display(), e),
+        })?;
+        let version = target.file_name().and_then(OsStr::to_str).and_then(|name| name.parse().ok());
+        match version {
+            Some(version) if target == self.version_link_target(version)? => Ok(Some(version)),
+            _ => Ok(None),
+        }
+    }
+
+    // Atomically replaces the root path with a link to the given version.
+    fn link_root_to_version(&self, version: u64) -> Result<(), MatcherError> {
+        let link_path = self.create_version_link(version)?;
+        FsMatcherConfigManager::rename(&link_path, &self.root_path)
+    }
+
+    // Creates a link to the given version beside the root path.
+    fn create_version_link(&self, version: u64) -> Result<PathBuf, MatcherError> {
+        let link_path = FsMatcherConfigManager::sibling_path(&self.root_path, TMP_SUFFIX)?;
+        if fs::symlink_metadata(&link_path).is_ok() {
+            fs::remove_file(&link_path).map_err(|e| MatcherError::ConfigurationError {
+                message: format!("Unable to delete the file [{}]. Err: {}", link_path.display(), e),
+            })?;
+        }
+        let target = self.version_link_target(version)?;
+        symlink(&target, &link_path).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Unable to create the link [{}] to [{}]. Err: {}",
+                link_path.display(),
+                target.display(),
+                e
+            ),
+        })?;
+        Ok(link_path)
+    }
+
+    // The links are relative, so that the configuration directory can be moved.
+    fn version_link_target(&self, version: u64) -> Result<PathBuf, MatcherError> {
+        let versions_path = self.versions_path()?;
+        let versions_dir_name = FsMatcherConfigManager::filename(&versions_path)?;
+        Ok(Path::new(versions_dir_name).join(version.to_string()))
+    }
+
+    // Restores the root path if prepare_versions stopped after moving the root directory
+    // into the versions directory, but before replacing it with the link.
+    fn recover_root_link(&self) -> Result<(), MatcherError> {
+        let root_path = Path::new(&self.root_path);
+        let link_path = FsMatcherConfigManager::sibling_path(root_path, TMP_SUFFIX)?;
+        if fs::symlink_metadata(root_path).is_err() && fs::symlink_metadata(&link_path).is_ok() {
+            warn!("Restoring the interrupted configuration update of [{}]", root_path.display());
+            FsMatcherConfigManager::rename(&link_path, root_path)?;
+        }
+        Ok(())
+    }
+
+    // Returns the path of a directory beside the given one whose name has the given suffix.
+    // E.g. the sibling of '/etc/tornado/rules.d' with suffix '.draft' is '/etc/tornado/rules.d.draft'.
+    fn sibling_path<P: AsRef<Path>>(dir: P, suffix: &str) -> Result<PathBuf, MatcherError> {
+        let dir_name = dir.as_ref().file_name().and_then(OsStr::to_str).ok_or_else(|| {
+            MatcherError::ConfigurationError {
+                message: format!("Error processing path name: [{}]", dir.as_ref().display()),
+            }
+        })?;
+        Ok(dir.as_ref().with_file_name(format!("{}{}", dir_name, suffix)))
+    }
+
+    fn write_draft(&self, config: &MatcherConfig) -> Result<(), MatcherError> {
+        let extensions = self.file_extensions()?;
+        self.replace_draft(|dir| {
+            FsMatcherConfigManager::write_to_dir(config, dir, Path::new(""), &extensions)
+        })
+    }
+
+    // Copies the files as they are, so that their format is preserved.
+    fn copy_to_draft<P: AsRef<Path>>(&self, dir: P) -> Result<(), MatcherError> {
+        self.replace_draft(|draft_dir| FsMatcherConfigManager::copy_dir(dir, draft_dir))
+    }
+
+    // Writes the draft into a temporary directory that then replaces the current draft,
+    // so that a failed write never leaves a partial draft.
+    fn replace_draft<F: FnOnce(&Path) -> Result<(), MatcherError>>(
+        &self,
+        write: F,
+    ) -> Result<(), MatcherError> {
+        let draft_path = self.draft_path()?;
+        let tmp_path = FsMatcherConfigManager::sibling_path(&draft_path, TMP_SUFFIX)?;
+        if tmp_path.exists() {
+            FsMatcherConfigManager::remove_dir(&tmp_path)?;
+        }
+        if let Err(err) = write(&tmp_path) {
+            FsMatcherConfigManager::remove_dir(&tmp_path)?;
+            return Err(err);
+        }
+        if draft_path.exists() {
+            FsMatcherConfigManager::remove_dir(&draft_path)?;
+        }
+        FsMatcherConfigManager::rename(&tmp_path, &draft_path)?;
+        info!("Draft saved in [{}]", draft_path.display());
+        Ok(())
+    }
+
+    // Writes a configuration in the same directory structure expected by read_from_dir.
+    // The rule filenames are prefixed by a progressive number that preserves the rule order.
+    // Each file keeps the extension found in `extensions`, the new files are written in JSON format.
+    fn write_to_dir<P: AsRef<Path>>(
+        config: &MatcherConfig,
+        dir: P,
+        relative_path: &Path,
+        extensions: &FileExtensions,
+    ) -> Result<(), MatcherError> {
+        let extension_of =
+            |path: PathBuf| extensions.get(&path).map(String::as_str).unwrap_or(DEFAULT_EXTENSION);
+        fs::create_dir(dir.as_ref()).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Unable to create the directory [{}]. Err: {}",
+                dir.as_ref().display(),
+                e
+            ),
+        })?;
+        match config {
+            MatcherConfig::Filter { name, filter, nodes } => {
+                if nodes.is_empty() {
+                    return Err(MatcherError::ConfigurationError {
+                        message: format!("The filter [{}] must have at least one node", name),
+                    });
+                }
+                let filename =
+                    format!("{}{}", FILTER_FILENAME, extension_of(relative_path.to_path_buf()));
+                FsMatcherConfigManager::write_file(dir.as_ref().join(filename), filter)?;
+                for node in nodes {
+                    let node_name = match node {
+                        MatcherConfig::Filter { name, .. } => name,
+                        MatcherConfig::Ruleset { name, .. } => name,
+                    };
+                    FsMatcherConfigManager::check_filename(node_name)?;
+                    FsMatcherConfigManager::write_to_dir(
+                        node,
+                        dir.as_ref().join(node_name),
+                        &relative_path.join(node_name),
+                        extensions,
+                    )?;
+                }
+            }
+            MatcherConfig::Ruleset { rules, .. } => {
+                for (index, rule) in rules.iter().enumerate() {
+                    FsMatcherConfigManager::check_filename(&rule.name)?;
+                    let filename = format!(
+                        "{:04}_{}{}",
+                        index * 10,
+                        rule.name,
+                        extension_of(relative_path.join(&rule.name))
+                    );
+                    FsMatcherConfigManager::write_file(dir.as_ref().join(filename), rule)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_dir<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<(), MatcherError> {
+        FsMatcherConfigManager::create_dir_all(to.as_ref())?;
+        for entry in FsMatcherConfigManager::read_dir_entries(from.as_ref())? {
+            let path = entry.path();
+            let target = to.as_ref().join(entry.file_name());
+            if path.is_dir() {
+                FsMatcherConfigManager::copy_dir(&path, &target)?;
+            } else {
+                fs::copy(&path, &target).map_err(|e| MatcherError::ConfigurationError {
+                    message: format!(
+                        "Unable to copy [{}] to [{}]. Err: {}",
+                        path.display(),
+                        target.display(),
+                        e
+                    ),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    // The format of the file is determined by its extension.
+    fn write_file<T: Serialize>(path: PathBuf, value: &T) -> Result<(), MatcherError> {
+        let format = ConfigFormat::from_filename(FsMatcherConfigManager::filename(&path)?)
+            .map(|(format, _)| format)
+            .unwrap_or(ConfigFormat::Json);
+        let content = format.serialize(value).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Cannot serialize the content of the file [{}]. Err: {}",
+                path.display(),
+                e
+            ),
+        })?;
+        debug!("Writing file: [{}]", path.display());
+        fs::write(&path, content).map_err(|e| MatcherError::ConfigurationError {
+            message: format!("Unable to write the file [{}]. Err: {}", path.display(), e),
+        })
+    }
+
+    // Node and rule names become file names, so they cannot contain path separators.
+    fn check_filename(name: &str) -> Result<(), MatcherError> {
+        if name.is_empty()
+            || name == "."
+            || name == ".."
+            || name.contains('/')
+            || name.contains('\\')
+        {
+            return Err(MatcherError::ConfigurationError {
+                message: format!("[{}] cannot be used as a file name", name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the numbers of the saved versions in ascending order.
+    fn read_versions<P: AsRef<Path>>(dir: P) -> Result<Vec<u64>, MatcherError> {
+        if !dir.as_ref().exists() {
+            return Ok(vec![]);
+        }
+        let mut versions = vec![];
+        for entry in FsMatcherConfigManager::read_dir_entries(dir.as_ref())? {
+            let path = entry.path();
+            if let Ok(version) = FsMatcherConfigManager::filename(&path)?.parse::<u64>() {
+                if path.is_dir() {
+                    versions.push(version);
+                }
+            }
+        }
+        versions.sort();
+        Ok(versions)
+    }
+
+    fn next_version<P: AsRef<Path>>(dir: P) -> Result<u64, MatcherError> {
+        Ok(FsMatcherConfigManager::read_versions(dir)?.last().map_or(1, |version| version + 1))
+    }
+
+    // Called after a deploy, when the last version is the active one.
+    fn remove_old_versions(&self) -> Result<(), MatcherError> {
+        let versions_path = self.versions_path()?;
+        let versions = FsMatcherConfigManager::read_versions(&versions_path)?;
+        if versions.len() > self.versions_to_keep + 1 {
+            for version in &versions[..versions.len() - self.versions_to_keep - 1] {
+                let version_path = versions_path.join(version.to_string());
+                debug!("Removing old configuration version [{}]", version_path.display());
+                FsMatcherConfigManager::remove_dir(&version_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn create_dir_all<P: AsRef<Path>>(dir: P) -> Result<(), MatcherError> {
+        fs::create_dir_all(dir.as_ref()).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Unable to create the directory [{}]. Err: {}",
+                dir.as_ref().display(),
+                e
+            ),
+        })
+    }
+
+    fn remove_dir<P: AsRef<Path>>(dir: P) -> Result<(), MatcherError> {
+        fs::remove_dir_all(dir.as_ref()).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Unable to delete the directory [{}]. Err: {}",
+                dir.as_ref().display(),
+                e
+            ),
+        })
+    }
+
+    fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<(), MatcherError> {
+        fs::rename(from.as_ref(), to.as_ref()).map_err(|e| MatcherError::ConfigurationError {
+            message: format!(
+                "Unable to move [{}] to [{}]. Err: {}",
+                from.as_ref().display(),
+                to.as_ref().display(),
+                e
+            ),
+        })
+    }
+
     fn read_dir_entries<P: AsRef<Path>>(dir: P) -> Result<Vec<DirEntry>, MatcherError> {
         let mut paths: Vec<_> =
             fs::read_dir(dir.as_ref()).and_then(Iterator::collect).map_err(|e| {
@@ -262,6 +911,7 @@ mod test {
 
     use super::*;
     use std::fs;
+    use tornado_common_api::Value;
 
     #[test]
     fn should_read_rules_from_folder_sorting_by_filename() {
@@ -542,6 +1192,582 @@ mod test {
         }
     }
 
+    #[test]
+    fn should_resolve_the_action_templates() {
+        let config = FsMatcherConfigManager::with_action_templates(
+            "./test_resources/config_action_templates/rules.d",
+            "./test_resources/config_action_templates/actions.d",
+        )
+        .read()
+        .unwrap();
+
+        match config {
+            MatcherConfig::Ruleset { rules, .. } => {
+                let action = &rules[0].actions[0];
+                assert_eq!("archive", action.id);
+                assert_eq!(None, action.template);
+                assert_eq!(&Value::Text("${event}".to_owned()), &action.payload["event"]);
+                assert_eq!(&Value::Text("emails".to_owned()), &action.payload["archive_type"]);
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn should_read_the_action_templates_in_all_the_supported_formats() {
+        let templates = FsMatcherConfigManager::read_action_templates_from_dir(
+            "./test_resources/config_action_templates/actions.d",
+        )
+        .unwrap();
+
+        assert_eq!(2, templates.len());
+        assert_eq!("archive", templates["archive"].id);
+        assert_eq!("logger", templates["notify"].id);
+    }
+
+    #[test]
+    fn should_fail_if_the_action_templates_are_not_available() {
+        let config =
+            FsMatcherConfigManager::new("./test_resources/config_action_templates/rules.d").read();
+        assert!(config.is_err());
+
+        let config = FsMatcherConfigManager::with_action_templates(
+            "./test_resources/config_action_templates/rules.d",
+            "./test_resources/config_action_templates/not_existing.d",
+        )
+        .read();
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn should_read_rules_and_filters_in_all_the_supported_formats() {
+        let path = "./test_resources/config_formats";
+        let config = FsMatcherConfigManager::new(path).read().unwrap();
+
+        match config {
+            MatcherConfig::Filter { name, filter, nodes } => {
+                assert_eq!("root", name);
+                assert!(filter.filter.is_some());
+                assert!(is_ruleset(
+                    get_config_by_name("emails", &nodes).unwrap(),
+                    "emails",
+                    &["from_yaml", "from_toml", "from_json"]
+                ));
+                assert!(is_ruleset(
+                    get_config_by_name("syslog", &nodes).unwrap(),
+                    "syslog",
+                    &["from_yml"]
+                ));
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn should_report_the_file_that_cannot_be_read() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().to_str().unwrap().to_owned();
+        fs::write(&format!("{}/0010_valid.yaml", dir), "description: [unclosed").unwrap();
+
+        // Act
+        let result = FsMatcherConfigManager::read_from_root_dir(&dir);
+
+        // Assert
+        match result {
+            Err(MatcherError::ConfigurationError { message }) => {
+                assert!(message.contains(&format!("{}/0010_valid.yaml", dir)))
+            }
+            _ => assert!(false),
+        }
+    }
+
+    fn new_rule(name: &str) -> Rule {
+        Rule {
+            name: name.to_owned(),
+            description: "".to_owned(),
+            do_continue: true,
+            active: true,
+            constraint: crate::config::rule::Constraint {
+                where_operator: None,
+                with: HashMap::new(),
+            },
+            actions: vec![],
+            tests: vec![],
+        }
+    }
+
+    fn new_filter(nodes: Vec<MatcherConfig>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: ROOT_NODE_NAME.to_owned(),
+            filter: Filter { description: "".to_owned(), active: true, filter: None },
+            nodes,
+        }
+    }
+
+    fn new_ruleset(name: &str, rule_names: &[&str]) -> MatcherConfig {
+        MatcherConfig::Ruleset {
+            name: name.to_owned(),
+            rules: rule_names.iter().map(|rule_name| new_rule(rule_name)).collect(),
+        }
+    }
+
+    fn new_deployed_config_manager(
+        tempdir: &tempfile::TempDir,
+        config: &MatcherConfig,
+    ) -> FsMatcherConfigManager {
+        let root_path = format!("{}/rules.d", tempdir.path().to_str().unwrap());
+        FsMatcherConfigManager::write_to_dir(config, &root_path, Path::new(""), &HashMap::new())
+            .unwrap();
+        let config_manager = FsMatcherConfigManager::new(root_path);
+        config_manager.prepare_versions().unwrap();
+        config_manager
+    }
+
+    #[test]
+    fn should_write_a_config_that_can_be_read_back() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = format!("{}/config", tempdir.path().to_str().unwrap());
+        let config = new_filter(vec![
+            new_ruleset("node1", &["rule_two", "rule_one"]),
+            new_filter(vec![new_ruleset("inner_node1", &["rule_three"])]),
+        ]);
+
+        // Act
+        FsMatcherConfigManager::write_to_dir(&config, &dir, Path::new(""), &HashMap::new())
+            .unwrap();
+        let result = FsMatcherConfigManager::read_from_root_dir(&dir).unwrap();
+
+        // Assert
+        assert!(is_filter(&result, "root", 2));
+        match result {
+            MatcherConfig::Filter { nodes, .. } => {
+                assert!(is_ruleset(
+                    get_config_by_name("node1", &nodes).unwrap(),
+                    "node1",
+                    &vec!["rule_two", "rule_one"]
+                ));
+                assert!(is_filter(get_config_by_name("root", &nodes).unwrap(), "root", 1));
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn should_not_write_names_that_are_not_valid_file_names() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = format!("{}/config", tempdir.path().to_str().unwrap());
+        let config = new_filter(vec![new_ruleset("../node1", &["rule_one"])]);
+
+        assert!(FsMatcherConfigManager::write_to_dir(
+            &config,
+            &dir,
+            Path::new(""),
+            &HashMap::new()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_create_the_draft_from_the_deployed_config() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+
+        // Act
+        let draft = config_manager.get_draft().unwrap();
+
+        // Assert
+        assert!(is_ruleset(&draft, "root", &["rule_one"]));
+        assert!(config_manager.draft_path().unwrap().exists());
+    }
+
+    #[test]
+    fn should_update_the_draft_without_changing_the_deployed_config() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+
+        // Act
+        config_manager.update_draft(&new_ruleset("root", &["rule_one", "rule_two"])).unwrap();
+
+        // Assert
+        assert!(is_ruleset(
+            &config_manager.get_draft().unwrap(),
+            "root",
+            &["rule_one", "rule_two"]
+        ));
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_not_save_an_invalid_draft() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+
+        // Act
+        let result = config_manager.update_draft(&new_ruleset("root", &["rule_one", "rule_one"]));
+
+        // Assert
+        assert!(result.is_err());
+        assert!(is_ruleset(&config_manager.get_draft().unwrap(), "root", &["rule_two"]));
+    }
+
+    #[test]
+    fn should_edit_the_draft() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+
+        // Act
+        let edited = config_manager
+            .edit_draft(Box::new(|draft| {
+                if let MatcherConfig::Ruleset { rules, .. } = draft {
+                    rules.push(new_rule("rule_two"));
+                }
+                Ok(())
+            }))
+            .unwrap();
+
+        // Assert
+        assert!(is_ruleset(&edited, "root", &["rule_one", "rule_two"]));
+        assert!(is_ruleset(
+            &config_manager.get_draft().unwrap(),
+            "root",
+            &["rule_one", "rule_two"]
+        ));
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_not_save_the_draft_if_the_edit_fails_or_is_not_valid() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+
+        // Act
+        let failed_edit = config_manager.edit_draft(Box::new(|draft| {
+            *draft = new_ruleset("root", &["rule_two"]);
+            Err(MatcherError::ConfigurationError { message: "edit failed".to_owned() })
+        }));
+        let invalid_edit = config_manager.edit_draft(Box::new(|draft| {
+            *draft = new_ruleset("root", &["rule_one", "rule_one"]);
+            Ok(())
+        }));
+
+        // Assert
+        assert!(failed_edit.is_err());
+        assert!(invalid_edit.is_err());
+        assert!(is_ruleset(&config_manager.get_draft().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_apply_all_the_concurrent_edits_of_the_draft() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            std::sync::Arc::new(new_deployed_config_manager(&tempdir, &new_ruleset("root", &[])));
+
+        // Act
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let config_manager = config_manager.clone();
+                std::thread::spawn(move || {
+                    config_manager.edit_draft(Box::new(move |draft| {
+                        if let MatcherConfig::Ruleset { rules, .. } = draft {
+                            rules.push(new_rule(&format!("rule_{}", i)));
+                        }
+                        Ok(())
+                    }))
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        // Assert
+        match config_manager.get_draft().unwrap() {
+            MatcherConfig::Ruleset { rules, .. } => assert_eq!(4, rules.len()),
+            _ => assert!(false),
+        }
+    }
+
+    fn new_config_formats_manager(tempdir: &tempfile::TempDir) -> FsMatcherConfigManager {
+        let root_path = format!("{}/rules.d", tempdir.path().to_str().unwrap());
+        FsMatcherConfigManager::copy_dir("./test_resources/config_formats", &root_path).unwrap();
+        let config_manager = FsMatcherConfigManager::new(root_path);
+        config_manager.prepare_versions().unwrap();
+        config_manager
+    }
+
+    #[test]
+    fn should_keep_the_format_of_the_files_in_the_draft() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager = new_config_formats_manager(&tempdir);
+
+        // Act
+        config_manager.get_draft().unwrap();
+        config_manager.deploy_draft().unwrap();
+
+        // Assert
+        let root_path = Path::new(&config_manager.root_path);
+        assert!(root_path.join("filter.yaml").exists());
+        assert!(root_path.join("emails/0020_from_toml.toml").exists());
+        assert!(root_path.join("syslog/0010_from_yml.yml").exists());
+    }
+
+    #[test]
+    fn should_write_the_edited_draft_files_in_their_original_format() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager = new_config_formats_manager(&tempdir);
+
+        // Act
+        let draft = config_manager
+            .edit_draft(Box::new(|config| {
+                if let MatcherConfig::Filter { nodes, .. } = config {
+                    for node in nodes {
+                        if let MatcherConfig::Ruleset { name, rules } = node {
+                            if name == "emails" {
+                                rules.reverse();
+                                rules.push(new_rule("new_rule"));
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }))
+            .unwrap();
+
+        // Assert
+        let draft_path = config_manager.draft_path().unwrap();
+        assert!(draft_path.join("filter.yaml").exists());
+        assert!(draft_path.join("emails/0000_from_json.json").exists());
+        assert!(draft_path.join("emails/0010_from_toml.toml").exists());
+        assert!(draft_path.join("emails/0020_from_yaml.yaml").exists());
+        assert!(draft_path.join("emails/0030_new_rule.json").exists());
+        assert!(draft_path.join("syslog/0000_from_yml.yml").exists());
+        assert_eq!(4, fs::read_dir(draft_path.join("emails")).unwrap().count());
+        assert_eq!(
+            serde_json::to_value(&draft).unwrap(),
+            serde_json::to_value(&config_manager.get_draft().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_delete_the_draft() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+
+        // Act
+        config_manager.delete_draft().unwrap();
+
+        // Assert
+        assert!(!config_manager.draft_path().unwrap().exists());
+        assert!(config_manager.deploy_draft().is_err());
+    }
+
+    #[test]
+    fn should_deploy_the_draft_and_keep_the_previous_config() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+
+        // Act
+        let deployed = config_manager.deploy_draft().unwrap();
+
+        // Assert
+        assert!(is_ruleset(&deployed, "root", &["rule_two"]));
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_two"]));
+        assert!(!config_manager.draft_path().unwrap().exists());
+        assert!(fs::symlink_metadata(&config_manager.root_path).unwrap().file_type().is_symlink());
+        assert_eq!(
+            vec![1, 2],
+            FsMatcherConfigManager::read_versions(config_manager.versions_path().unwrap()).unwrap()
+        );
+        assert!(is_ruleset(
+            &FsMatcherConfigManager::read_from_root_dir(
+                config_manager.versions_path().unwrap().join("1")
+            )
+            .unwrap(),
+            "root",
+            &["rule_one"]
+        ));
+    }
+
+    fn new_not_prepared_config_manager(tempdir: &tempfile::TempDir) -> FsMatcherConfigManager {
+        let root_path = format!("{}/rules.d", tempdir.path().to_str().unwrap());
+        FsMatcherConfigManager::write_to_dir(
+            &new_ruleset("root", &["rule_one"]),
+            &root_path,
+            Path::new(""),
+            &HashMap::new(),
+        )
+        .unwrap();
+        FsMatcherConfigManager::new(root_path)
+    }
+
+    #[test]
+    fn should_replace_the_root_dir_with_a_link_to_a_version() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager = new_not_prepared_config_manager(&tempdir);
+
+        // Act
+        config_manager.prepare_versions().unwrap();
+        config_manager.prepare_versions().unwrap();
+
+        // Assert
+        assert!(fs::symlink_metadata(&config_manager.root_path).unwrap().file_type().is_symlink());
+        assert_eq!(Some(1), config_manager.active_version().unwrap());
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_not_deploy_if_the_versions_are_not_prepared() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager = new_not_prepared_config_manager(&tempdir);
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+
+        // Act
+        let result = config_manager.deploy_draft();
+
+        // Assert
+        assert!(result.is_err());
+        assert!(fs::symlink_metadata(&config_manager.root_path).unwrap().is_dir());
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_restore_the_root_path_if_the_preparation_is_interrupted() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager = new_not_prepared_config_manager(&tempdir);
+        let versions_path = config_manager.versions_path().unwrap();
+        fs::create_dir(&versions_path).unwrap();
+        config_manager.create_version_link(1).unwrap();
+        fs::rename(&config_manager.root_path, versions_path.join("1")).unwrap();
+
+        // Act
+        let read_before_prepare = config_manager.read();
+        config_manager.prepare_versions().unwrap();
+
+        // Assert
+        assert!(read_before_prepare.is_err());
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+        assert_eq!(Some(1), config_manager.active_version().unwrap());
+    }
+
+    #[test]
+    fn should_not_save_a_draft_with_operators_that_cannot_be_built() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        let mut rule = new_rule("rule_two");
+        rule.constraint.where_operator = Some(crate::config::rule::Operator::Regex {
+            regex: "[".to_owned(),
+            target: "${event.type}".to_owned(),
+            flags: None,
+        });
+
+        // Act
+        let result = config_manager
+            .update_draft(&MatcherConfig::Ruleset { name: "root".to_owned(), rules: vec![rule] });
+
+        // Assert
+        assert!(result.is_err());
+        assert!(is_ruleset(&config_manager.get_draft().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_rollback_to_the_previous_config() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+        config_manager.deploy_draft().unwrap();
+        config_manager.update_draft(&new_ruleset("root", &["rule_three"])).unwrap();
+        config_manager.deploy_draft().unwrap();
+
+        // Act
+        let first_rollback = config_manager.rollback().unwrap();
+        let second_rollback = config_manager.rollback().unwrap();
+        let third_rollback = config_manager.rollback();
+
+        // Assert
+        assert!(is_ruleset(&config_manager.get_draft().unwrap(), "root", &["rule_three"]));
+        assert!(is_ruleset(&first_rollback, "root", &["rule_two"]));
+        assert!(is_ruleset(&second_rollback, "root", &["rule_one"]));
+        assert!(third_rollback.is_err());
+        assert!(is_ruleset(&config_manager.read().unwrap(), "root", &["rule_one"]));
+    }
+
+    #[test]
+    fn should_keep_the_replaced_config_if_a_draft_exists_at_rollback() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_one"]));
+        config_manager.update_draft(&new_ruleset("root", &["rule_two"])).unwrap();
+        config_manager.deploy_draft().unwrap();
+        config_manager.update_draft(&new_ruleset("root", &["rule_three"])).unwrap();
+
+        // Act
+        let rollback = config_manager.rollback().unwrap();
+
+        // Assert
+        assert!(is_ruleset(&rollback, "root", &["rule_one"]));
+        assert!(is_ruleset(&config_manager.get_draft().unwrap(), "root", &["rule_three"]));
+        assert!(is_ruleset(
+            &FsMatcherConfigManager::read_from_root_dir(
+                config_manager.versions_path().unwrap().join("2")
+            )
+            .unwrap(),
+            "root",
+            &["rule_two"]
+        ));
+    }
+
+    #[test]
+    fn should_keep_only_the_configured_number_of_versions() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let config_manager =
+            new_deployed_config_manager(&tempdir, &new_ruleset("root", &["rule_0"]))
+                .with_versions_to_keep(2);
+
+        // Act
+        for i in 1..5 {
+            config_manager.update_draft(&new_ruleset("root", &[&format!("rule_{}", i)])).unwrap();
+            config_manager.deploy_draft().unwrap();
+        }
+
+        // Assert
+        assert_eq!(
+            vec![3, 4, 5],
+            FsMatcherConfigManager::read_versions(config_manager.versions_path().unwrap()).unwrap()
+        );
+        assert!(is_ruleset(&config_manager.rollback().unwrap(), "root", &["rule_3"]));
+    }
+
     fn get_config_by_name<'a>(name: &str, nodes: &'a [MatcherConfig]) -> Option<&'a MatcherConfig> {
         for node in nodes {
             if get_config_name(node).eq(name) {
@@ -4,8 +4,10 @@ use crate::error::MatcherError;
 use serde_derive::{Deserialize, Serialize};
 
 pub mod filter;
+pub mod format;
 pub mod fs;
 pub mod rule;
+pub mod template;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MatcherConfig {
@@ -15,6 +17,46 @@ pub enum MatcherConfig {
 
 /// A MatcherConfigManager permits to read and manipulate the Tornado Configuration
 /// from a configuration source.
+/// The configuration changes are first saved in a draft that can be edited freely;
+/// deploying the draft makes it the active configuration, while the previously
+/// deployed ones are kept to permit a rollback.
+/// A change applied to the draft by MatcherConfigManager::edit_draft.
+pub type DraftEdit<'a> = Box<dyn FnOnce(&mut MatcherConfig) -> Result<(), MatcherError> + 'a>;
+
 pub trait MatcherConfigManager: Sync + Send {
+    /// Returns the deployed configuration.
     fn read(&self) -> Result<MatcherConfig, MatcherError>;
+
+    /// Prepares the storage of the deployed versions, completing any preparation
+    /// that was previously interrupted.
+    /// It must be called once at startup, before the configuration is read,
+    /// and before the drafts are deployed or rolled back.
+    fn prepare_versions(&self) -> Result<(), MatcherError> {
+        Ok(())
+    }
+
+    /// Returns the current draft.
+    /// If no draft exists, a new one is created from the deployed configuration.
+    fn get_draft(&self) -> Result<MatcherConfig, MatcherError>;
+
+    /// Validates the configuration and saves it as the current draft,
+    /// replacing the previous one.
+    fn update_draft(&self, config: &MatcherConfig) -> Result<(), MatcherError>;
+
+    /// Applies the edit to the current draft, then validates and saves it.
+    /// No other change of the draft can happen between the read and the save.
+    /// It returns the saved draft.
+    fn edit_draft(&self, edit: DraftEdit<'_>) -> Result<MatcherConfig, MatcherError>;
+
+    /// Deletes the current draft, if any.
+    fn delete_draft(&self) -> Result<(), MatcherError>;
+
+    /// Validates the current draft and makes it the deployed configuration.
+    /// It returns the newly deployed configuration.
+    fn deploy_draft(&self) -> Result<MatcherConfig, MatcherError>;
+
+    /// Restores the most recent of the previously deployed configurations.
+    /// If no draft exists, the replaced configuration becomes the draft.
+    /// It returns the restored configuration.
+    fn rollback(&self) -> Result<MatcherConfig, MatcherError>;
 }
@@ -6,7 +6,7 @@ use crate::error::MatcherError;
 use serde_derive::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
-use tornado_common_api::{Payload, Value};
+use tornado_common_api::{Event, Payload, Value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rule {
@@ -18,6 +18,9 @@ pub struct Rule {
     pub active: bool,
     pub constraint: Constraint,
     pub actions: Vec<Action>,
+    /// Example Events with the outcome expected when they are processed by the Rule.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tests: Vec<RuleTest>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,14 +34,67 @@ pub struct Constraint {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Extractor {
     pub from: String,
-    pub regex: ExtractorRegex,
+    #[serde(flatten)]
+    pub kind: ExtractorKind,
+    #[serde(default)]
+    pub modifiers_post: Vec<Modifier>,
+}
+
+/// The strategy used by an Extractor to generate a value from its 'from' field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ExtractorKind {
+    #[serde(rename = "regex")]
+    Regex(ExtractorRegex),
+    #[serde(rename = "json")]
+    Json(ExtractorJson),
+    #[serde(rename = "key_value")]
+    KeyValue(ExtractorKeyValue),
+    #[serde(rename = "jmespath")]
+    Jmespath(ExtractorJmespath),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractorRegex {
     #[serde(rename = "match")]
     pub regex: String,
-    pub group_match_idx: u16,
+    #[serde(default)]
+    pub group_match_idx: Option<u16>,
+    #[serde(default)]
+    pub all_matches: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractorJson {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractorKeyValue {
+    #[serde(default)]
+    pub pair_separator: Option<String>,
+    #[serde(default)]
+    pub key_value_separator: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractorJmespath {
+    pub expression: String,
+}
+
+/// The post-processing steps applied, in order, to the value of an extracted variable
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum Modifier {
+    #[serde(rename = "trim")]
+    Trim,
+    #[serde(rename = "lowercase")]
+    Lowercase,
+    #[serde(rename = "uppercase")]
+    Uppercase,
+    #[serde(rename = "to_number")]
+    ToNumber,
+    #[serde(rename = "replace")]
+    Replace { find: String, replace: String },
+    #[serde(rename = "default")]
+    Default { value: Value },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -48,26 +104,145 @@ pub enum Operator {
     And { operators: Vec<Operator> },
     #[serde(rename = "OR")]
     Or { operators: Vec<Operator> },
+    #[serde(rename = "NOT")]
+    Not { operator: Box<Operator> },
+    #[serde(rename = "any")]
+    Any { target: String, operator: Box<Operator> },
+    #[serde(rename = "all")]
+    All { target: String, operator: Box<Operator> },
+    #[serde(rename = "between")]
+    Between {
+        target: String,
+        min: Value,
+        max: Value,
+        #[serde(default)]
+        exclusive_min: bool,
+        #[serde(default)]
+        exclusive_max: bool,
+    },
     #[serde(rename = "contain")]
     Contain { first: Value, second: Value },
+    #[serde(rename = "containsIgnoreCase")]
+    ContainsIgnoreCase { first: Value, second: Value },
+    #[serde(rename = "custom")]
+    Custom {
+        name: String,
+        #[serde(default)]
+        args: HashMap<String, Value>,
+    },
+    #[serde(rename = "endsWith")]
+    EndsWith { first: Value, second: Value },
     #[serde(rename = "equal")]
     Equal { first: Value, second: Value },
+    #[serde(rename = "equalsIgnoreCase")]
+    EqualsIgnoreCase { first: Value, second: Value },
+    #[serde(rename = "exists")]
+    Exists { target: String },
     #[serde(rename = "ge")]
     GreaterEqualThan { first: Value, second: Value },
     #[serde(rename = "gt")]
     GreaterThan { first: Value, second: Value },
+    #[serde(rename = "in")]
+    In { first: Value, second: Value },
+    #[serde(rename = "ipInRange")]
+    IpInRange { ranges: Vec<String>, target: String },
+    #[serde(rename = "isNull")]
+    IsNull { target: String },
+    #[serde(rename = "isType")]
+    IsType { target: String, value_type: ValueType },
     #[serde(rename = "le")]
     LessEqualThan { first: Value, second: Value },
+    #[serde(rename = "length")]
+    Length {
+        target: String,
+        #[serde(default)]
+        min: Option<Value>,
+        #[serde(default)]
+        max: Option<Value>,
+        #[serde(default)]
+        exclusive_min: bool,
+        #[serde(default)]
+        exclusive_max: bool,
+    },
     #[serde(rename = "lt")]
     LessThan { first: Value, second: Value },
     #[serde(rename = "regex")]
-    Regex { regex: String, target: String },
+    Regex {
+        regex: String,
+        target: String,
+        #[serde(default)]
+        flags: Option<String>,
+    },
+    #[serde(rename = "regexAny")]
+    RegexAny {
+        regexes: Vec<String>,
+        target: String,
+        #[serde(default)]
+        flags: Option<String>,
+        #[serde(default)]
+        variable: Option<String>,
+    },
+    #[serde(rename = "startsWith")]
+    StartsWith { first: Value, second: Value },
+    #[serde(rename = "timeWindow")]
+    TimeWindow {
+        timezone: String,
+        #[serde(default)]
+        days: Vec<String>,
+        from: String,
+        to: String,
+    },
+}
+
+/// The types of value that can be checked by the 'isType' operator
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ValueType {
+    #[serde(rename = "text")]
+    Text,
+    #[serde(rename = "number")]
+    Number,
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "array")]
+    Array,
+    #[serde(rename = "map")]
+    Map,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Action {
+    #[serde(default)]
     pub id: String,
+    #[serde(default)]
     pub payload: Payload,
+    /// An optional condition; when it evaluates to false, the action is not executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Operator>,
+    /// The name of the ActionTemplate this action is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// An example Event with the outcome expected when it is processed by a Rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTest {
+    #[serde(default)]
+    pub description: String,
+    pub event: Event,
+    pub expected: RuleTestExpectation,
+}
+
+/// The expected outcome of a RuleTest.
+/// The extracted variables and the actions are verified only if present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTestExpectation {
+    pub matched: bool,
+    /// The variables extracted by the Rule, without the rule name prefix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extracted_vars: Option<HashMap<String, Value>>,
+    /// The actions produced by the Rule, with their payload already rendered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<tornado_common_api::Action>>,
 }
 
 impl Rule {
@@ -106,7 +281,10 @@ mod test {
         }
 
         assert_eq!("${event.payload.body}", rule.constraint.with["extracted_temp"].from);
-        assert_eq!("([0-9]+\\sDegrees)", rule.constraint.with["extracted_temp"].regex.regex);
+        match &rule.constraint.with["extracted_temp"].kind {
+            ExtractorKind::Regex(regex) => assert_eq!("([0-9]+\\sDegrees)", regex.regex),
+            _ => assert!(false),
+        }
     }
 
     #[test]
@@ -140,7 +318,10 @@ mod test {
         }
 
         assert_eq!("${event.payload.body}", rule.constraint.with["extracted_temp"].from);
-        assert_eq!("([0-9]+\\sDegrees)", rule.constraint.with["extracted_temp"].regex.regex);
+        match &rule.constraint.with["extracted_temp"].kind {
+            ExtractorKind::Regex(regex) => assert_eq!("([0-9]+\\sDegrees)", regex.regex),
+            _ => assert!(false),
+        }
     }
 
     #[test]
@@ -151,4 +332,147 @@ mod test {
         assert!(rule.is_ok());
     }
 
+    #[test]
+    fn should_deserialize_rule_from_json_with_not_operator() {
+        let json = r#"{
+            "description": "",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WHERE": {
+                    "type": "NOT",
+                    "operator": {
+                        "type": "contain",
+                        "first": "${event.payload.subject}",
+                        "second": "test"
+                    }
+                },
+                "WITH": {}
+            },
+            "actions": []
+        }"#;
+        let rule = Rule::from_json(&json).unwrap();
+
+        assert_eq!(
+            Some(Operator::Not {
+                operator: Box::new(Operator::Contain {
+                    first: Value::Text("${event.payload.subject}".to_owned()),
+                    second: Value::Text("test".to_owned()),
+                })
+            }),
+            rule.constraint.where_operator
+        );
+    }
+
+    #[test]
+    fn should_deserialize_rule_from_json_with_is_type_operator() {
+        let json = r#"{
+            "description": "",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WHERE": {
+                    "type": "isType",
+                    "target": "${event.payload.value}",
+                    "value_type": "number"
+                },
+                "WITH": {}
+            },
+            "actions": []
+        }"#;
+        let rule = Rule::from_json(&json).unwrap();
+
+        assert_eq!(
+            Some(Operator::IsType {
+                target: "${event.payload.value}".to_owned(),
+                value_type: ValueType::Number,
+            }),
+            rule.constraint.where_operator
+        );
+    }
+
+    #[test]
+    fn should_deserialize_extractors_of_all_kinds() {
+        let json = r#"{
+            "description": "",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WITH": {
+                    "from_json": {
+                        "from": "${event.payload.body}",
+                        "json": {}
+                    },
+                    "from_key_value": {
+                        "from": "${event.payload.body}",
+                        "key_value": {
+                            "pair_separator": ";"
+                        }
+                    },
+                    "from_jmespath": {
+                        "from": "${event.payload.body}",
+                        "jmespath": {
+                            "expression": "data.host"
+                        }
+                    }
+                }
+            },
+            "actions": []
+        }"#;
+        let rule = Rule::from_json(&json).unwrap();
+
+        match &rule.constraint.with["from_json"].kind {
+            ExtractorKind::Json(_) => {}
+            _ => assert!(false),
+        }
+        match &rule.constraint.with["from_key_value"].kind {
+            ExtractorKind::KeyValue(key_value) => {
+                assert_eq!(Some(";".to_owned()), key_value.pair_separator);
+                assert_eq!(None, key_value.key_value_separator);
+            }
+            _ => assert!(false),
+        }
+        match &rule.constraint.with["from_jmespath"].kind {
+            ExtractorKind::Jmespath(jmespath) => assert_eq!("data.host", jmespath.expression),
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn should_deserialize_actions_with_when_condition() {
+        let json = r#"{
+            "description": "",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WITH": {}
+            },
+            "actions": [
+                {
+                    "id": "logger",
+                    "payload": {}
+                },
+                {
+                    "id": "pager",
+                    "payload": {},
+                    "when": {
+                        "type": "equal",
+                        "first": "${event.payload.severity}",
+                        "second": "critical"
+                    }
+                }
+            ]
+        }"#;
+        let rule = Rule::from_json(&json).unwrap();
+
+        assert_eq!(None, rule.actions[0].when);
+        assert_eq!(
+            Some(Operator::Equal {
+                first: Value::Text("${event.payload.severity}".to_owned()),
+                second: Value::Text("critical".to_owned()),
+            }),
+            rule.actions[1].when
+        );
+    }
+
 }
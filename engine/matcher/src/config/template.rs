@@ -0,0 +1,232 @@
+use crate::config::rule::{Action, Rule};
+use crate::config::MatcherConfig;
+use crate::error::MatcherError;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tornado_common_api::{Payload, Value};
+
+/// A named Action definition that can be shared by many Rules.
+/// A Rule references a template through the 'template' field of an Action; the payload entries
+/// declared in the Rule override the ones of the template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionTemplate {
+    pub id: String,
+    #[serde(default)]
+    pub payload: Payload,
+}
+
+impl ActionTemplate {
+    pub fn from_json(json: &str) -> Result<ActionTemplate, MatcherError> {
+        serde_json::from_str(json).map_err(|e| MatcherError::JsonDeserializationError {
+            message: format!("Cannot deserialize ActionTemplate. Error [{}]", e),
+        })
+    }
+
+    /// Returns the Action obtained by applying the overrides of the given Action to this template.
+    /// The id of the template is used if the Action does not declare its own id.
+    /// The payload maps are merged recursively; any other overriding value replaces
+    /// the value of the template.
+    pub fn apply(&self, action: &Action) -> Action {
+        let mut payload = self.payload.clone();
+        merge_payload(&mut payload, &action.payload);
+        Action {
+            id: if action.id.is_empty() { self.id.to_owned() } else { action.id.to_owned() },
+            payload,
+            when: action.when.clone(),
+            template: None,
+        }
+    }
+}
+
+/// Replaces all the Actions of the Rules in the config that reference a template
+/// with the result of the template application.
+/// It returns an error if a Rule references a template that is not defined.
+pub fn resolve_action_templates(
+    config: &mut MatcherConfig,
+    templates: &HashMap<String, ActionTemplate>,
+) -> Result<(), MatcherError> {
+    match config {
+        MatcherConfig::Ruleset { rules, .. } => {
+            for rule in rules {
+                resolve_rule_action_templates(rule, templates)?;
+            }
+        }
+        MatcherConfig::Filter { nodes, .. } => {
+            for node in nodes {
+                resolve_action_templates(node, templates)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn resolve_rule_action_templates(
+    rule: &mut Rule,
+    templates: &HashMap<String, ActionTemplate>,
+) -> Result<(), MatcherError> {
+    let rule_name = &rule.name;
+    for action in rule.actions.iter_mut() {
+        if let Some(template_name) = &action.template {
+            let template =
+                templates.get(template_name).ok_or_else(|| MatcherError::ConfigurationError {
+                    message: format!(
+                        "The action template [{}] used by rule [{}] is not defined",
+                        template_name, rule_name
+                    ),
+                })?;
+            *action = template.apply(action);
+        }
+    }
+    Ok(())
+}
+
+fn merge_payload(payload: &mut Payload, overrides: &Payload) {
+    for (key, value) in overrides {
+        match (payload.get_mut(key), value) {
+            (Some(Value::Map(current)), Value::Map(value)) => merge_payload(current, value),
+            _ => {
+                payload.insert(key.to_owned(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::config::rule::{Constraint, Operator};
+    use maplit::*;
+
+    fn archive_template() -> ActionTemplate {
+        ActionTemplate::from_json(
+            r#"{
+                "id": "archive",
+                "payload": {
+                    "event": "${event}",
+                    "archive_type": "one",
+                    "options": {
+                        "compress": true,
+                        "retention": 30
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn new_rule(name: &str, actions: Vec<Action>) -> Rule {
+        Rule {
+            name: name.to_owned(),
+            description: "".to_owned(),
+            do_continue: true,
+            active: true,
+            constraint: Constraint { where_operator: None, with: HashMap::new() },
+            actions,
+            tests: vec![],
+        }
+    }
+
+    fn template_action(template: &str, payload: Payload) -> Action {
+        Action { id: "".to_owned(), payload, when: None, template: Some(template.to_owned()) }
+    }
+
+    #[test]
+    fn should_apply_the_template_overriding_the_payload() {
+        let action = Action {
+            id: "".to_owned(),
+            payload: hashmap![
+                "archive_type".to_owned() => Value::Text("two".to_owned()),
+                "options".to_owned() => Value::Map(hashmap![
+                    "retention".to_owned() => Value::Text("7".to_owned()),
+                ]),
+            ],
+            when: Some(Operator::Equal {
+                first: Value::Text("${event.type}".to_owned()),
+                second: Value::Text("email".to_owned()),
+            }),
+            template: Some("archive".to_owned()),
+        };
+
+        let result = archive_template().apply(&action);
+
+        assert_eq!("archive", result.id);
+        assert_eq!(None, result.template);
+        assert_eq!(action.when, result.when);
+        assert_eq!(
+            hashmap![
+                "event".to_owned() => Value::Text("${event}".to_owned()),
+                "archive_type".to_owned() => Value::Text("two".to_owned()),
+                "options".to_owned() => Value::Map(hashmap![
+                    "compress".to_owned() => Value::Bool(true),
+                    "retention".to_owned() => Value::Text("7".to_owned()),
+                ]),
+            ],
+            result.payload
+        );
+    }
+
+    #[test]
+    fn should_use_the_action_id_if_present() {
+        let mut action = template_action("archive", HashMap::new());
+        action.id = "custom_archive".to_owned();
+
+        let result = archive_template().apply(&action);
+
+        assert_eq!("custom_archive", result.id);
+        assert_eq!(archive_template().payload, result.payload);
+    }
+
+    #[test]
+    fn should_resolve_the_templates_of_all_the_rules_in_the_tree() {
+        let mut config = MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: crate::config::filter::Filter {
+                description: "".to_owned(),
+                active: true,
+                filter: None,
+            },
+            nodes: vec![MatcherConfig::Ruleset {
+                name: "ruleset".to_owned(),
+                rules: vec![new_rule(
+                    "rule",
+                    vec![
+                        template_action("archive", HashMap::new()),
+                        Action {
+                            id: "logger".to_owned(),
+                            payload: HashMap::new(),
+                            when: None,
+                            template: None,
+                        },
+                    ],
+                )],
+            }],
+        };
+        let templates = hashmap!["archive".to_owned() => archive_template()];
+
+        resolve_action_templates(&mut config, &templates).unwrap();
+
+        match config {
+            MatcherConfig::Filter { nodes, .. } => match &nodes[0] {
+                MatcherConfig::Ruleset { rules, .. } => {
+                    assert_eq!("archive", rules[0].actions[0].id);
+                    assert_eq!(archive_template().payload, rules[0].actions[0].payload);
+                    assert_eq!("logger", rules[0].actions[1].id);
+                }
+                _ => assert!(false),
+            },
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn should_fail_if_the_template_is_not_defined() {
+        let mut config = MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![new_rule("rule", vec![template_action("icinga2", HashMap::new())])],
+        };
+        let templates = hashmap!["archive".to_owned() => archive_template()];
+
+        assert!(resolve_action_templates(&mut config, &templates).is_err());
+    }
+}
@@ -14,6 +14,12 @@ pub enum MatcherError {
     #[fail(display = "MissingOperatorError: No operator specified (the args array is empty)")]
     MissingOperatorError {},
 
+    #[fail(
+        display = "CircularExtractedVariablesError: The extracted variables {:?} of rule [{}] depend on each other",
+        variables, rule_name
+    )]
+    CircularExtractedVariablesError { rule_name: String, variables: Vec<String> },
+
     #[fail(display = "ParseOperatorError: [{}]", message)]
     ParseOperatorError { message: String },
 
@@ -29,6 +35,18 @@ pub enum MatcherError {
     #[fail(display = "OperatorBuildFailError: [{}]\n cause: [{}]", message, cause)]
     OperatorBuildFailError { message: String, cause: String },
 
+    #[fail(
+        display = "PlaceholderFilterBuildError: Cannot build filter [{}]\n cause: [{}]",
+        filter, cause
+    )]
+    PlaceholderFilterBuildError { filter: String, cause: String },
+
+    #[fail(
+        display = "PlaceholderFilterRenderError: Cannot apply filter [{}]\n cause: [{}]",
+        filter, cause
+    )]
+    PlaceholderFilterRenderError { filter: String, cause: String },
+
     #[fail(display = "UnknownAccessorError: Unknown accessor: [{}]", accessor)]
     UnknownAccessorError { accessor: String },
 
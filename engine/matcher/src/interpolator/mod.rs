@@ -5,14 +5,16 @@
 // - https://github.com/hoodie/concatenation_benchmarks-rs
 //
 
-use crate::accessor::{Accessor, AccessorBuilder};
+use crate::accessor::{AccessorBuilder, ExtractedVars};
 use crate::error::MatcherError;
+use crate::interpolator::placeholder::{JsonFormat, Placeholder};
 use crate::model::InternalEvent;
 use lazy_static::*;
 use regex::Regex;
-use std::collections::HashMap;
 use tornado_common_api::{Number, Value};
 
+pub mod placeholder;
+
 lazy_static! {
     static ref RE: Regex =
         Regex::new(r"(\$\{[^}]+})").expect("StringInterpolator regex must be valid");
@@ -20,6 +22,7 @@ lazy_static! {
 
 /// A StringInterpolator allows the dynamic substitution of placeholders in a string
 /// with values extracted from an incoming event or from the extracted variables.
+/// Each placeholder can transform its value with a chain of filters, e.g. "${event.type | upper}".
 /// E.g.:
 /// ```rust
 ///
@@ -48,14 +51,15 @@ lazy_static! {
 pub struct StringInterpolator {
     template: String,
     rule_name: String,
-    accessors: Vec<BoundedAccessor>,
+    accessors: Vec<BoundedPlaceholder>,
+    json_format: JsonFormat,
 }
 
 #[derive(Debug, PartialEq)]
-struct BoundedAccessor {
+struct BoundedPlaceholder {
     start: usize,
     end: usize,
-    accessor: Accessor,
+    placeholder: Placeholder,
 }
 
 impl StringInterpolator {
@@ -71,13 +75,29 @@ impl StringInterpolator {
         let accessors = RE
             .find_iter(&template_string)
             .map(|m| {
-                accessor_builder
-                    .build(&rule_name_string, m.as_str())
-                    .map(|accessor| BoundedAccessor { start: m.start(), end: m.end(), accessor })
+                Placeholder::build(&rule_name_string, m.as_str(), accessor_builder).map(
+                    |placeholder| BoundedPlaceholder {
+                        start: m.start(),
+                        end: m.end(),
+                        placeholder,
+                    },
+                )
             })
             .collect::<Result<Vec<_>, MatcherError>>()?;
 
-        Ok(StringInterpolator { template: template_string, rule_name: rule_name_string, accessors })
+        Ok(StringInterpolator {
+            template: template_string,
+            rule_name: rule_name_string,
+            accessors,
+            json_format: JsonFormat::Compact,
+        })
+    }
+
+    /// Sets the JSON layout used to render the values of type Array and Map
+    /// of the placeholders without a 'json' filter. The default is JsonFormat::Compact.
+    pub fn with_json_format(mut self, json_format: JsonFormat) -> Self {
+        self.json_format = json_format;
+        self
     }
 
     /// Returns whether the template used to create this StringInterpolator
@@ -94,35 +114,39 @@ impl StringInterpolator {
     /// Performs the placeholders substitution on the internal template and return the
     /// resulting string.
     /// The placeholders are replaced with values extracted from the passed event and extracted_vars.
-    /// Only values of type String, Number, Boolean and null are accepted; consequently, this method
-    /// will return an error if:
-    /// - the placeholder cannot be resolved
-    /// - the value associated with the placeholder is of type Array
-    /// - the value associated with the placeholder is of type Map
+    /// Values of type Array and Map are rendered as JSON, compact unless a different layout is set
+    /// with `with_json_format`; the 'json' filter chooses the layout of a single placeholder,
+    /// e.g. "${event.payload | json(\"pretty\")}".
+    /// This method returns an error if a placeholder cannot be resolved.
     pub fn render(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
     ) -> Result<String, MatcherError> {
         let mut render = String::new();
 
         // keeps the index of the previous argument end
         let mut prev_end = 0;
 
-        for bounded_accessor in &self.accessors {
-            if prev_end != bounded_accessor.start {
-                render.push_str(&self.template[prev_end..bounded_accessor.start])
+        for bounded_placeholder in &self.accessors {
+            if prev_end != bounded_placeholder.start {
+                render.push_str(&self.template[prev_end..bounded_placeholder.start])
             }
 
-            let accessor = &bounded_accessor.accessor;
+            let placeholder = &bounded_placeholder.placeholder;
 
-            let value = accessor.get(event, extracted_vars).ok_or(
-                MatcherError::InterpolatorRenderError {
+            let value = placeholder
+                .get(event, extracted_vars)
+                .map_err(|e| MatcherError::InterpolatorRenderError {
                     template: self.template.to_owned(),
                     rule_name: self.rule_name.to_owned(),
-                    cause: format!("Accessor [{:?}] returned empty value.", accessor),
-                },
-            )?;
+                    cause: format!("{}", e),
+                })?
+                .ok_or(MatcherError::InterpolatorRenderError {
+                    template: self.template.to_owned(),
+                    rule_name: self.rule_name.to_owned(),
+                    cause: format!("Placeholder [{:?}] returned empty value.", placeholder),
+                })?;
             match value.as_ref() {
                 Value::Text(text) => render.push_str(text),
                 Value::Bool(val) => render.push_str(&val.to_string()),
@@ -132,19 +156,22 @@ impl StringInterpolator {
                     Number::Float(num) => render.push_str(&num.to_string()),
                 },
                 Value::Null => render.push_str("null"),
-                Value::Map(..) => return Err(MatcherError::InterpolatorRenderError {
-                    template: self.template.to_owned(),
-                    rule_name: self.rule_name.to_owned(),
-                    cause: format!("Accessor [{:?}] returned a Map. Expected text, number, boolean or null.", accessor),
-                }),
-                Value::Array(..) => return Err(MatcherError::InterpolatorRenderError {
-                    template: self.template.to_owned(),
-                    rule_name: self.rule_name.to_owned(),
-                    cause: format!("Accessor [{:?}] returned an Array. Expected text, number, boolean or null.", accessor),
-                }),
+                Value::Map(..) | Value::Array(..) => {
+                    let json = self.json_format.serialize(value.as_ref()).ok_or_else(|| {
+                        MatcherError::InterpolatorRenderError {
+                            template: self.template.to_owned(),
+                            rule_name: self.rule_name.to_owned(),
+                            cause: format!(
+                                "Placeholder [{:?}] returned a value that cannot be serialized to JSON.",
+                                placeholder
+                            ),
+                        }
+                    })?;
+                    render.push_str(&json)
+                }
             }
 
-            prev_end = bounded_accessor.end;
+            prev_end = bounded_placeholder.end;
         }
 
         let template_len = self.template.len();
@@ -162,6 +189,8 @@ impl StringInterpolator {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::accessor::Accessor;
+    use std::collections::HashMap;
     use tornado_common_api::{Event, Payload};
 
     #[test]
@@ -190,22 +219,22 @@ mod test {
 
         assert_eq!(&11, &interpolator.accessors[0].start);
         assert_eq!(&32, &interpolator.accessors[0].end);
-        match &interpolator.accessors[0].accessor {
+        match interpolator.accessors[0].placeholder.accessor() {
             Accessor::Payload { keys } => assert_eq!(1, keys.len()),
             _ => assert!(false),
         }
 
         assert_eq!(&36, &interpolator.accessors[1].start);
         assert_eq!(&49, &interpolator.accessors[1].end);
-        match &interpolator.accessors[1].accessor {
+        match interpolator.accessors[1].placeholder.accessor() {
             Accessor::Type => assert!(true),
             _ => assert!(false),
         }
 
         assert_eq!(&58, &interpolator.accessors[2].start);
         assert_eq!(&78, &interpolator.accessors[2].end);
-        match &interpolator.accessors[2].accessor {
-            Accessor::ExtractedVar { key } => assert_eq!("rule.test12", key),
+        match interpolator.accessors[2].placeholder.accessor() {
+            Accessor::ExtractedVar { key, .. } => assert_eq!("rule.test12", key),
             _ => assert!(false),
         }
     }
@@ -336,7 +365,7 @@ mod test {
         // Act
         let interpolator =
             StringInterpolator::build(template, "rule_for_test", &Default::default()).unwrap();
-        let result = interpolator.render(&event, Some(&extracted_vars));
+        let result = interpolator.render(&event, Some(&ExtractedVars::new(&extracted_vars)));
 
         // Assert
         assert!(result.is_ok());
@@ -475,9 +504,9 @@ mod test {
     }
 
     #[test]
-    fn render_should_fail_if_value_is_an_array() {
+    fn should_render_an_array_as_json() {
         // Arrange
-        let body = vec![Value::Text("".to_owned())];
+        let body = vec![Value::Text("linux".to_owned()), Value::Number(Number::PosInt(1))];
 
         let mut payload = Payload::new();
         payload.insert("body".to_owned(), Value::Array(body));
@@ -488,7 +517,7 @@ mod test {
             payload,
         });
 
-        let template = "${event.payload.body}";
+        let template = "Alert: ${event.payload.body}";
 
         // Act
         let interpolator =
@@ -496,14 +525,14 @@ mod test {
         let result = interpolator.render(&event, None);
 
         // Assert
-        assert!(result.is_err());
+        assert_eq!(r#"Alert: ["linux",1]"#, &result.unwrap());
     }
 
     #[test]
-    fn render_should_fail_if_value_is_a_map() {
+    fn should_render_a_map_as_json() {
         // Arrange
         let mut body = HashMap::new();
-        body.insert("".to_owned(), Value::Text("".to_owned()));
+        body.insert("host".to_owned(), Value::Text("host01".to_owned()));
 
         let mut payload = Payload::new();
         payload.insert("body".to_owned(), Value::Map(body));
@@ -514,7 +543,7 @@ mod test {
             payload,
         });
 
-        let template = "${event.payload.body}";
+        let template = "Alert: ${event.payload.body}";
 
         // Act
         let interpolator =
@@ -522,7 +551,33 @@ mod test {
         let result = interpolator.render(&event, None);
 
         // Assert
-        assert!(result.is_err());
+        assert_eq!(r#"Alert: {"host":"host01"}"#, &result.unwrap());
+    }
+
+    #[test]
+    fn should_render_the_values_with_the_configured_json_format() {
+        // Arrange
+        let body = vec![Value::Text("linux".to_owned())];
+
+        let mut payload = Payload::new();
+        payload.insert("body".to_owned(), Value::Array(body));
+
+        let event = InternalEvent::new(Event {
+            event_type: "event_type_value".to_owned(),
+            created_ms: 1554130814854,
+            payload,
+        });
+
+        let template = r#"Alert: ${event.payload.body} ${event.payload.body | json("compact")}"#;
+
+        // Act
+        let interpolator = StringInterpolator::build(template, "rule", &Default::default())
+            .unwrap()
+            .with_json_format(JsonFormat::Pretty);
+        let result = interpolator.render(&event, None);
+
+        // Assert
+        assert_eq!("Alert: [\n  \"linux\"\n] [\"linux\"]", &result.unwrap());
     }
 
     #[test]
@@ -602,4 +657,46 @@ mod test {
             .is_interpolation_required());
     }
 
+    #[test]
+    fn should_render_placeholders_with_filters() {
+        // Arrange
+        let mut payload = Payload::new();
+        payload.insert("host".to_owned(), Value::Text("Host01".to_owned()));
+        payload.insert(
+            "tags".to_owned(),
+            Value::Array(vec![Value::Text("linux".to_owned()), Value::Text("db".to_owned())]),
+        );
+
+        let event = InternalEvent::new(Event {
+            event_type: "event_type_value".to_owned(),
+            created_ms: 1554130814854,
+            payload,
+        });
+
+        let template = r#"host: ${event.payload.host | lower} - day: ${event.created_ms | date("%Y-%m-%d")} - tags: ${event.payload.tags | json} - var: ${_variables.x | default("n/a")}"#;
+
+        // Act
+        let interpolator =
+            StringInterpolator::build(template, "rule", &Default::default()).unwrap();
+        let result = interpolator.render(&event, None);
+
+        // Assert
+        assert_eq!(
+            r#"host: host01 - day: 2019-04-01 - tags: ["linux","db"] - var: n/a"#,
+            &result.unwrap()
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_unknown_filter() {
+        // Arrange
+        let template = "host: ${event.payload.host | unknown}";
+
+        // Act
+        let interpolator = StringInterpolator::build(template, "rule", &Default::default());
+
+        // Assert
+        assert!(interpolator.is_err());
+    }
+
 }
@@ -0,0 +1,441 @@
+use crate::accessor::{Accessor, AccessorBuilder, ExtractedVars};
+use crate::error::MatcherError;
+use crate::model::InternalEvent;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{TimeZone, Utc};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use tornado_common_api::{Number, Value};
+
+const PLACEHOLDER_START_DELIMITER: &str = "${";
+const PLACEHOLDER_END_DELIMITER: &str = "}";
+const FILTER_SEPARATOR: char = '|';
+const FILTER_ARGS_START_DELIMITER: char = '(';
+const FILTER_ARGS_END_DELIMITER: char = ')';
+
+/// A Placeholder is an Accessor whose value is transformed by a chain of filters.
+/// The filters follow the accessor path and are separated by the '|' character, e.g.:
+/// "${event.payload.host | lower}".
+#[derive(Debug, PartialEq)]
+pub struct Placeholder {
+    accessor: Accessor,
+    filters: Vec<Filter>,
+}
+
+/// A function that transforms the value of a Placeholder.
+#[derive(Debug, PartialEq)]
+pub enum Filter {
+    /// Converts a text to lowercase
+    Lower,
+    /// Converts a text to uppercase
+    Upper,
+    /// Removes the leading and trailing whitespaces of a text
+    Trim,
+    /// Formats a timestamp in milliseconds since the epoch as a UTC date
+    Date { format: String },
+    /// Serializes the value as JSON, either compact or pretty-printed
+    Json { format: JsonFormat },
+    /// Replaces a missing value
+    Default { value: Value },
+}
+
+/// The layout of the JSON text produced by the 'json' filter.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JsonFormat {
+    Compact,
+    Pretty,
+}
+
+impl JsonFormat {
+    pub fn serialize(self, value: &Value) -> Option<String> {
+        match self {
+            JsonFormat::Compact => serde_json::to_string(value).ok(),
+            JsonFormat::Pretty => serde_json::to_string_pretty(value).ok(),
+        }
+    }
+}
+
+impl Placeholder {
+    /// Builds a Placeholder from a text.
+    /// If the text is not enclosed in the "${" and "}" delimiters, the Placeholder
+    /// has no filters and its value is the one of the Accessor built from the text itself.
+    pub fn build(
+        rule_name: &str,
+        input: &str,
+        accessor_builder: &AccessorBuilder,
+    ) -> Result<Placeholder, MatcherError> {
+        let trimmed = input.trim();
+        if !(trimmed.starts_with(PLACEHOLDER_START_DELIMITER)
+            && trimmed.ends_with(PLACEHOLDER_END_DELIMITER))
+        {
+            return Ok(Placeholder {
+                accessor: accessor_builder.build(rule_name, input)?,
+                filters: vec![],
+            });
+        }
+
+        let content = &trimmed
+            [PLACEHOLDER_START_DELIMITER.len()..(trimmed.len() - PLACEHOLDER_END_DELIMITER.len())];
+        let mut parts = split_filters(content).into_iter();
+        let path = parts.next().unwrap_or("");
+        let accessor = accessor_builder.build(
+            rule_name,
+            &format!("{}{}{}", PLACEHOLDER_START_DELIMITER, path.trim(), PLACEHOLDER_END_DELIMITER),
+        )?;
+        let filters = parts.map(Filter::build).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Placeholder { accessor, filters })
+    }
+
+    /// Returns whether the value of the Accessor is transformed by at least one filter.
+    pub fn has_filters(&self) -> bool {
+        !self.filters.is_empty()
+    }
+
+    pub fn accessor(&self) -> &Accessor {
+        &self.accessor
+    }
+
+    pub fn into_accessor(self) -> Accessor {
+        self.accessor
+    }
+
+    /// Returns the value of the Accessor transformed by the filters.
+    /// It returns an error if a filter cannot be applied to the value.
+    pub fn get<'o>(
+        &'o self,
+        event: &'o InternalEvent,
+        extracted_vars: Option<&'o ExtractedVars<'o>>,
+    ) -> Result<Option<Cow<'o, Value>>, MatcherError> {
+        let value = self.accessor.get(event, extracted_vars);
+        if self.filters.is_empty() {
+            return Ok(value);
+        }
+        let mut value = value.map(Cow::into_owned);
+        for filter in &self.filters {
+            value = filter.apply(value)?;
+        }
+        Ok(value.map(Cow::Owned))
+    }
+}
+
+impl Filter {
+    /// Builds a Filter from its definition, e.g. "lower" or "date(\"%Y-%m-%d\")".
+    /// The arguments, if any, are JSON literals separated by commas.
+    pub fn build(definition: &str) -> Result<Filter, MatcherError> {
+        let definition = definition.trim();
+        let (name, args) = match definition.find(FILTER_ARGS_START_DELIMITER) {
+            Some(index) if definition.ends_with(FILTER_ARGS_END_DELIMITER) => {
+                let args = &definition[(index + 1)..(definition.len() - 1)];
+                let args: Vec<Value> =
+                    serde_json::from_str(&format!("[{}]", args)).map_err(|e| {
+                        MatcherError::PlaceholderFilterBuildError {
+                            filter: definition.to_owned(),
+                            cause: format!("Cannot parse the arguments: {}", e),
+                        }
+                    })?;
+                (definition[..index].trim(), args)
+            }
+            _ => (definition, vec![]),
+        };
+
+        let filter = match (name, args.as_slice()) {
+            ("lower", []) => Filter::Lower,
+            ("upper", []) => Filter::Upper,
+            ("trim", []) => Filter::Trim,
+            ("json", []) => Filter::Json { format: JsonFormat::Compact },
+            ("json", [Value::Text(format)]) if format == "compact" => {
+                Filter::Json { format: JsonFormat::Compact }
+            }
+            ("json", [Value::Text(format)]) if format == "pretty" => {
+                Filter::Json { format: JsonFormat::Pretty }
+            }
+            ("date", [Value::Text(format)]) => {
+                if StrftimeItems::new(format).any(|item| item == Item::Error) {
+                    return Err(MatcherError::PlaceholderFilterBuildError {
+                        filter: definition.to_owned(),
+                        cause: format!("Invalid date format [{}]", format),
+                    });
+                }
+                Filter::Date { format: format.to_owned() }
+            }
+            ("default", [value]) => Filter::Default { value: value.clone() },
+            ("lower", _)
+            | ("upper", _)
+            | ("trim", _)
+            | ("json", _)
+            | ("date", _)
+            | ("default", _) => {
+                return Err(MatcherError::PlaceholderFilterBuildError {
+                    filter: definition.to_owned(),
+                    cause: "Wrong number or type of arguments".to_owned(),
+                })
+            }
+            _ => {
+                return Err(MatcherError::PlaceholderFilterBuildError {
+                    filter: definition.to_owned(),
+                    cause: format!("Unknown filter [{}]", name),
+                })
+            }
+        };
+        Ok(filter)
+    }
+
+    /// Applies the filter to a value.
+    /// Text filters leave the values of other types unchanged. Only the 'default' filter
+    /// produces a value when the input is missing.
+    /// It returns an error if the value cannot be transformed, e.g. if the 'date' filter
+    /// receives a value that is not a timestamp.
+    pub fn apply(&self, value: Option<Value>) -> Result<Option<Value>, MatcherError> {
+        let value = match (self, value) {
+            (Filter::Default { value: default }, None) => return Ok(Some(default.clone())),
+            (_, None) => return Ok(None),
+            (_, Some(value)) => value,
+        };
+        let result = match self {
+            Filter::Default { .. } => value,
+            Filter::Lower => map_text(value, |text| text.to_lowercase()),
+            Filter::Upper => map_text(value, |text| text.to_uppercase()),
+            Filter::Trim => map_text(value, |text| text.trim().to_owned()),
+            Filter::Json { format } => Value::Text(format.serialize(&value).ok_or_else(|| {
+                self.render_error(format!("Cannot serialize [{:?}] to JSON", value))
+            })?),
+            Filter::Date { format } => {
+                let timestamp_ms =
+                    value.get_number().and_then(Number::as_u64).ok_or_else(|| {
+                        self.render_error(format!(
+                            "[{:?}] is not a timestamp in milliseconds",
+                            value
+                        ))
+                    })?;
+                let date_time = i64::try_from(timestamp_ms)
+                    .ok()
+                    .and_then(|timestamp_ms| Utc.timestamp_millis_opt(timestamp_ms).single())
+                    .ok_or_else(|| {
+                        self.render_error(format!(
+                            "[{}] is out of the range of the supported dates",
+                            timestamp_ms
+                        ))
+                    })?;
+                Value::Text(date_time.format(format).to_string())
+            }
+        };
+        Ok(Some(result))
+    }
+
+    fn render_error(&self, cause: String) -> MatcherError {
+        MatcherError::PlaceholderFilterRenderError { filter: format!("{:?}", self), cause }
+    }
+}
+
+fn map_text<F: Fn(&str) -> String>(value: Value, function: F) -> Value {
+    match value {
+        Value::Text(text) => Value::Text(function(&text)),
+        _ => value,
+    }
+}
+
+/// Splits the content of a placeholder on the '|' characters that are not within
+/// double quotes.
+fn split_filters(content: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, character) in content.char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            FILTER_SEPARATOR if !in_string => {
+                parts.push(&content[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&content[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::collections::HashMap;
+    use tornado_common_api::{Event, Number};
+
+    fn event_with_payload(key: &str, value: Value) -> InternalEvent {
+        let mut event = Event::new("test_type");
+        event.payload.insert(key.to_owned(), value);
+        InternalEvent::new(event)
+    }
+
+    #[test]
+    fn should_build_a_placeholder_with_filters() {
+        let placeholder = Placeholder::build(
+            "rule",
+            r#"${ event.payload.host | trim | lower | default("a|b") }"#,
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+
+        assert_eq!(Accessor::Payload { keys: vec!["host".into()] }, placeholder.accessor);
+        assert_eq!(
+            vec![
+                Filter::Trim,
+                Filter::Lower,
+                Filter::Default { value: Value::Text("a|b".to_owned()) }
+            ],
+            placeholder.filters
+        );
+    }
+
+    #[test]
+    fn should_build_a_placeholder_without_filters() {
+        let placeholder =
+            Placeholder::build("rule", "${event.type}", &AccessorBuilder::new()).unwrap();
+        assert_eq!(Accessor::Type, placeholder.accessor);
+        assert!(!placeholder.has_filters());
+
+        let placeholder = Placeholder::build("rule", "constant", &AccessorBuilder::new()).unwrap();
+        assert_eq!(
+            Accessor::Constant { value: Value::Text("constant".to_owned()) },
+            placeholder.accessor
+        );
+        assert!(!placeholder.has_filters());
+    }
+
+    #[test]
+    fn build_should_fail_if_unknown_filter() {
+        assert!(
+            Placeholder::build("rule", "${event.type | camel}", &AccessorBuilder::new()).is_err()
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_arguments() {
+        assert!(Filter::build("lower(1)").is_err());
+        assert!(Filter::build("date").is_err());
+        assert!(Filter::build("date(1)").is_err());
+        assert!(Filter::build(r#"date("%Y-%m-%d", "UTC")"#).is_err());
+        assert!(Filter::build(r#"date("%Y-%")"#).is_err());
+        assert!(Filter::build("default(n/a)").is_err());
+        assert!(Filter::build("default()").is_err());
+        assert!(Filter::build(r#"json("tabs")"#).is_err());
+    }
+
+    #[test]
+    fn should_apply_the_text_filters() {
+        let event = event_with_payload("host", Value::Text("  Host01 ".to_owned()));
+
+        let lower = Placeholder::build(
+            "rule",
+            "${event.payload.host | trim | lower}",
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+        let upper =
+            Placeholder::build("rule", "${event.payload.host|upper}", &AccessorBuilder::new())
+                .unwrap();
+
+        assert_eq!("host01", lower.get(&event, None).unwrap().unwrap().as_ref());
+        assert_eq!("  HOST01 ", upper.get(&event, None).unwrap().unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_format_a_timestamp_as_date() {
+        let placeholder = Placeholder::build(
+            "rule",
+            r#"${event.created_ms | date("%Y-%m-%d %H:%M")}"#,
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.created_ms = 1_567_420_200_000;
+
+        assert_eq!(
+            "2019-09-02 10:30",
+            placeholder.get(&InternalEvent::new(event), None).unwrap().unwrap().as_ref()
+        );
+    }
+
+    #[test]
+    fn should_return_an_error_if_date_is_not_a_timestamp() {
+        let placeholder = Placeholder::build(
+            "rule",
+            r#"${event.payload.host | date("%Y")}"#,
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+
+        let text_event = event_with_payload("host", Value::Text("host01".to_owned()));
+        let too_big_event =
+            event_with_payload("host", Value::Number(Number::PosInt(u64::max_value())));
+
+        assert!(placeholder.get(&text_event, None).is_err());
+        assert!(placeholder.get(&too_big_event, None).is_err());
+    }
+
+    #[test]
+    fn should_serialize_a_value_as_json() {
+        let placeholder =
+            Placeholder::build("rule", "${event.payload.tags | json}", &AccessorBuilder::new())
+                .unwrap();
+
+        let event = event_with_payload(
+            "tags",
+            Value::Array(vec![Value::Text("linux".to_owned()), Value::Number(Number::PosInt(1))]),
+        );
+
+        assert_eq!(r#"["linux",1]"#, placeholder.get(&event, None).unwrap().unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_serialize_a_value_as_pretty_json() {
+        let placeholder = Placeholder::build(
+            "rule",
+            r#"${event.payload.tags | json("pretty")}"#,
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = event_with_payload("tags", Value::Array(vec![Value::Text("linux".to_owned())]));
+
+        assert_eq!("[\n  \"linux\"\n]", placeholder.get(&event, None).unwrap().unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_use_the_default_value_only_if_value_is_missing() {
+        let placeholder = Placeholder::build(
+            "rule",
+            r#"${_variables.missing | upper | default("n/a")}"#,
+            &AccessorBuilder::new(),
+        )
+        .unwrap();
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("n/a", placeholder.get(&event, None).unwrap().unwrap().as_ref());
+
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert("rule.missing".to_owned(), Value::Text("found".to_owned()));
+
+        assert_eq!(
+            "FOUND",
+            placeholder
+                .get(&event, Some(&ExtractedVars::new(&extracted_vars)))
+                .unwrap()
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[test]
+    fn should_split_filters_outside_of_strings() {
+        assert_eq!(
+            vec!["a ", r#" default("x|\"|y") "#, " lower"],
+            split_filters(r#"a | default("x|\"|y") | lower"#)
+        );
+    }
+}
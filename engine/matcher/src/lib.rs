@@ -7,6 +7,7 @@ pub mod error;
 pub mod interpolator;
 pub mod matcher;
 pub mod model;
+pub mod rule_test;
 pub mod validator;
 
 #[cfg(test)]
@@ -4,10 +4,12 @@
 //! An *Action* is linked to the "actions" section of a Rule and determines the outcome
 //! produced by a matching Event.
 
-use crate::accessor::{Accessor, AccessorBuilder};
+use crate::accessor::{Accessor, AccessorBuilder, ExtractedVars};
 use crate::config::rule::Action as ConfigAction;
 use crate::error::MatcherError;
+use crate::interpolator::placeholder::Placeholder;
 use crate::interpolator::StringInterpolator;
+use crate::matcher::operator::{Operator, OperatorBuilder};
 use crate::model::InternalEvent;
 use std::collections::HashMap;
 use tornado_common_api::Value;
@@ -21,16 +23,24 @@ pub struct ActionResolverBuilder {
 /// The ActionResolver builder
 impl ActionResolverBuilder {
     pub fn new() -> ActionResolverBuilder {
-        ActionResolverBuilder { accessor: AccessorBuilder::new() }
+        ActionResolverBuilder::with_accessor(AccessorBuilder::new())
+    }
+
+    /// Returns a new ActionResolverBuilder that builds the action payloads with the given
+    /// AccessorBuilder.
+    pub fn with_accessor(accessor: AccessorBuilder) -> ActionResolverBuilder {
+        ActionResolverBuilder { accessor }
     }
 
     /// Receives an array of Actions as defined in a Rule and returns an array of ActionResolver elements.
     /// Each ActionResolver is linked to an input Action definition and contains the logic needed to build
     /// the final Action object, ready to be sent to the executors.
+    /// The optional 'when' condition of each Action is built with the given OperatorBuilder.
     pub fn build(
         &self,
         rule_name: &str,
         actions: &[ConfigAction],
+        operator_builder: &OperatorBuilder,
     ) -> Result<Vec<ActionResolver>, MatcherError> {
         let mut matcher_actions = vec![];
 
@@ -39,6 +49,10 @@ impl ActionResolverBuilder {
                 rule_name: rule_name.to_owned(),
                 id: action.id.to_owned(),
                 payload: HashMap::new(),
+                when: match &action.when {
+                    Some(operator) => Some(operator_builder.build(rule_name, operator)?),
+                    None => None,
+                },
             };
 
             for (payload_key, payload_value) in &action.payload {
@@ -93,7 +107,12 @@ impl ActionResolverBuilder {
                 if interpolator.is_interpolation_required() {
                     Ok(ActionValueProcessor::Interpolator(interpolator))
                 } else {
-                    Ok(ActionValueProcessor::Accessor(accessor.build(rule_name, text)?))
+                    let placeholder = Placeholder::build(rule_name, text, accessor)?;
+                    if placeholder.has_filters() {
+                        Ok(ActionValueProcessor::Placeholder(placeholder))
+                    } else {
+                        Ok(ActionValueProcessor::Accessor(placeholder.into_accessor()))
+                    }
                 }
             }
             Value::Bool(boolean) => Ok(ActionValueProcessor::Bool(*boolean)),
@@ -108,15 +127,33 @@ pub struct ActionResolver {
     rule_name: String,
     id: String,
     payload: HashMap<String, ActionValueProcessor>,
+    when: Option<Box<dyn Operator>>,
 }
 
 impl ActionResolver {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns whether the Action has to be executed for the InternalEvent.
+    /// This is true if the Action has no 'when' condition or if the condition evaluates to true.
+    pub fn is_triggered(
+        &self,
+        event: &InternalEvent,
+        extracted_vars: Option<&ExtractedVars>,
+    ) -> bool {
+        match &self.when {
+            Some(operator) => operator.evaluate(event, extracted_vars),
+            None => true,
+        }
+    }
+
     /// Builds an Action by extracting the required data from the InternalEvent.
     /// The outcome is a fully resolved Action ready to be processed by the executors.
     pub fn execute(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
     ) -> Result<Action, MatcherError> {
         let mut action = Action { id: self.id.to_owned(), payload: HashMap::new() };
 
@@ -134,6 +171,7 @@ impl ActionResolver {
 #[derive(Debug, PartialEq)]
 enum ActionValueProcessor {
     Accessor(Accessor),
+    Placeholder(Placeholder),
     Null,
     Bool(bool),
     Number(Number),
@@ -148,7 +186,7 @@ impl ActionValueProcessor {
         rule_name: &str,
         action_id: &str,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
     ) -> Result<Value, MatcherError> {
         match self {
             ActionValueProcessor::Accessor(accessor) => Ok(accessor
@@ -159,6 +197,19 @@ impl ActionValueProcessor {
                     cause: format!("Accessor [{:?}] returned empty value.", accessor),
                 })?
                 .into_owned()),
+            ActionValueProcessor::Placeholder(placeholder) => Ok(placeholder
+                .get(event, extracted_vars)
+                .map_err(|e| MatcherError::CreateActionError {
+                    action_id: action_id.to_owned(),
+                    rule_name: rule_name.to_owned(),
+                    cause: format!("{}", e),
+                })?
+                .ok_or(MatcherError::CreateActionError {
+                    action_id: action_id.to_owned(),
+                    rule_name: rule_name.to_owned(),
+                    cause: format!("Placeholder [{:?}] returned empty value.", placeholder),
+                })?
+                .into_owned()),
             ActionValueProcessor::Interpolator(interpolator) => {
                 interpolator.render(event, extracted_vars).map(Value::Text)
             }
@@ -195,6 +246,7 @@ impl ActionValueProcessor {
 mod test {
     use super::*;
     use crate::accessor::Accessor;
+    use crate::config::rule::Operator as ConfigOperator;
     use maplit::*;
     use std::collections::HashMap;
     use tornado_common_api::{Event, Payload};
@@ -202,14 +254,20 @@ mod test {
     #[test]
     fn should_build_a_matcher_action() {
         // Arrange
-        let mut action = ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         let value = "constant value".to_owned();
         action.payload.insert("key".to_owned(), Value::Text(value.clone()));
 
         let config = vec![action];
 
         // Act
-        let actions = ActionResolverBuilder::new().build("", &config).unwrap();
+        let actions =
+            ActionResolverBuilder::new().build("", &config, &OperatorBuilder::new()).unwrap();
 
         // Assert
         assert_eq!(1, actions.len());
@@ -227,7 +285,12 @@ mod test {
     #[test]
     fn action_resolver_builder_should_identify_whether_interpolation_is_required() {
         // Arrange
-        let mut action = ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
 
         action.payload.insert("constant".to_owned(), Value::Text("constant value".to_owned()));
         action.payload.insert("expression".to_owned(), Value::Text("${event.type}".to_owned()));
@@ -239,7 +302,8 @@ mod test {
         let config = vec![action];
 
         // Act
-        let actions = ActionResolverBuilder::new().build("", &config).unwrap();
+        let actions =
+            ActionResolverBuilder::new().build("", &config, &OperatorBuilder::new()).unwrap();
 
         // Assert
         assert_eq!(1, actions.len());
@@ -266,8 +330,12 @@ mod test {
     #[test]
     fn should_build_an_action() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("type".to_owned(), Value::Text("${event.type}".to_owned()));
         config_action
             .payload
@@ -291,7 +359,9 @@ mod test {
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -310,7 +380,8 @@ mod test {
             .insert("rule_for_test.test2".to_owned(), Value::Text("var_test_2_value".to_owned()));
 
         // Act
-        let result = matcher_action.execute(&event, Some(&extracted_vars)).unwrap();
+        let result =
+            matcher_action.execute(&event, Some(&ExtractedVars::new(&extracted_vars))).unwrap();
 
         // Assert
         assert_eq!(&"an_action_id", &result.id);
@@ -326,15 +397,21 @@ mod test {
     #[test]
     fn should_build_an_action_with_text_to_be_interpolated_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action
             .payload
             .insert("type".to_owned(), Value::Text("The event type is: ${event.type}".to_owned()));
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -357,16 +434,120 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_build_an_action_with_filtered_placeholders_in_config() {
+        // Arrange
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
+        config_action
+            .payload
+            .insert("type".to_owned(), Value::Text("${event.type | upper}".to_owned()));
+        config_action.payload.insert(
+            "message".to_owned(),
+            Value::Text("The event type is: ${event.type | upper}".to_owned()),
+        );
+        config_action.payload.insert(
+            "var".to_owned(),
+            Value::Text(r#"${_variables.missing | default(0)}"#.to_owned()),
+        );
+
+        let rule_name = "rule_for_test";
+        let config = vec![config_action];
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
+        let matcher_action = &matcher_actions[0];
+
+        let event = InternalEvent::new(Event::new("an_event_type"));
+
+        // Act
+        let result = matcher_action.execute(&event, None).unwrap();
+
+        // Assert
+        match &matcher_action.payload["type"] {
+            ActionValueProcessor::Placeholder(..) => assert!(true),
+            _ => assert!(false),
+        }
+        assert_eq!(&"AN_EVENT_TYPE", &result.payload.get("type").unwrap());
+        assert_eq!(&"The event type is: AN_EVENT_TYPE", &result.payload.get("message").unwrap());
+        assert_eq!(&Value::Number(Number::PosInt(0)), result.payload.get("var").unwrap());
+    }
+
+    #[test]
+    fn should_evaluate_the_when_condition_of_an_action() {
+        // Arrange
+        let config = vec![
+            ConfigAction {
+                id: "always".to_owned(),
+                payload: HashMap::new(),
+                when: None,
+                template: None,
+            },
+            ConfigAction {
+                id: "only_for_email".to_owned(),
+                payload: HashMap::new(),
+                when: Some(ConfigOperator::Equal {
+                    first: Value::Text("${event.type}".to_owned()),
+                    second: Value::Text("email".to_owned()),
+                }),
+                template: None,
+            },
+        ];
+
+        // Act
+        let actions =
+            ActionResolverBuilder::new().build("rule", &config, &OperatorBuilder::new()).unwrap();
+
+        // Assert
+        let email = InternalEvent::new(Event::new("email"));
+        let trap = InternalEvent::new(Event::new("trap"));
+
+        assert!(actions[0].is_triggered(&email, None));
+        assert!(actions[0].is_triggered(&trap, None));
+        assert!(actions[1].is_triggered(&email, None));
+        assert!(!actions[1].is_triggered(&trap, None));
+    }
+
+    #[test]
+    fn build_should_fail_if_the_when_condition_is_not_valid() {
+        // Arrange
+        let config = vec![ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: Some(ConfigOperator::Equal {
+                first: Value::Text("${event.unknown}".to_owned()),
+                second: Value::Text("email".to_owned()),
+            }),
+            template: None,
+        }];
+
+        // Act
+        let actions = ActionResolverBuilder::new().build("rule", &config, &OperatorBuilder::new());
+
+        // Assert
+        assert!(actions.is_err());
+    }
+
     #[test]
     fn should_build_an_action_with_bool_type_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("type".to_owned(), Value::Bool(true));
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -389,13 +570,19 @@ mod test {
     #[test]
     fn should_build_an_action_with_null_type_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("type".to_owned(), Value::Null);
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -418,13 +605,19 @@ mod test {
     #[test]
     fn should_build_an_action_with_number_type_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("type".to_owned(), Value::Number(Number::PosInt(123456)));
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -447,8 +640,12 @@ mod test {
     #[test]
     fn should_build_an_action_with_array_type_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert(
             "type".to_owned(),
             Value::Array(vec![
@@ -460,7 +657,9 @@ mod test {
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -490,8 +689,12 @@ mod test {
     #[test]
     fn should_build_an_action_with_map_type_in_config() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("type".to_owned(),
                                      Value::Map(hashmap!["one".to_owned() => Value::Number(Number::Float(123456.0)),
                                             "two".to_owned() => Value::Text("${event.type}".to_owned())]
@@ -499,7 +702,9 @@ mod test {
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -526,8 +731,12 @@ mod test {
     #[test]
     fn should_build_an_action_with_maps_in_payload() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action
             .payload
             .insert("payload_body".to_owned(), Value::Text("${event.payload.body}".to_owned()));
@@ -538,7 +747,9 @@ mod test {
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut body = HashMap::new();
@@ -565,13 +776,19 @@ mod test {
     #[test]
     fn should_put_the_whole_event_in_the_payload() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action.payload.insert("event".to_owned(), Value::Text("${event}".to_owned()));
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
@@ -597,15 +814,21 @@ mod test {
     #[test]
     fn should_put_the_whole_event_payload_in_the_action_payload() {
         // Arrange
-        let mut config_action =
-            ConfigAction { id: "an_action_id".to_owned(), payload: HashMap::new() };
+        let mut config_action = ConfigAction {
+            id: "an_action_id".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
         config_action
             .payload
             .insert("event_payload".to_owned(), Value::Text("${event.payload}".to_owned()));
 
         let rule_name = "rule_for_test";
         let config = vec![config_action];
-        let matcher_actions = ActionResolverBuilder::new().build(rule_name, &config).unwrap();
+        let matcher_actions = ActionResolverBuilder::new()
+            .build(rule_name, &config, &OperatorBuilder::new())
+            .unwrap();
         let matcher_action = &matcher_actions[0];
 
         let mut payload = Payload::new();
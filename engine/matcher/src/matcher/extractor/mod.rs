@@ -4,14 +4,20 @@
 //! An *Extractor* is linked to the "WITH" clause of a Rule and determines the value
 //! of dynamically generated variables.
 
-use crate::accessor::{Accessor, AccessorBuilder};
-use crate::config::rule::Extractor;
+use crate::accessor::{Accessor, AccessorBuilder, ExtractedVars};
+use crate::config::rule::{Extractor, ExtractorKind};
 use crate::error::MatcherError;
+use crate::matcher::extractor::modifier::ValueModifier;
 use crate::model::InternalEvent;
+use jmespath::{Expression, Variable};
 use log::*;
-use regex::Regex as RustRegex;
+use regex::{Captures, Regex as RustRegex};
 use std::collections::HashMap;
-use tornado_common_api::Value;
+use tornado_common_api::{Number, Value};
+
+const DEFAULT_KEY_VALUE_SEPARATOR: &str = "=";
+
+pub mod modifier;
 
 /// The MatcherExtractor instance builder.
 #[derive(Default)]
@@ -22,7 +28,13 @@ pub struct MatcherExtractorBuilder {
 impl MatcherExtractorBuilder {
     /// Returns a new MatcherExtractorBuilder instance.
     pub fn new() -> MatcherExtractorBuilder {
-        MatcherExtractorBuilder { accessor: AccessorBuilder::new() }
+        MatcherExtractorBuilder::with_accessor(AccessorBuilder::new())
+    }
+
+    /// Returns a new MatcherExtractorBuilder that builds the extractor targets with the given
+    /// AccessorBuilder.
+    pub fn with_accessor(accessor: AccessorBuilder) -> MatcherExtractorBuilder {
+        MatcherExtractorBuilder { accessor }
     }
 
     /// Returns a specific MatcherExtractor instance based on the matcher.extractor rule configuration.
@@ -33,7 +45,7 @@ impl MatcherExtractorBuilder {
     ///
     ///    use tornado_common_api::Event;
     ///    use tornado_engine_matcher::matcher::extractor::MatcherExtractorBuilder;
-    ///    use tornado_engine_matcher::config::rule::{Extractor, ExtractorRegex};
+    ///    use tornado_engine_matcher::config::rule::{Extractor, ExtractorKind, ExtractorRegex};
     ///    use tornado_engine_matcher::model::InternalEvent;
     ///    use std::collections::HashMap;
     ///
@@ -43,10 +55,12 @@ impl MatcherExtractorBuilder {
     ///        String::from("extracted_temp"),
     ///        Extractor {
     ///            from: String::from("${event.type}"),
-    ///            regex: ExtractorRegex {
+    ///            kind: ExtractorKind::Regex(ExtractorRegex {
     ///                regex: String::from(r"[0-9]+"),
-    ///                group_match_idx: 0,
-    ///            },
+    ///                group_match_idx: Some(0),
+    ///                all_matches: false,
+    ///            }),
+    ///            modifiers_post: vec![],
     ///        },
     ///    );
     ///
@@ -72,18 +86,23 @@ impl MatcherExtractorBuilder {
         rule_name: &str,
         config: &HashMap<String, Extractor>,
     ) -> Result<MatcherExtractor, MatcherError> {
-        let mut matcher_extractor = MatcherExtractor { extractors: HashMap::new() };
+        let mut targets = HashMap::new();
         for (key, v) in config.iter() {
-            matcher_extractor.extractors.insert(
-                key.to_owned(),
-                VariableExtractor::build(
-                    rule_name,
-                    key,
-                    &v.regex.regex,
-                    v.regex.group_match_idx,
-                    self.accessor.build(rule_name, &v.from)?,
-                )?,
-            );
+            targets.insert(key.to_owned(), self.accessor.build(rule_name, &v.from)?);
+        }
+
+        let mut matcher_extractor = MatcherExtractor { extractors: vec![] };
+        for key in extraction_order(rule_name, &targets)? {
+            let v = &config[&key];
+            let target = targets.remove(&key).expect("the target of each key should be present");
+            let extractor = VariableExtractor::build(
+                rule_name,
+                &key,
+                &v.kind,
+                target,
+                v.modifiers_post.iter().map(ValueModifier::build).collect::<Result<Vec<_>, _>>()?,
+            )?;
+            matcher_extractor.extractors.push((key, extractor));
         }
 
         trace!(
@@ -95,15 +114,76 @@ impl MatcherExtractorBuilder {
     }
 }
 
+/// Returns the names of the variables of a rule sorted so that each variable comes after
+/// all the variables of the same rule read by its target.
+/// The order is deterministic, as the variables are visited by name and each one is
+/// preceded by its own dependencies.
+/// Returns an error if the variables have circular dependencies.
+pub fn extraction_order(
+    rule_name: &str,
+    targets: &HashMap<String, Accessor>,
+) -> Result<Vec<String>, MatcherError> {
+    let mut keys: Vec<&str> = targets.keys().map(String::as_str).collect();
+    keys.sort();
+
+    let mut order = vec![];
+    let mut path = vec![];
+    for key in keys {
+        visit_dependencies(rule_name, key, targets, &mut path, &mut order)?;
+    }
+    Ok(order)
+}
+
+fn visit_dependencies<'a>(
+    rule_name: &str,
+    key: &'a str,
+    targets: &'a HashMap<String, Accessor>,
+    path: &mut Vec<&'a str>,
+    order: &mut Vec<String>,
+) -> Result<(), MatcherError> {
+    if order.iter().any(|ordered| ordered == key) {
+        return Ok(());
+    }
+    if let Some(position) = path.iter().position(|visiting| *visiting == key) {
+        return Err(MatcherError::CircularExtractedVariablesError {
+            rule_name: rule_name.to_owned(),
+            variables: path[position..].iter().map(|variable| (*variable).to_owned()).collect(),
+        });
+    }
+
+    let prefix = format!("{}.", rule_name);
+    let mut dependencies: Vec<&str> = targets[key]
+        .extracted_var_keys()
+        .into_iter()
+        .filter(|var_key| var_key.starts_with(&prefix))
+        .map(|var_key| &var_key[prefix.len()..])
+        .filter_map(|dependency| targets.get_key_value(dependency).map(|(key, _)| key.as_str()))
+        .collect();
+    dependencies.sort();
+    dependencies.dedup();
+
+    path.push(key);
+    for dependency in dependencies {
+        visit_dependencies(rule_name, dependency, targets, path, order)?;
+    }
+    path.pop();
+
+    order.push(key.to_owned());
+    Ok(())
+}
+
+/// Extracts the variables of a rule.
+/// The variables are processed in dependency order, so that an extractor can read the
+/// variables of the same rule through the "${_variables.name}" accessor.
 #[derive(Debug)]
 pub struct MatcherExtractor {
-    extractors: HashMap<String, VariableExtractor>,
+    extractors: Vec<(String, VariableExtractor)>,
 }
 
 impl MatcherExtractor {
     /*
     /// Returns the value of the variable named 'key' generated from the provided Event.
-    fn extract(&self, key: &str, event: &InternalEvent, extracted_vars: Option<&HashMap<String, Value>>) -> Result<String, MatcherError> {
+    fn extract(&self, key: &str, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> Result<String, MatcherError> {
         let extracted = self.extractors.get(key).and_then(|extractor| extractor.extract(event, extracted_vars));
         self.check_extracted(key, extracted)
     }
@@ -119,18 +199,16 @@ impl MatcherExtractor {
         extracted_vars: &mut HashMap<String, Value>,
     ) -> Result<(), MatcherError> {
         for (key, extractor) in &self.extractors {
-            let value =
-                self.check_extracted(key, extractor.extract(event, Some(extracted_vars)))?;
-            extracted_vars.insert(extractor.scoped_key.clone(), Value::Text(value));
+            let value = self.check_extracted(
+                key,
+                extractor.extract(event, Some(&ExtractedVars::new(extracted_vars))),
+            )?;
+            extracted_vars.insert(extractor.scoped_key.clone(), value);
         }
         Ok(())
     }
 
-    fn check_extracted(
-        &self,
-        key: &str,
-        extracted: Option<String>,
-    ) -> Result<String, MatcherError> {
+    fn check_extracted(&self, key: &str, extracted: Option<Value>) -> Result<Value, MatcherError> {
         match extracted {
             Some(value) => Ok(value),
             None => {
@@ -140,45 +218,234 @@ impl MatcherExtractor {
     }
 }
 
+/// Extracts a variable from a value of the Event.
+/// The extracted value is then passed through the modifiers, in order.
 #[derive(Debug)]
 struct VariableExtractor {
     scoped_key: String,
-    regex: RustRegex,
-    group_match_idx: u16,
     target: Accessor,
+    extractor: ValueExtractor,
+    modifiers: Vec<ValueModifier>,
+}
+
+/// The strategy used to generate the value of a variable from the target value.
+#[derive(Debug)]
+enum ValueExtractor {
+    /// Applies a regex to the target text.
+    /// The extracted value is:
+    /// - a Text with the content of the group at index 'group_match_idx', if it is set;
+    /// - otherwise, a Map with the content of all the named groups of the regex.
+    ///
+    /// When 'all_matches' is true, the regex is applied to all non-overlapping matches and
+    /// the extracted value is an Array with one element for each match.
+    Regex { regex: RustRegex, group_match_idx: Option<u16>, all_matches: bool },
+    /// Parses the target text as a JSON document.
+    Json,
+    /// Splits the target text into a Map of key-value pairs.
+    /// When no 'pair_separator' is set, the pairs are separated by whitespaces.
+    KeyValue { pair_separator: Option<String>, key_value_separator: String },
+    /// Evaluates a JMESPath expression against the target.
+    /// A target text is parsed as a JSON document before the evaluation.
+    /// The numbers in the result are always converted to floats.
+    Jmespath { expression: Expression<'static> },
 }
 
 impl VariableExtractor {
     pub fn build(
         rule_name: &str,
         key: &str,
-        regex: &str,
-        group_match_idx: u16,
+        kind: &ExtractorKind,
         target: Accessor,
+        modifiers: Vec<ValueModifier>,
     ) -> Result<VariableExtractor, MatcherError> {
-        let regex = RustRegex::new(regex).map_err(|e| MatcherError::ExtractorBuildFailError {
-            message: format!("Cannot parse regex [{}]", regex),
-            cause: e.to_string(),
-        })?;
-
         Ok(VariableExtractor {
             scoped_key: format!("{}.{}", rule_name, key),
             target,
-            group_match_idx,
-            regex,
+            extractor: ValueExtractor::build(key, kind)?,
+            modifiers,
         })
     }
 
     pub fn extract(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
-    ) -> Option<String> {
-        let cow_value = self.target.get(event, extracted_vars)?;
-        let value = cow_value.get_text()?;
-        let captures = self.regex.captures(value)?;
-        let group_idx = self.group_match_idx;
-        captures.get(group_idx as usize).map(|matched| matched.as_str().to_owned())
+        extracted_vars: Option<&ExtractedVars>,
+    ) -> Option<Value> {
+        let value = self.target.get(event, extracted_vars)?;
+        let value = self.extractor.extract(&value);
+        self.modifiers.iter().fold(value, |value, modifier| modifier.apply(value))
+    }
+}
+
+impl ValueExtractor {
+    fn build(key: &str, kind: &ExtractorKind) -> Result<ValueExtractor, MatcherError> {
+        match kind {
+            ExtractorKind::Regex(config) => {
+                let regex = RustRegex::new(&config.regex).map_err(|e| {
+                    MatcherError::ExtractorBuildFailError {
+                        message: format!("Cannot parse regex [{}]", config.regex),
+                        cause: e.to_string(),
+                    }
+                })?;
+
+                if config.group_match_idx.is_none()
+                    && regex.capture_names().all(|name| name.is_none())
+                {
+                    return Err(MatcherError::ExtractorBuildFailError {
+                        message: format!("Cannot build the extractor for variable [{}]", key),
+                        cause: format!(
+                            "The regex [{}] has no named groups and no 'group_match_idx' is specified",
+                            regex
+                        ),
+                    });
+                }
+
+                Ok(ValueExtractor::Regex {
+                    regex,
+                    group_match_idx: config.group_match_idx,
+                    all_matches: config.all_matches,
+                })
+            }
+            ExtractorKind::Json(_) => Ok(ValueExtractor::Json),
+            ExtractorKind::KeyValue(config) => {
+                let pair_separator = config.pair_separator.clone();
+                let key_value_separator = config
+                    .key_value_separator
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_KEY_VALUE_SEPARATOR.to_owned());
+                if pair_separator.as_deref() == Some("") || key_value_separator.is_empty() {
+                    return Err(MatcherError::ExtractorBuildFailError {
+                        message: format!("Cannot build the extractor for variable [{}]", key),
+                        cause: "The key-value separators must not be empty".to_owned(),
+                    });
+                }
+                Ok(ValueExtractor::KeyValue { pair_separator, key_value_separator })
+            }
+            ExtractorKind::Jmespath(config) => {
+                let expression = jmespath::compile(&config.expression).map_err(|e| {
+                    MatcherError::ExtractorBuildFailError {
+                        message: format!(
+                            "Cannot parse JMESPath expression [{}]",
+                            config.expression
+                        ),
+                        cause: e.to_string(),
+                    }
+                })?;
+                Ok(ValueExtractor::Jmespath { expression })
+            }
+        }
+    }
+
+    fn extract(&self, value: &Value) -> Option<Value> {
+        match self {
+            ValueExtractor::Regex { regex, group_match_idx, all_matches } => {
+                let text = value.get_text()?;
+                if *all_matches {
+                    let matches: Vec<Value> = regex
+                        .captures_iter(text)
+                        .filter_map(|captures| {
+                            ValueExtractor::extract_from_captures(
+                                regex,
+                                *group_match_idx,
+                                &captures,
+                            )
+                        })
+                        .collect();
+                    if matches.is_empty() {
+                        None
+                    } else {
+                        Some(Value::Array(matches))
+                    }
+                } else {
+                    let captures = regex.captures(text)?;
+                    ValueExtractor::extract_from_captures(regex, *group_match_idx, &captures)
+                }
+            }
+            ValueExtractor::Json => serde_json::from_str(value.get_text()?).ok(),
+            ValueExtractor::KeyValue { pair_separator, key_value_separator } => {
+                let text = value.get_text()?;
+                let pairs: Box<dyn Iterator<Item = &str>> = match pair_separator {
+                    Some(separator) => Box::new(text.split(separator.as_str())),
+                    None => Box::new(text.split_whitespace()),
+                };
+                let mut map = HashMap::new();
+                for pair in pairs {
+                    let mut split = pair.splitn(2, key_value_separator.as_str());
+                    if let (Some(key), Some(value)) = (split.next(), split.next()) {
+                        let key = key.trim();
+                        if !key.is_empty() {
+                            map.insert(key.to_owned(), Value::Text(value.trim().to_owned()));
+                        }
+                    }
+                }
+                if map.is_empty() {
+                    None
+                } else {
+                    Some(Value::Map(map))
+                }
+            }
+            ValueExtractor::Jmespath { expression } => {
+                let result = match value {
+                    Value::Text(text) => expression.search(Variable::from_json(text).ok()?),
+                    _ => expression.search(value),
+                };
+                match result {
+                    // An expression that selects nothing produces no value
+                    Ok(variable) if variable.is_null() => None,
+                    Ok(variable) => variable_to_value(&variable),
+                    Err(e) => {
+                        debug!(
+                            "ValueExtractor - cannot evaluate JMESPath expression [{}]: {}",
+                            expression, e
+                        );
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn extract_from_captures(
+        regex: &RustRegex,
+        group_match_idx: Option<u16>,
+        captures: &Captures,
+    ) -> Option<Value> {
+        match group_match_idx {
+            Some(group_idx) => captures
+                .get(group_idx as usize)
+                .map(|matched| Value::Text(matched.as_str().to_owned())),
+            None => {
+                let mut groups = HashMap::new();
+                for name in regex.capture_names().flatten() {
+                    if let Some(matched) = captures.name(name) {
+                        groups.insert(name.to_owned(), Value::Text(matched.as_str().to_owned()));
+                    }
+                }
+                Some(Value::Map(groups))
+            }
+        }
+    }
+}
+
+fn variable_to_value(variable: &Variable) -> Option<Value> {
+    match variable {
+        Variable::String(text) => Some(Value::Text(text.to_owned())),
+        Variable::Bool(boolean) => Some(Value::Bool(*boolean)),
+        Variable::Number(number) => Some(Value::Number(Number::Float(*number))),
+        Variable::Object(values) => {
+            let mut map = HashMap::new();
+            for (key, value) in values {
+                map.insert(key.to_owned(), variable_to_value(value)?);
+            }
+            Some(Value::Map(map))
+        }
+        Variable::Array(values) => values
+            .iter()
+            .map(|value| variable_to_value(value))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Variable::Null => Some(Value::Null),
+        Variable::Expref(_) => None,
     }
 }
 
@@ -186,18 +453,20 @@ impl VariableExtractor {
 mod test {
     use super::*;
     use crate::accessor::AccessorBuilder;
-    use crate::config::rule::ExtractorRegex;
+    use crate::config::rule::{
+        ExtractorJmespath, ExtractorJson, ExtractorKeyValue, ExtractorRegex, Modifier,
+    };
     use std::collections::HashMap;
-    use tornado_common_api::Event;
+    use tornado_common_api::{Event, Number};
 
     #[test]
     fn should_build_an_extractor() {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            "",
-            0,
+            &regex_kind("", Some(0), false),
             AccessorBuilder::new().build("", "").unwrap(),
+            vec![],
         );
         assert!(extractor.is_ok());
     }
@@ -207,9 +476,9 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            "[",
-            0,
+            &regex_kind("[", Some(0), false),
             AccessorBuilder::new().build("", "").unwrap(),
+            vec![],
         );
         assert!(extractor.is_err());
     }
@@ -219,16 +488,16 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?",
-            0,
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", Some(0), false),
             AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
         )
         .unwrap();
 
         let event = new_event("http://stackoverflow.com/");
 
         assert_eq!(
-            "http://stackoverflow.com/".to_owned(),
+            Value::Text("http://stackoverflow.com/".to_owned()),
             extractor.extract(&event, None).unwrap()
         );
     }
@@ -238,15 +507,15 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?",
-            1,
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", Some(1), false),
             AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
         )
         .unwrap();
 
         let event = new_event("http://stackoverflow.com/");
 
-        assert_eq!("http".to_owned(), extractor.extract(&event, None).unwrap());
+        assert_eq!(Value::Text("http".to_owned()), extractor.extract(&event, None).unwrap());
     }
 
     #[test]
@@ -254,15 +523,18 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?",
-            2,
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", Some(2), false),
             AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
         )
         .unwrap();
 
         let event = new_event("http://stackoverflow.com/");
 
-        assert_eq!("stackoverflow.com".to_owned(), extractor.extract(&event, None).unwrap());
+        assert_eq!(
+            Value::Text("stackoverflow.com".to_owned()),
+            extractor.extract(&event, None).unwrap()
+        );
     }
 
     #[test]
@@ -270,9 +542,9 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?",
-            10000,
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", Some(10000), false),
             AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
         )
         .unwrap();
 
@@ -286,9 +558,9 @@ mod test {
         let extractor = VariableExtractor::build(
             "rule_name",
             "key",
-            r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?",
-            10000,
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", Some(10000), false),
             AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
         )
         .unwrap();
 
@@ -297,6 +569,295 @@ mod test {
         assert!(extractor.extract(&event, None).is_none());
     }
 
+    #[test]
+    fn build_should_fail_if_no_group_idx_and_no_named_groups() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &regex_kind(r"(https?|ftp)://([^/\r\n]+)(/[^\r\n]*)?", None, false),
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
+        );
+        assert!(extractor.is_err());
+    }
+
+    #[test]
+    fn should_match_and_return_named_groups() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &regex_kind(
+                r"(?P<protocol>https?|ftp)://(?P<host>[^/\r\n]+)(?P<path>/[^\r\n]+)?",
+                None,
+                false,
+            ),
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = new_event("http://stackoverflow.com/");
+
+        let mut expected = HashMap::new();
+        expected.insert("protocol".to_owned(), Value::Text("http".to_owned()));
+        expected.insert("host".to_owned(), Value::Text("stackoverflow.com".to_owned()));
+
+        assert_eq!(Value::Map(expected), extractor.extract(&event, None).unwrap());
+    }
+
+    #[test]
+    fn should_match_and_return_the_group_of_all_matches() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &regex_kind(r"host=([a-z0-9]+)", Some(1), true),
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = new_event("host=one, host=two; host=three");
+
+        assert_eq!(
+            Value::Array(vec![
+                Value::Text("one".to_owned()),
+                Value::Text("two".to_owned()),
+                Value::Text("three".to_owned()),
+            ]),
+            extractor.extract(&event, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_match_and_return_the_named_groups_of_all_matches() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &regex_kind(r"(?P<key>[a-z]+)=(?P<value>[0-9]+)", None, true),
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = new_event("cpu=95 mem=30");
+
+        let mut first = HashMap::new();
+        first.insert("key".to_owned(), Value::Text("cpu".to_owned()));
+        first.insert("value".to_owned(), Value::Text("95".to_owned()));
+        let mut second = HashMap::new();
+        second.insert("key".to_owned(), Value::Text("mem".to_owned()));
+        second.insert("value".to_owned(), Value::Text("30".to_owned()));
+
+        assert_eq!(
+            Value::Array(vec![Value::Map(first), Value::Map(second)]),
+            extractor.extract(&event, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_return_none_if_all_matches_finds_nothing() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &regex_kind(r"host=([a-z0-9]+)", Some(1), true),
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = new_event("no hosts here");
+
+        assert!(extractor.extract(&event, None).is_none());
+    }
+
+    #[test]
+    fn should_parse_a_json_text() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &ExtractorKind::Json(ExtractorJson {}),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = event_with_body(Value::Text(
+            r#"{"host": "server-01", "ports": [80, 443], "up": true}"#.to_owned(),
+        ));
+
+        let mut expected = HashMap::new();
+        expected.insert("host".to_owned(), Value::Text("server-01".to_owned()));
+        expected.insert(
+            "ports".to_owned(),
+            Value::Array(vec![
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(443)),
+            ]),
+        );
+        expected.insert("up".to_owned(), Value::Bool(true));
+
+        assert_eq!(Some(Value::Map(expected)), extractor.extract(&event, None));
+    }
+
+    #[test]
+    fn should_return_none_if_not_valid_json() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &ExtractorKind::Json(ExtractorJson {}),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = event_with_body(Value::Text(r#"{"host": "#.to_owned()));
+
+        assert!(extractor.extract(&event, None).is_none());
+    }
+
+    #[test]
+    fn should_split_key_value_pairs_separated_by_whitespaces() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &ExtractorKind::KeyValue(ExtractorKeyValue {
+                pair_separator: None,
+                key_value_separator: None,
+            }),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = event_with_body(Value::Text(
+            "action=drop  src=10.0.0.1 dst= message query=a=b".to_owned(),
+        ));
+
+        let mut expected = HashMap::new();
+        expected.insert("action".to_owned(), Value::Text("drop".to_owned()));
+        expected.insert("src".to_owned(), Value::Text("10.0.0.1".to_owned()));
+        expected.insert("dst".to_owned(), Value::Text("".to_owned()));
+        expected.insert("query".to_owned(), Value::Text("a=b".to_owned()));
+
+        assert_eq!(Some(Value::Map(expected)), extractor.extract(&event, None));
+    }
+
+    #[test]
+    fn should_split_key_value_pairs_with_custom_separators() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &ExtractorKind::KeyValue(ExtractorKeyValue {
+                pair_separator: Some(";".to_owned()),
+                key_value_separator: Some(":".to_owned()),
+            }),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = event_with_body(Value::Text("user: John Doe; role : admin".to_owned()));
+
+        let mut expected = HashMap::new();
+        expected.insert("user".to_owned(), Value::Text("John Doe".to_owned()));
+        expected.insert("role".to_owned(), Value::Text("admin".to_owned()));
+
+        assert_eq!(Some(Value::Map(expected)), extractor.extract(&event, None));
+        assert!(extractor
+            .extract(&event_with_body(Value::Text("no pairs".to_owned())), None)
+            .is_none());
+    }
+
+    #[test]
+    fn build_should_fail_if_empty_key_value_separator() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &ExtractorKind::KeyValue(ExtractorKeyValue {
+                pair_separator: Some("".to_owned()),
+                key_value_separator: None,
+            }),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        );
+        assert!(extractor.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_a_jmespath_expression_on_a_json_text() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &jmespath_kind("data.hosts[?up].name"),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let event = event_with_body(Value::Text(
+            r#"{"data": {"hosts": [{"name": "a", "up": true}, {"name": "b", "up": false}]}}"#
+                .to_owned(),
+        ));
+
+        assert_eq!(
+            Some(Value::Array(vec![Value::Text("a".to_owned())])),
+            extractor.extract(&event, None)
+        );
+    }
+
+    #[test]
+    fn should_evaluate_a_jmespath_expression_on_a_value_of_the_event() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &jmespath_kind("data.temperature"),
+            AccessorBuilder::new().build("", "${event.payload}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        let mut data = HashMap::new();
+        data.insert("temperature".to_owned(), Value::Number(Number::PosInt(44)));
+        let mut event = Event::new("test_type");
+        event.payload.insert("data".to_owned(), Value::Map(data));
+
+        assert_eq!(
+            Some(Value::Number(Number::Float(44.0))),
+            extractor.extract(&InternalEvent::new(event), None)
+        );
+    }
+
+    #[test]
+    fn should_return_none_if_jmespath_expression_selects_nothing() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &jmespath_kind("data.missing"),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        )
+        .unwrap();
+
+        assert!(extractor
+            .extract(&event_with_body(Value::Text(r#"{"data": {}}"#.to_owned())), None)
+            .is_none());
+        assert!(extractor
+            .extract(&event_with_body(Value::Text("not json".to_owned())), None)
+            .is_none());
+    }
+
+    #[test]
+    fn build_should_fail_if_not_valid_jmespath_expression() {
+        let extractor = VariableExtractor::build(
+            "rule_name",
+            "key",
+            &jmespath_kind("data.["),
+            AccessorBuilder::new().build("", "${event.payload.body}").unwrap(),
+            vec![],
+        );
+        assert!(extractor.is_err());
+    }
+
     #[test]
     fn should_extract_all_variables_and_return_true() {
         let mut from_config = HashMap::new();
@@ -305,7 +866,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -313,7 +879,12 @@ mod test {
             String::from("extracted_text"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[a-z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -337,7 +908,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -345,7 +921,12 @@ mod test {
             String::from("extracted_none"),
             Extractor {
                 from: String::from("${event.payload.nothing}"),
-                regex: ExtractorRegex { regex: String::from(r"[a-z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -357,7 +938,205 @@ mod test {
         assert!(extractor.process_all(&mut event, &mut extracted_vars).is_err());
     }
 
+    #[test]
+    fn should_apply_the_modifiers_to_the_extracted_variables() {
+        let mut from_config = HashMap::new();
+
+        from_config.insert(
+            String::from("temperature"),
+            Extractor {
+                from: String::from("${event.type}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"temp=([^C]+)"),
+                    group_match_idx: Some(1),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![
+                    Modifier::Replace { find: "'".to_owned(), replace: "".to_owned() },
+                    Modifier::Trim,
+                    Modifier::ToNumber,
+                ],
+            },
+        );
+
+        from_config.insert(
+            String::from("unit"),
+            Extractor {
+                from: String::from("${event.type}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[A-Z]$"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![Modifier::Lowercase],
+            },
+        );
+
+        from_config.insert(
+            String::from("room"),
+            Extractor {
+                from: String::from("${event.type}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"room=([a-z]+)"),
+                    group_match_idx: Some(1),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![
+                    Modifier::Default { value: Value::Text("unknown".to_owned()) },
+                    Modifier::Uppercase,
+                ],
+            },
+        );
+
+        let extractor = MatcherExtractorBuilder::new().build("rule", &from_config).unwrap();
+
+        let event = new_event("temp= 44'C");
+        let mut extracted_vars = HashMap::new();
+
+        extractor.process_all(&event, &mut extracted_vars).unwrap();
+
+        assert_eq!(3, extracted_vars.len());
+        assert_eq!(
+            &Value::Number(Number::PosInt(44)),
+            extracted_vars.get("rule.temperature").unwrap()
+        );
+        assert_eq!("c", extracted_vars.get("rule.unit").unwrap());
+        assert_eq!("UNKNOWN", extracted_vars.get("rule.room").unwrap());
+    }
+
+    #[test]
+    fn should_fail_if_a_modifier_cannot_be_applied() {
+        let mut from_config = HashMap::new();
+
+        from_config.insert(
+            String::from("temperature"),
+            Extractor {
+                from: String::from("${event.type}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![Modifier::ToNumber],
+            },
+        );
+
+        let extractor = MatcherExtractorBuilder::new().build("rule", &from_config).unwrap();
+
+        let event = new_event("temp=44'C");
+        let mut extracted_vars = HashMap::new();
+
+        assert!(extractor.process_all(&event, &mut extracted_vars).is_err());
+    }
+
+    #[test]
+    fn should_extract_variables_that_read_other_variables_of_the_same_rule() {
+        let mut from_config = HashMap::new();
+
+        from_config.insert(
+            String::from("a_number"),
+            Extractor {
+                from: String::from("${_variables.b_value}"),
+                kind: regex_kind(r"[0-9]+", Some(0), false),
+                modifiers_post: vec![Modifier::ToNumber],
+            },
+        );
+        from_config.insert(
+            String::from("b_value"),
+            Extractor {
+                from: String::from("${_variables.c_pair}"),
+                kind: regex_kind(r"=(.+)", Some(1), false),
+                modifiers_post: vec![],
+            },
+        );
+        from_config.insert(
+            String::from("c_pair"),
+            Extractor {
+                from: String::from("${event.type}"),
+                kind: regex_kind(r"temp=[0-9]+", Some(0), false),
+                modifiers_post: vec![],
+            },
+        );
+
+        let extractor = MatcherExtractorBuilder::new().build("rule", &from_config).unwrap();
+
+        let event = new_event("temp=44'C");
+        let mut extracted_vars = HashMap::new();
+
+        assert!(extractor.process_all(&event, &mut extracted_vars).is_ok());
+        assert_eq!(3, extracted_vars.len());
+        assert_eq!("temp=44", extracted_vars.get("rule.c_pair").unwrap());
+        assert_eq!("44", extracted_vars.get("rule.b_value").unwrap());
+        assert_eq!(
+            &Value::Number(Number::PosInt(44)),
+            extracted_vars.get("rule.a_number").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_sort_the_variables_by_dependencies() {
+        let builder = AccessorBuilder::new();
+        let mut targets = HashMap::new();
+        targets.insert("d".to_owned(), builder.build("rule", "${_variables.b}").unwrap());
+        targets.insert("c".to_owned(), builder.build("rule", "${event.type}").unwrap());
+        targets.insert("b".to_owned(), builder.build("rule", "${_variables.rule.e}").unwrap());
+        targets.insert("a".to_owned(), builder.build("rule", "${_variables.other.a}").unwrap());
+        targets.insert("e".to_owned(), builder.build("rule", "${event.payload.e}").unwrap());
+
+        assert_eq!(vec!["a", "e", "b", "c", "d"], extraction_order("rule", &targets).unwrap());
+    }
+
+    #[test]
+    fn build_should_fail_if_circular_dependencies() {
+        let mut from_config = HashMap::new();
+
+        from_config.insert(
+            String::from("first"),
+            Extractor {
+                from: String::from("${_variables.second}"),
+                kind: regex_kind(r"[0-9]+", Some(0), false),
+                modifiers_post: vec![],
+            },
+        );
+        from_config.insert(
+            String::from("second"),
+            Extractor {
+                from: String::from("${_variables.first[0]}"),
+                kind: regex_kind(r"[0-9]+", Some(0), false),
+                modifiers_post: vec![],
+            },
+        );
+
+        let extractor = MatcherExtractorBuilder::new().build("rule", &from_config);
+
+        assert_eq!(
+            Some(MatcherError::CircularExtractedVariablesError {
+                rule_name: "rule".to_owned(),
+                variables: vec!["first".to_owned(), "second".to_owned()],
+            }),
+            extractor.err()
+        );
+    }
+
     fn new_event(event_type: &str) -> InternalEvent {
         InternalEvent::new(Event::new(event_type))
     }
+
+    fn event_with_body(body: Value) -> InternalEvent {
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), body);
+        InternalEvent::new(event)
+    }
+
+    fn regex_kind(regex: &str, group_match_idx: Option<u16>, all_matches: bool) -> ExtractorKind {
+        ExtractorKind::Regex(ExtractorRegex {
+            regex: regex.to_owned(),
+            group_match_idx,
+            all_matches,
+        })
+    }
+
+    fn jmespath_kind(expression: &str) -> ExtractorKind {
+        ExtractorKind::Jmespath(ExtractorJmespath { expression: expression.to_owned() })
+    }
 }
@@ -0,0 +1,179 @@
+//! The modifier module contains the post-processing steps applied to the value of
+//! an extracted variable before it is stored.
+
+use crate::config::rule::Modifier;
+use crate::error::MatcherError;
+use tornado_common_api::{Number, Value};
+
+/// A post-processing step of an extracted variable.
+/// The modifiers that work on texts are applied to all the texts contained in arrays and maps.
+#[derive(Debug, PartialEq)]
+pub enum ValueModifier {
+    Trim,
+    Lowercase,
+    Uppercase,
+    ToNumber,
+    Replace { find: String, replace: String },
+    Default { value: Value },
+}
+
+impl ValueModifier {
+    pub fn build(modifier: &Modifier) -> Result<ValueModifier, MatcherError> {
+        Ok(match modifier {
+            Modifier::Trim => ValueModifier::Trim,
+            Modifier::Lowercase => ValueModifier::Lowercase,
+            Modifier::Uppercase => ValueModifier::Uppercase,
+            Modifier::ToNumber => ValueModifier::ToNumber,
+            Modifier::Replace { find, replace } => {
+                if find.is_empty() {
+                    return Err(MatcherError::ExtractorBuildFailError {
+                        message: "Cannot build the 'replace' modifier".to_owned(),
+                        cause: "The 'find' text must not be empty".to_owned(),
+                    });
+                }
+                ValueModifier::Replace { find: find.to_owned(), replace: replace.to_owned() }
+            }
+            Modifier::Default { value } => ValueModifier::Default { value: value.clone() },
+        })
+    }
+
+    /// Applies the modifier to an extracted value.
+    /// It returns None if the value is missing or if it cannot be modified,
+    /// for example, when a text that is not a number is passed to the 'to_number' modifier.
+    pub fn apply(&self, value: Option<Value>) -> Option<Value> {
+        match self {
+            ValueModifier::Default { value: default } => value.or_else(|| Some(default.clone())),
+            ValueModifier::Trim => {
+                map_texts(value?, &|text| Some(Value::Text(text.trim().to_owned())))
+            }
+            ValueModifier::Lowercase => {
+                map_texts(value?, &|text| Some(Value::Text(text.to_lowercase())))
+            }
+            ValueModifier::Uppercase => {
+                map_texts(value?, &|text| Some(Value::Text(text.to_uppercase())))
+            }
+            ValueModifier::ToNumber => map_texts(value?, &|text| parse_number(text.trim())),
+            ValueModifier::Replace { find, replace } => {
+                map_texts(value?, &|text| Some(Value::Text(text.replace(find, replace))))
+            }
+        }
+    }
+}
+
+fn map_texts(value: Value, modify: &dyn Fn(&str) -> Option<Value>) -> Option<Value> {
+    match value {
+        Value::Text(text) => modify(&text),
+        Value::Array(array) => Some(Value::Array(
+            array.into_iter().map(|value| map_texts(value, modify)).collect::<Option<Vec<_>>>()?,
+        )),
+        Value::Map(map) => Some(Value::Map(
+            map.into_iter()
+                .map(|(key, value)| map_texts(value, modify).map(|value| (key, value)))
+                .collect::<Option<_>>()?,
+        )),
+        Value::Number(..) | Value::Bool(..) | Value::Null => Some(value),
+    }
+}
+
+fn parse_number(text: &str) -> Option<Value> {
+    if let Ok(number) = text.parse::<u64>() {
+        return Some(Value::Number(Number::PosInt(number)));
+    }
+    if let Ok(number) = text.parse::<i64>() {
+        return Some(Value::Number(Number::NegInt(number)));
+    }
+    match text.parse::<f64>() {
+        Ok(number) if number.is_finite() => Some(Value::Number(Number::Float(number))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use maplit::*;
+
+    fn text(text: &str) -> Option<Value> {
+        Some(Value::Text(text.to_owned()))
+    }
+
+    #[test]
+    fn should_trim_a_text() {
+        assert_eq!(text("value"), ValueModifier::Trim.apply(text("  value \n")));
+    }
+
+    #[test]
+    fn should_change_the_case_of_a_text() {
+        assert_eq!(text("value"), ValueModifier::Lowercase.apply(text("VaLuE")));
+        assert_eq!(text("VALUE"), ValueModifier::Uppercase.apply(text("VaLuE")));
+    }
+
+    #[test]
+    fn should_replace_all_the_occurrences_of_a_text() {
+        let modifier = ValueModifier::Replace { find: "-".to_owned(), replace: ".".to_owned() };
+        assert_eq!(text("10.20.30"), modifier.apply(text("10-20-30")));
+    }
+
+    #[test]
+    fn build_should_fail_if_replace_find_is_empty() {
+        let modifier = ValueModifier::build(&Modifier::Replace {
+            find: "".to_owned(),
+            replace: "a".to_owned(),
+        });
+        assert!(modifier.is_err());
+    }
+
+    #[test]
+    fn should_convert_a_text_to_number() {
+        assert_eq!(
+            Some(Value::Number(Number::PosInt(42))),
+            ValueModifier::ToNumber.apply(text(" 42 "))
+        );
+        assert_eq!(
+            Some(Value::Number(Number::NegInt(-42))),
+            ValueModifier::ToNumber.apply(text("-42"))
+        );
+        assert_eq!(
+            Some(Value::Number(Number::Float(42.5))),
+            ValueModifier::ToNumber.apply(text("42.5"))
+        );
+    }
+
+    #[test]
+    fn should_return_none_if_text_is_not_a_number() {
+        assert_eq!(None, ValueModifier::ToNumber.apply(text("forty-two")));
+        assert_eq!(None, ValueModifier::ToNumber.apply(text("NaN")));
+        assert_eq!(None, ValueModifier::ToNumber.apply(text("")));
+    }
+
+    #[test]
+    fn should_modify_all_texts_of_arrays_and_maps() {
+        let value = Value::Array(vec![
+            Value::Text("1".to_owned()),
+            Value::Map(hashmap!("value".to_owned() => Value::Text("2".to_owned()))),
+        ]);
+
+        assert_eq!(
+            Some(Value::Array(vec![
+                Value::Number(Number::PosInt(1)),
+                Value::Map(hashmap!("value".to_owned() => Value::Number(Number::PosInt(2)))),
+            ])),
+            ValueModifier::ToNumber.apply(Some(value))
+        );
+    }
+
+    #[test]
+    fn should_use_the_default_value_only_if_value_is_missing() {
+        let modifier = ValueModifier::Default { value: Value::Text("n/a".to_owned()) };
+
+        assert_eq!(text("n/a"), modifier.apply(None));
+        assert_eq!(text("value"), modifier.apply(text("value")));
+    }
+
+    #[test]
+    fn should_return_none_if_value_is_missing() {
+        assert_eq!(None, ValueModifier::Trim.apply(None));
+        assert_eq!(None, ValueModifier::ToNumber.apply(None));
+    }
+}
@@ -2,6 +2,7 @@ pub mod action;
 pub mod extractor;
 pub mod operator;
 
+use crate::accessor::{AccessorBuilder, ExtractedVars};
 use crate::config::MatcherConfig;
 use crate::error::MatcherError;
 use crate::matcher::extractor::{MatcherExtractor, MatcherExtractorBuilder};
@@ -46,19 +47,47 @@ pub struct Matcher {
 impl Matcher {
     /// Builds a new Matcher and configures it to operate with a set of Rules.
     pub fn build(config: &MatcherConfig) -> Result<Matcher, MatcherError> {
+        Matcher::build_with_operator_builder(config, &operator::OperatorBuilder::new())
+    }
+
+    /// Builds a new Matcher whose Rules can read the given global constants
+    /// through the "${constants.*}" accessor.
+    pub fn build_with_constants(
+        config: &MatcherConfig,
+        constants: HashMap<String, Value>,
+    ) -> Result<Matcher, MatcherError> {
+        Matcher::build_with_operator_builder(
+            config,
+            &operator::OperatorBuilder::with_accessor(AccessorBuilder::with_constants(constants)),
+        )
+    }
+
+    /// Builds a new Matcher using the given OperatorBuilder.
+    /// This allows the Rules to use the custom operators registered in the builder.
+    /// The AccessorBuilder of the OperatorBuilder is used to build all the accessors of the Rules.
+    pub fn build_with_operator_builder(
+        config: &MatcherConfig,
+        operator_builder: &operator::OperatorBuilder,
+    ) -> Result<Matcher, MatcherError> {
         info!("Matcher build start");
-        MatcherConfigValidator::new().validate(config)?;
-        Matcher::build_processing_tree(config).map(|node| Matcher { node })
+        MatcherConfigValidator::with_accessor(operator_builder.accessor().clone())
+            .validate(config)?;
+        Matcher::build_processing_tree(config, operator_builder).map(|node| Matcher { node })
     }
 
-    fn build_processing_tree(config: &MatcherConfig) -> Result<ProcessingNode, MatcherError> {
+    fn build_processing_tree(
+        config: &MatcherConfig,
+        operator_builder: &operator::OperatorBuilder,
+    ) -> Result<ProcessingNode, MatcherError> {
         match config {
             MatcherConfig::Ruleset { name, rules } => {
                 info!("Start processing {} Matcher Config Rules", rules.len());
 
-                let action_builder = action::ActionResolverBuilder::new();
-                let operator_builder = operator::OperatorBuilder::new();
-                let extractor_builder = MatcherExtractorBuilder::new();
+                let action_builder = action::ActionResolverBuilder::with_accessor(
+                    operator_builder.accessor().clone(),
+                );
+                let extractor_builder =
+                    MatcherExtractorBuilder::with_accessor(operator_builder.accessor().clone());
                 let mut processed_rules = vec![];
 
                 for rule in rules.iter().filter(|rule| rule.active) {
@@ -71,7 +100,11 @@ impl Matcher {
                         operator: operator_builder
                             .build_option(&rule.name, &rule.constraint.where_operator)?,
                         extractor: extractor_builder.build(&rule.name, &rule.constraint.with)?,
-                        actions: action_builder.build(&rule.name, &rule.actions)?,
+                        actions: action_builder.build(
+                            &rule.name,
+                            &rule.actions,
+                            operator_builder,
+                        )?,
                     })
                 }
 
@@ -81,7 +114,6 @@ impl Matcher {
             }
             MatcherConfig::Filter { name, filter, nodes } => {
                 debug!("Start processing Matcher Filter [{}] Config", name);
-                let operator_builder = operator::OperatorBuilder::new();
 
                 let matcher_filter = MatcherFilter {
                     active: filter.active,
@@ -91,7 +123,7 @@ impl Matcher {
                 let mut matcher_nodes = vec![];
                 if matcher_filter.active {
                     for node in nodes {
-                        matcher_nodes.push(Matcher::build_processing_tree(node)?);
+                        matcher_nodes.push(Matcher::build_processing_tree(node, operator_builder)?);
                     }
                 };
 
@@ -176,22 +208,30 @@ impl Matcher {
                 name: rule.name.clone(),
                 status: ProcessedRuleStatus::NotMatched,
                 actions: vec![],
+                skipped_actions: vec![],
                 message: None,
             };
 
-            if rule.operator.evaluate(internal_event, Some(&extracted_vars)) {
+            let mut operator_vars = HashMap::new();
+            if rule.operator.evaluate_and_extract(
+                internal_event,
+                Some(&ExtractedVars::new(&extracted_vars)),
+                &mut operator_vars,
+            ) {
                 trace!(
                     "Matcher process - event matches rule: [{}]. Checking extracted variables.",
                     &rule.name
                 );
 
+                extracted_vars.extend(operator_vars);
+
                 match rule.extractor.process_all(&internal_event, &mut extracted_vars) {
                     Ok(_) => {
                         trace!("Matcher process - event matches rule: [{}] and its extracted variables.", &rule.name);
 
                         match Matcher::process_actions(
                             internal_event,
-                            Some(&extracted_vars),
+                            Some(&ExtractedVars::new(&extracted_vars)),
                             &mut processed_rule,
                             &rule.actions,
                         ) {
@@ -232,12 +272,21 @@ impl Matcher {
 
     fn process_actions(
         processed_event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
         processed_rule: &mut ProcessedRule,
         actions: &[action::ActionResolver],
     ) -> Result<(), MatcherError> {
         for action in actions {
-            processed_rule.actions.push(action.execute(processed_event, extracted_vars)?);
+            if action.is_triggered(processed_event, extracted_vars) {
+                processed_rule.actions.push(action.execute(processed_event, extracted_vars)?);
+            } else {
+                trace!(
+                    "Matcher process - the 'when' condition of action [{}] of rule [{}] is false. The action is skipped.",
+                    action.id(),
+                    &processed_rule.name
+                );
+                processed_rule.skipped_actions.push(action.id().to_owned());
+            }
         }
         Ok(())
     }
@@ -247,7 +296,9 @@ impl Matcher {
 mod test {
     use super::*;
     use crate::config::filter::Filter;
-    use crate::config::rule::{Action, Constraint, Extractor, ExtractorRegex, Operator, Rule};
+    use crate::config::rule::{
+        Action, Constraint, Extractor, ExtractorKind, ExtractorRegex, Operator, Rule,
+    };
     use std::collections::HashMap;
     use tornado_common_api::*;
 
@@ -578,11 +629,21 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
-        let mut action = Action { id: String::from("action_id"), payload: HashMap::new() };
+        let mut action = Action {
+            id: String::from("action_id"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
 
         action
             .payload
@@ -617,6 +678,75 @@ mod test {
         };
     }
 
+    #[test]
+    fn should_skip_the_actions_whose_when_condition_is_false() {
+        // Arrange
+        let mut rule_1 = new_rule("rule1", None);
+
+        rule_1.constraint.with.insert(
+            String::from("severity"),
+            Extractor {
+                from: String::from("${event.payload.severity}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
+            },
+        );
+
+        rule_1.actions.push(Action {
+            id: String::from("log"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        });
+        rule_1.actions.push(Action {
+            id: String::from("page"),
+            payload: HashMap::new(),
+            when: Some(Operator::Equal {
+                first: Value::Text("${_variables.severity}".to_owned()),
+                second: Value::Text("critical".to_owned()),
+            }),
+            template: None,
+        });
+        rule_1.actions.push(Action {
+            id: String::from("mail"),
+            payload: HashMap::new(),
+            when: Some(Operator::Equal {
+                first: Value::Text("${_variables.severity}".to_owned()),
+                second: Value::Text("warning".to_owned()),
+            }),
+            template: None,
+        });
+
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1],
+        })
+        .unwrap();
+
+        let mut event = Event::new("email");
+        event.payload.insert("severity".to_owned(), Value::Text("critical".to_owned()));
+
+        // Act
+        let result = matcher.process(event);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!(2, processed_rule.actions.len());
+                assert_eq!("log", processed_rule.actions[0].id);
+                assert_eq!("page", processed_rule.actions[1].id);
+                assert_eq!(vec!["mail".to_owned()], processed_rule.skipped_actions);
+            }
+            _ => assert!(false),
+        };
+    }
+
     #[test]
     fn should_return_status_not_matched_if_where_returns_false() {
         // Arrange
@@ -666,7 +796,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.temp}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -711,11 +846,21 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.temp}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
-        let mut action = Action { id: String::from("action_id"), payload: HashMap::new() };
+        let mut action = Action {
+            id: String::from("action_id"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
 
         action
             .payload
@@ -855,7 +1000,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -898,7 +1048,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -914,7 +1069,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[em]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[em]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -962,7 +1122,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -978,7 +1143,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[ai]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[ai]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1025,7 +1195,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.array[1]}"),
-                regex: ExtractorRegex { regex: String::from(r"[z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1074,7 +1249,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.map.key1}"),
-                regex: ExtractorRegex { regex: String::from(r"[z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1550,7 +1730,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.value}"),
-                regex: ExtractorRegex { regex: String::from(r"[a-z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1560,7 +1745,12 @@ mod test {
             String::from("extracted_temp"),
             Extractor {
                 from: String::from("${event.payload.value}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1772,7 +1962,12 @@ mod test {
             String::from("extracted"),
             Extractor {
                 from: String::from("${event.payload.value}"),
-                regex: ExtractorRegex { regex: String::from(r"[a-z]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1788,7 +1983,12 @@ mod test {
             String::from("extracted"),
             Extractor {
                 from: String::from("${event.payload.value}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -1835,6 +2035,293 @@ mod test {
         };
     }
 
+    #[test]
+    fn should_expose_the_variables_extracted_by_the_operators() {
+        // Arrange
+        let mut rule_1 = new_rule(
+            "rule1",
+            Operator::And {
+                operators: vec![
+                    Operator::Equal {
+                        first: Value::Text("${event.type}".to_owned()),
+                        second: Value::Text("syslog".to_owned()),
+                    },
+                    Operator::RegexAny {
+                        regexes: vec!["disk full".to_owned(), "out of memory".to_owned()],
+                        target: "${event.payload.body}".to_owned(),
+                        flags: Some("i".to_owned()),
+                        variable: Some("reason".to_owned()),
+                    },
+                ],
+            },
+        );
+
+        let mut action = Action {
+            id: String::from("action_id"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
+        action.payload.insert("reason".to_owned(), Value::Text("${_variables.reason}".to_owned()));
+        rule_1.actions.push(action);
+
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1],
+        })
+        .unwrap();
+
+        let mut event = Event::new("syslog");
+        event
+            .payload
+            .insert("body".to_owned(), Value::Text("Out of Memory: kill process".to_owned()));
+
+        // Act
+        let result = matcher.process(event);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = rules.rules.get(0).unwrap();
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!("out of memory", rules.extracted_vars.get("rule1.reason").unwrap());
+                assert_eq!(
+                    "out of memory",
+                    processed_rule.actions[0].payload.get("reason").unwrap()
+                );
+            }
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn should_expose_named_groups_and_all_matches_to_the_actions() {
+        // Arrange
+        let mut rule_1 = new_rule(
+            "rule1",
+            Operator::Equal {
+                first: Value::Text("${event.type}".to_owned()),
+                second: Value::Text("syslog".to_owned()),
+            },
+        );
+
+        rule_1.constraint.with.insert(
+            String::from("log"),
+            Extractor {
+                from: String::from("${event.payload.body}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(
+                        r"^(?P<host>[a-z0-9]+) (?P<service>[a-z]+): (?P<state>[A-Z]+)",
+                    ),
+                    group_match_idx: None,
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
+            },
+        );
+        rule_1.constraint.with.insert(
+            String::from("codes"),
+            Extractor {
+                from: String::from("${event.payload.body}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"E([0-9]+)"),
+                    group_match_idx: Some(1),
+                    all_matches: true,
+                }),
+                modifiers_post: vec![],
+            },
+        );
+
+        let mut action = Action {
+            id: String::from("action_id"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
+        action.payload.insert("host".to_owned(), Value::Text("${_variables.log.host}".to_owned()));
+        action.payload.insert(
+            "message".to_owned(),
+            Value::Text("${_variables.log.service} is ${_variables.log.state}".to_owned()),
+        );
+        action
+            .payload
+            .insert("last_code".to_owned(), Value::Text("${_variables.codes[1]}".to_owned()));
+        rule_1.actions.push(action);
+
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1],
+        })
+        .unwrap();
+
+        let mut event = Event::new("syslog");
+        event.payload.insert(
+            "body".to_owned(),
+            Value::Text("host01 nginx: CRITICAL errors E500 E503".to_owned()),
+        );
+
+        // Act
+        let result = matcher.process(event);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = rules.rules.get(0).unwrap();
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!(
+                    &Value::Array(vec![
+                        Value::Text("500".to_owned()),
+                        Value::Text("503".to_owned())
+                    ]),
+                    rules.extracted_vars.get("rule1.codes").unwrap()
+                );
+
+                let payload = &processed_rule.actions[0].payload;
+                assert_eq!("host01", payload.get("host").unwrap());
+                assert_eq!("nginx is CRITICAL", payload.get("message").unwrap());
+                assert_eq!("503", payload.get("last_code").unwrap());
+            }
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn should_use_the_custom_operators_of_the_operator_builder() {
+        // Arrange
+        let mut operator_builder = operator::OperatorBuilder::new();
+        operator_builder.register_custom_operator(
+            "is_type",
+            |rule_name: &str,
+             args: &HashMap<String, Value>,
+             accessor: &crate::accessor::AccessorBuilder| {
+                Ok(Box::new(operator::equal::Equal::build(
+                    accessor.build(rule_name, "${event.type}")?,
+                    accessor.build_from_value(rule_name, &args["type"])?,
+                )?) as Box<dyn operator::Operator>)
+            },
+        );
+
+        let mut args = HashMap::new();
+        args.insert("type".to_owned(), Value::Text("email".to_owned()));
+        let rule_1 = new_rule("rule1_email", Operator::Custom { name: "is_type".to_owned(), args });
+
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule_1] };
+
+        // Act
+        let matcher = Matcher::build_with_operator_builder(&config, &operator_builder).unwrap();
+        let email_result = matcher.process(Event::new("email"));
+        let sms_result = matcher.process(Event::new("sms"));
+
+        // Assert
+        match (email_result.result, sms_result.result) {
+            (
+                ProcessedNode::Ruleset { rules: email_rules, .. },
+                ProcessedNode::Ruleset { rules: sms_rules, .. },
+            ) => {
+                assert_eq!(ProcessedRuleStatus::Matched, email_rules.rules[0].status);
+                assert_eq!(ProcessedRuleStatus::NotMatched, sms_rules.rules[0].status);
+            }
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn build_should_fail_if_custom_operator_is_not_registered() {
+        // Arrange
+        let rule_1 = new_rule(
+            "rule1_email",
+            Operator::Custom { name: "is_type".to_owned(), args: HashMap::new() },
+        );
+
+        // Act
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1],
+        });
+
+        // Assert
+        match matcher {
+            Err(MatcherError::UnknownOperatorError { operator }) => assert_eq!("is_type", operator),
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn should_resolve_the_global_constants_in_operators_extractors_and_actions() {
+        // Arrange
+        let mut rule_1 = new_rule(
+            "rule1",
+            Operator::Equal {
+                first: Value::Text("${event.payload.site}".to_owned()),
+                second: Value::Text("${constants.site_id}".to_owned()),
+            },
+        );
+        rule_1.constraint.with.insert(
+            String::from("domain"),
+            Extractor {
+                from: String::from("${constants.hostname}"),
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"\.(.+)$"),
+                    group_match_idx: Some(1),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
+            },
+        );
+
+        let mut action = Action {
+            id: String::from("action_id"),
+            payload: HashMap::new(),
+            when: None,
+            template: None,
+        };
+        action.payload.insert(
+            "message".to_owned(),
+            Value::Text("${constants.hostname} of ${_variables.domain}".to_owned()),
+        );
+        rule_1.actions.push(action);
+
+        let mut constants = HashMap::new();
+        constants.insert("site_id".to_owned(), Value::Text("site_01".to_owned()));
+        constants.insert("hostname".to_owned(), Value::Text("tornado.example.com".to_owned()));
+
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule_1] };
+        let matcher = Matcher::build_with_constants(&config, constants).unwrap();
+
+        let mut event = Event::new("syslog");
+        event.payload.insert("site".to_owned(), Value::Text("site_01".to_owned()));
+
+        // Act
+        let result = matcher.process(event);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = rules.rules.get(0).unwrap();
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!(
+                    "tornado.example.com of example.com",
+                    processed_rule.actions[0].payload.get("message").unwrap()
+                );
+            }
+            _ => assert!(false),
+        };
+    }
+
+    #[test]
+    fn build_should_fail_if_a_constant_is_not_defined() {
+        let rule_1 = new_rule(
+            "rule1",
+            Operator::Equal {
+                first: Value::Text("${event.payload.site}".to_owned()),
+                second: Value::Text("${constants.site_id}".to_owned()),
+            },
+        );
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule_1] };
+
+        assert!(Matcher::build_with_constants(&config, HashMap::new()).is_err());
+    }
+
     fn new_matcher(config: &MatcherConfig) -> Result<Matcher, MatcherError> {
         //crate::test_root::start_context();
         Matcher::build(config)
@@ -1850,6 +2337,7 @@ mod test {
             actions: vec![],
             description: "".to_owned(),
             constraint,
+            tests: vec![],
         }
     }
 
@@ -0,0 +1,291 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::config;
+use crate::error::MatcherError;
+use crate::matcher::operator::{Operator, OperatorBuilder};
+use crate::model::InternalEvent;
+use std::collections::HashMap;
+use tornado_common_api::Value;
+
+const OPERATOR_NAME: &str = "all";
+
+/// A matching matcher.operator that evaluates to true if a child operator
+/// evaluates to true for every element of an array.
+/// The current element is accessible by the child operator through the "${item}" accessor.
+#[derive(Debug)]
+pub struct All {
+    target: Accessor,
+    operator: Box<dyn Operator>,
+}
+
+impl All {
+    pub fn build(
+        rule_name: &str,
+        target: Accessor,
+        args: &config::rule::Operator,
+        builder: &OperatorBuilder,
+    ) -> Result<All, MatcherError> {
+        let operator = builder.with_item_allowed().build(rule_name, args)?;
+        Ok(All { target, operator })
+    }
+}
+
+impl Operator for All {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => match value.as_ref() {
+                Value::Array(array) => array.iter().all(|item| {
+                    let vars = ExtractedVars::with_item(extracted_vars, item);
+                    self.operator.evaluate(event, Some(&vars))
+                }),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    fn evaluate_and_extract(
+        &self,
+        event: &InternalEvent,
+        extracted_vars: Option<&ExtractedVars>,
+        new_vars: &mut HashMap<String, Value>,
+    ) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => match value.as_ref() {
+                Value::Array(array) => {
+                    // The variables are produced by the first element
+                    let mut first_item_vars = None;
+                    let matched = array.iter().all(|item| {
+                        let vars = ExtractedVars::with_item(extracted_vars, item);
+                        let mut item_vars = HashMap::new();
+                        let matched =
+                            self.operator.evaluate_and_extract(event, Some(&vars), &mut item_vars);
+                        if first_item_vars.is_none() {
+                            first_item_vars = Some(item_vars);
+                        }
+                        matched
+                    });
+                    if let (true, Some(item_vars)) = (matched, first_item_vars) {
+                        new_vars.extend(item_vars);
+                    }
+                    matched
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use maplit::*;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = All {
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            operator: Box::new(crate::matcher::operator::true_operator::True {}),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.attachments}".to_owned()).unwrap(),
+            &config::rule::Operator::Equal {
+                first: Value::Text("${item}".to_owned()),
+                second: Value::Text("report.pdf".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        assert_eq!(Accessor::Payload { keys: vec!["attachments".into()] }, operator.target);
+        assert_eq!("equal", operator.operator.name());
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_nested_operator() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.attachments}".to_owned()).unwrap(),
+            &config::rule::Operator::Equal {
+                first: Value::Text("${WRONG}".to_owned()),
+                second: Value::Text("report.pdf".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        );
+
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_all_elements_match() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.alerts}".to_owned()).unwrap(),
+            &config::rule::Operator::Equal {
+                first: Value::Text("${item.status}".to_owned()),
+                second: Value::Text("resolved".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("webhook");
+        event.payload.insert(
+            "alerts".to_owned(),
+            Value::Array(vec![
+                Value::Map(hashmap!("status".to_owned() => Value::Text("resolved".to_owned()))),
+                Value::Map(hashmap!("status".to_owned() => Value::Text("resolved".to_owned()))),
+            ]),
+        );
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_one_element_does_not_match() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.alerts}".to_owned()).unwrap(),
+            &config::rule::Operator::Equal {
+                first: Value::Text("${item.status}".to_owned()),
+                second: Value::Text("resolved".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("webhook");
+        event.payload.insert(
+            "alerts".to_owned(),
+            Value::Array(vec![
+                Value::Map(hashmap!("status".to_owned() => Value::Text("resolved".to_owned()))),
+                Value::Map(hashmap!("status".to_owned() => Value::Text("firing".to_owned()))),
+            ]),
+        );
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_empty_array() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.alerts}".to_owned()).unwrap(),
+            &config::rule::Operator::Or { operators: vec![] },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("webhook");
+        event.payload.insert("alerts".to_owned(), Value::Array(vec![]));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_target_is_not_an_array() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.alerts}".to_owned()).unwrap(),
+            &config::rule::Operator::And { operators: vec![] },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("webhook");
+        event.payload.insert("alerts".to_owned(), Value::Text("resolved".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+
+        let event = Event::new("webhook");
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_support_nested_quantifiers() {
+        let operator = All::build(
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.alerts}".to_owned()).unwrap(),
+            &config::rule::Operator::Any {
+                target: "${item.tags}".to_owned(),
+                operator: Box::new(config::rule::Operator::Equal {
+                    first: Value::Text("${item}".to_owned()),
+                    second: Value::Text("prod".to_owned()),
+                }),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("webhook");
+        event.payload.insert(
+            "alerts".to_owned(),
+            Value::Array(vec![
+                Value::Map(hashmap!("tags".to_owned() => Value::Array(vec![
+                    Value::Text("db".to_owned()),
+                    Value::Text("prod".to_owned()),
+                ]))),
+                Value::Map(hashmap!("tags".to_owned() => Value::Array(vec![
+                    Value::Text("prod".to_owned()),
+                ]))),
+            ]),
+        );
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("webhook");
+        event.payload.insert(
+            "alerts".to_owned(),
+            Value::Array(vec![
+                Value::Map(hashmap!("tags".to_owned() => Value::Array(vec![
+                    Value::Text("prod".to_owned()),
+                ]))),
+                Value::Map(hashmap!("tags".to_owned() => Value::Array(vec![
+                    Value::Text("test".to_owned()),
+                ]))),
+            ]),
+        );
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_extract_the_variables_of_the_first_element() {
+        let operator = All::build(
+            "rule",
+            AccessorBuilder::new().build("", &"${event.payload.messages}".to_owned()).unwrap(),
+            &config::rule::Operator::RegexAny {
+                regexes: vec!["disk".to_owned(), "memory".to_owned()],
+                target: "${item}".to_owned(),
+                flags: None,
+                variable: Some("reason".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "messages".to_owned(),
+            Value::Array(vec![
+                Value::Text("disk full".to_owned()),
+                Value::Text("out of memory".to_owned()),
+            ]),
+        );
+        let mut new_vars = HashMap::new();
+
+        assert!(operator.evaluate_and_extract(&InternalEvent::new(event), None, &mut new_vars));
+        assert_eq!(1, new_vars.len());
+        assert_eq!("disk", new_vars.get("rule.reason").unwrap());
+    }
+}
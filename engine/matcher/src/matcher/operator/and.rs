@@ -1,3 +1,4 @@
+use crate::accessor::ExtractedVars;
 use crate::config;
 use crate::error::MatcherError;
 use crate::matcher::operator::{Operator, OperatorBuilder};
@@ -33,12 +34,17 @@ impl Operator for And {
         OPERATOR_NAME
     }
 
-    fn evaluate(
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        self.operators.iter().all(|op| op.evaluate(event, extracted_vars))
+    }
+
+    fn evaluate_and_extract(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
+        new_vars: &mut HashMap<String, Value>,
     ) -> bool {
-        self.operators.iter().all(|op| op.evaluate(event, extracted_vars))
+        self.operators.iter().all(|op| op.evaluate_and_extract(event, extracted_vars, new_vars))
     }
 }
 
@@ -0,0 +1,324 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use std::cmp::Ordering;
+use tornado_common_api::Value;
+
+const OPERATOR_NAME: &str = "between";
+
+/// A matching matcher.operator that checks whether the target is within a range.
+/// Each bound is inclusive unless it is declared exclusive.
+#[derive(Debug)]
+pub struct Between {
+    target: Accessor,
+    bounds: Bounds,
+}
+
+/// The lower and upper bounds of a range.
+/// A missing bound leaves the range open on that side.
+#[derive(Debug)]
+pub struct Bounds {
+    min: Option<Accessor>,
+    max: Option<Accessor>,
+    exclusive_min: bool,
+    exclusive_max: bool,
+}
+
+impl Bounds {
+    pub fn build(
+        min: Option<Accessor>,
+        max: Option<Accessor>,
+        exclusive_min: bool,
+        exclusive_max: bool,
+    ) -> Result<Bounds, MatcherError> {
+        if let (Some(Accessor::Constant { value: min }), Some(Accessor::Constant { value: max })) =
+            (&min, &max)
+        {
+            if min.partial_cmp(max) == Some(Ordering::Greater) {
+                return Err(MatcherError::OperatorBuildFailError {
+                    message: format!("Cannot build the range [{:?}, {:?}]", min, max),
+                    cause: "The lower bound must not be greater than the upper bound".to_owned(),
+                });
+            }
+        }
+        Ok(Bounds { min, max, exclusive_min, exclusive_max })
+    }
+
+    /// Returns whether the value is within the bounds.
+    /// Values that cannot be compared with a bound, e.g. a text and a number, are out of range.
+    pub fn contains(
+        &self,
+        value: &Value,
+        event: &InternalEvent,
+        extracted_vars: Option<&ExtractedVars>,
+    ) -> bool {
+        let above_min = match &self.min {
+            Some(min) => match min.get(event, extracted_vars) {
+                Some(min) => match value.partial_cmp(min.as_ref()) {
+                    Some(Ordering::Greater) => true,
+                    Some(Ordering::Equal) => !self.exclusive_min,
+                    _ => false,
+                },
+                None => false,
+            },
+            None => true,
+        };
+        if !above_min {
+            return false;
+        }
+        match &self.max {
+            Some(max) => match max.get(event, extracted_vars) {
+                Some(max) => match value.partial_cmp(max.as_ref()) {
+                    Some(Ordering::Less) => true,
+                    Some(Ordering::Equal) => !self.exclusive_max,
+                    _ => false,
+                },
+                None => false,
+            },
+            None => true,
+        }
+    }
+}
+
+impl Between {
+    pub fn build(target: Accessor, bounds: Bounds) -> Result<Between, MatcherError> {
+        if bounds.min.is_none() || bounds.max.is_none() {
+            return Err(MatcherError::OperatorBuildFailError {
+                message: "Cannot build the 'between' operator".to_owned(),
+                cause: "Both the lower and the upper bound are required".to_owned(),
+            });
+        }
+        Ok(Between { target, bounds })
+    }
+}
+
+impl Operator for Between {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => self.bounds.contains(value.as_ref(), event, extracted_vars),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    fn bounds(min: Value, max: Value, exclusive_min: bool, exclusive_max: bool) -> Bounds {
+        Bounds::build(
+            Some(AccessorBuilder::new().build_from_value("", &min).unwrap()),
+            Some(AccessorBuilder::new().build_from_value("", &max).unwrap()),
+            exclusive_min,
+            exclusive_max,
+        )
+        .unwrap()
+    }
+
+    fn event_with_value(value: Value) -> InternalEvent {
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), value);
+        InternalEvent::new(event)
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = Between {
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            bounds: Bounds { min: None, max: None, exclusive_min: false, exclusive_max: false },
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            bounds(
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(95)),
+                false,
+                true,
+            ),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+        assert_eq!(
+            &Value::Number(Number::PosInt(80)),
+            operator.bounds.min.unwrap().get(&event, None).unwrap().as_ref()
+        );
+        assert_eq!(
+            &Value::Number(Number::PosInt(95)),
+            operator.bounds.max.unwrap().get(&event, None).unwrap().as_ref()
+        );
+        assert!(!operator.bounds.exclusive_min);
+        assert!(operator.bounds.exclusive_max);
+    }
+
+    #[test]
+    fn build_should_fail_if_a_bound_is_missing() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            Bounds::build(
+                Some(AccessorBuilder::new().build("", &"80".to_owned()).unwrap()),
+                None,
+                false,
+                false,
+            )
+            .unwrap(),
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_min_is_greater_than_max() {
+        let bounds = Bounds::build(
+            Some(
+                AccessorBuilder::new()
+                    .build_from_value("", &Value::Number(Number::PosInt(95)))
+                    .unwrap(),
+            ),
+            Some(
+                AccessorBuilder::new()
+                    .build_from_value("", &Value::Number(Number::PosInt(80)))
+                    .unwrap(),
+            ),
+            false,
+            false,
+        );
+        assert!(bounds.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_value_is_in_the_range() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            bounds(
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(95)),
+                false,
+                false,
+            ),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(&event_with_value(Value::Number(Number::PosInt(85))), None));
+        assert!(operator.evaluate(&event_with_value(Value::Number(Number::Float(94.9))), None));
+        assert!(!operator.evaluate(&event_with_value(Value::Number(Number::PosInt(96))), None));
+        assert!(!operator.evaluate(&event_with_value(Value::Number(Number::NegInt(-85))), None));
+    }
+
+    #[test]
+    fn should_include_the_bounds_by_default() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            bounds(
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(95)),
+                false,
+                false,
+            ),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(&event_with_value(Value::Number(Number::PosInt(80))), None));
+        assert!(operator.evaluate(&event_with_value(Value::Number(Number::Float(95.0))), None));
+    }
+
+    #[test]
+    fn should_exclude_the_exclusive_bounds() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            bounds(
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(95)),
+                true,
+                true,
+            ),
+        )
+        .unwrap();
+
+        assert!(!operator.evaluate(&event_with_value(Value::Number(Number::PosInt(80))), None));
+        assert!(operator.evaluate(&event_with_value(Value::Number(Number::PosInt(81))), None));
+        assert!(!operator.evaluate(&event_with_value(Value::Number(Number::PosInt(95))), None));
+    }
+
+    #[test]
+    fn should_compare_texts() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            bounds(Value::Text("b".to_owned()), Value::Text("d".to_owned()), false, false),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(&event_with_value(Value::Text("cat".to_owned())), None));
+        assert!(!operator.evaluate(&event_with_value(Value::Text("dog".to_owned())), None));
+    }
+
+    #[test]
+    fn should_evaluate_bounds_from_the_event() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            Bounds::build(
+                Some(AccessorBuilder::new().build("", &"${event.payload.min}".to_owned()).unwrap()),
+                Some(AccessorBuilder::new().build("", &"${event.payload.max}".to_owned()).unwrap()),
+                false,
+                false,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Number(Number::PosInt(10)));
+        event.payload.insert("min".to_owned(), Value::Number(Number::NegInt(-10)));
+        event.payload.insert("max".to_owned(), Value::Number(Number::Float(10.5)));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_values_are_not_comparable() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            bounds(
+                Value::Number(Number::PosInt(80)),
+                Value::Number(Number::PosInt(95)),
+                false,
+                false,
+            ),
+        )
+        .unwrap();
+
+        assert!(!operator.evaluate(&event_with_value(Value::Text("85".to_owned())), None));
+        assert!(!operator.evaluate(&event_with_value(Value::Null), None));
+    }
+
+    #[test]
+    fn should_return_false_if_fields_do_not_exist() {
+        let operator = Between::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            Bounds::build(
+                Some(AccessorBuilder::new().build("", &"${event.payload.min}".to_owned()).unwrap()),
+                Some(AccessorBuilder::new().build("", &"${event.payload.max}".to_owned()).unwrap()),
+                false,
+                false,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert!(!operator.evaluate(&event_with_value(Value::Number(Number::PosInt(10))), None));
+    }
+}
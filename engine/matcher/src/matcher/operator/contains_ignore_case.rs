@@ -0,0 +1,201 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use tornado_common_api::{cow_to_str, Value};
+
+const OPERATOR_NAME: &str = "containsIgnoreCase";
+
+/// A matching matcher.operator that evaluates whether the first argument contains the second
+/// ignoring the case
+#[derive(Debug)]
+pub struct ContainsIgnoreCase {
+    first: Accessor,
+    second: Accessor,
+}
+
+impl ContainsIgnoreCase {
+    pub fn build(first: Accessor, second: Accessor) -> Result<ContainsIgnoreCase, MatcherError> {
+        Ok(ContainsIgnoreCase { first, second })
+    }
+}
+
+impl Operator for ContainsIgnoreCase {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.first.get(event, extracted_vars) {
+            Some(first_value) => {
+                let second = self.second.get(event, extracted_vars);
+                let substring = match cow_to_str(&second) {
+                    Some(substring) => substring.to_lowercase(),
+                    None => return false,
+                };
+                match first_value.as_ref() {
+                    Value::Text(first) => first.to_lowercase().contains(&substring),
+                    Value::Array(array) => array.iter().any(|value| match value {
+                        Value::Text(text) => text.to_lowercase() == substring,
+                        _ => false,
+                    }),
+                    Value::Map(map) => map.keys().any(|key| key.to_lowercase() == substring),
+                    Value::Number(..) | Value::Bool(..) | Value::Null => false,
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use maplit::*;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = ContainsIgnoreCase {
+            first: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            second: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.first.get(&event, None).unwrap().as_ref());
+        assert_eq!("two", operator.second.get(&event, None).unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_text_contains_substring_with_different_case() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"Disk is FULL on host".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"full".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_text_does_not_contain_substring() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"Disk is FULL on host".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"empty".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.subject}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("alert");
+        event.payload.insert("subject".to_owned(), Value::Text("New ALERT received".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_return_false_if_fields_do_not_exist() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.1}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.2}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_value_of_type_number() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"9".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Number(Number::Float(999.99)));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_array_contains_a_text_with_different_case() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.array}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"Linux".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "array".to_owned(),
+            Value::Array(vec![Value::Text("LINUX".to_owned()), Value::Number(Number::PosInt(1))]),
+        );
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_array_contains_only_a_substring() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.array}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"lin".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event
+            .payload
+            .insert("array".to_owned(), Value::Array(vec![Value::Text("LINUX".to_owned())]));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_map_contains_a_key_with_different_case() {
+        let operator = ContainsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.map}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"KEY_TWO".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "map".to_owned(),
+            Value::Map(hashmap!(
+                "key_one".to_owned() => Value::Null,
+                "key_two".to_owned() => Value::Null,
+            )),
+        );
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
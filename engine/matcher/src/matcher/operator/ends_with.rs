@@ -0,0 +1,168 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "endsWith";
+
+/// A matching matcher.operator that evaluates whether the first argument ends with the second
+#[derive(Debug)]
+pub struct EndsWith {
+    first: Accessor,
+    second: Accessor,
+}
+
+impl EndsWith {
+    pub fn build(first: Accessor, second: Accessor) -> Result<EndsWith, MatcherError> {
+        Ok(EndsWith { first, second })
+    }
+}
+
+impl Operator for EndsWith {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        let first = self.first.get(event, extracted_vars);
+        match cow_to_str(&first) {
+            Some(text) => {
+                let second = self.second.get(event, extracted_vars);
+                match cow_to_str(&second) {
+                    Some(suffix) => text.ends_with(suffix),
+                    None => false,
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = EndsWith {
+            first: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            second: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.first.get(&event, None).unwrap().as_ref());
+        assert_eq!("two", operator.second.get(&event, None).unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_text_ends_with_suffix() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"server-01-db".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"-db".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_text_does_not_end_with_suffix() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"db-server-01".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"-db".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_be_case_sensitive() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"server-01-DB".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"-db".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"${event.payload.hostname}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.suffix}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("hostname".to_owned(), Value::Text("server-01-db".to_owned()));
+        event.payload.insert("suffix".to_owned(), Value::Text("-db".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_return_false_if_fields_do_not_exist() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"${event.payload.1}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.2}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_value_of_type_number() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"9".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Number(Number::PosInt(999)));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_value_of_type_array() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"-db".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event
+            .payload
+            .insert("value".to_owned(), Value::Array(vec![Value::Text("01-db".to_owned())]));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "equalsIgnoreCase";
+
+/// A matching matcher.operator that checks whether two strings are equal ignoring the case
+#[derive(Debug)]
+pub struct EqualsIgnoreCase {
+    first: Accessor,
+    second: Accessor,
+}
+
+impl EqualsIgnoreCase {
+    pub fn build(first: Accessor, second: Accessor) -> Result<EqualsIgnoreCase, MatcherError> {
+        Ok(EqualsIgnoreCase { first, second })
+    }
+}
+
+impl Operator for EqualsIgnoreCase {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        let first = self.first.get(event, extracted_vars);
+        match cow_to_str(&first) {
+            Some(first_text) => {
+                let second = self.second.get(event, extracted_vars);
+                match cow_to_str(&second) {
+                    Some(second_text) => first_text.to_lowercase() == second_text.to_lowercase(),
+                    None => false,
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = EqualsIgnoreCase {
+            first: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            second: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.first.get(&event, None).unwrap().as_ref());
+        assert_eq!("two", operator.second.get(&event, None).unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_equal_arguments() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"CRITICAL".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"CRITICAL".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_arguments_differ_only_in_case() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"Critical".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"CRITICAL".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_different_arguments() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"critical".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"CRITICAL1".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.severity}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("critical");
+        event.payload.insert("severity".to_owned(), Value::Text("CRITICAL".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_return_false_if_fields_do_not_exist() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.1}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.2}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_values_are_not_text() {
+        let operator = EqualsIgnoreCase::build(
+            AccessorBuilder::new().build("", &"${event.payload.one}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.two}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("one".to_owned(), Value::Bool(true));
+        event.payload.insert("two".to_owned(), Value::Bool(true));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
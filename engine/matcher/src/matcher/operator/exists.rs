@@ -0,0 +1,126 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+
+const OPERATOR_NAME: &str = "exists";
+
+/// A matching matcher.operator that checks whether a field of the Event exists
+#[derive(Debug)]
+pub struct Exists {
+    target: Accessor,
+}
+
+impl Exists {
+    pub fn build(target: Accessor) -> Result<Exists, MatcherError> {
+        Ok(Exists { target })
+    }
+}
+
+impl Operator for Exists {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        self.target.get(event, extracted_vars).is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use maplit::*;
+    use std::collections::HashMap;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = Exists { target: AccessorBuilder::new().build("", &"".to_owned()).unwrap() };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator =
+            Exists::build(AccessorBuilder::new().build("", &"one".to_owned()).unwrap()).unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_field_exists() {
+        let operator = Exists::build(
+            AccessorBuilder::new().build("", &"${event.payload.oid}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("oid".to_owned(), Value::Text("1.3.6.1".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_field_exists_with_null_value() {
+        let operator = Exists::build(
+            AccessorBuilder::new().build("", &"${event.payload.oid}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("oid".to_owned(), Value::Null);
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_does_not_exist() {
+        let operator = Exists::build(
+            AccessorBuilder::new().build("", &"${event.payload.oid}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_nested_fields() {
+        let operator = Exists::build(
+            AccessorBuilder::new().build("", &"${event.payload.body.oid}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "body".to_owned(),
+            Value::Map(hashmap!("oid".to_owned() => Value::Text("1.3.6.1".to_owned()))),
+        );
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), Value::Text("1.3.6.1".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_extracted_variables() {
+        let operator = Exists::build(
+            AccessorBuilder::new().build("rule", &"${_variables.temp}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+        let mut extracted_vars = HashMap::new();
+        extracted_vars.insert("rule.temp".to_owned(), Value::Text("10".to_owned()));
+
+        assert!(operator.evaluate(&event, Some(&ExtractedVars::new(&extracted_vars))));
+        assert!(!operator.evaluate(&event, None));
+    }
+}
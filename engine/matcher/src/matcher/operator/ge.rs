@@ -1,10 +1,9 @@
-use crate::accessor::Accessor;
+use crate::accessor::{Accessor, ExtractedVars};
 use crate::error::MatcherError;
 use crate::matcher::operator::Operator;
 use crate::model::InternalEvent;
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use tornado_common_api::{partial_cmp_option_cow_value, Value};
+use tornado_common_api::partial_cmp_option_cow_value;
 
 const OPERATOR_NAME: &str = "ge";
 
@@ -27,11 +26,7 @@ impl Operator for GreaterEqualThan {
         OPERATOR_NAME
     }
 
-    fn evaluate(
-        &self,
-        event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
-    ) -> bool {
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
         let cmp = partial_cmp_option_cow_value(&self.first.get(event, extracted_vars), || {
             self.second.get(event, extracted_vars)
         });
@@ -0,0 +1,368 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use std::collections::HashSet;
+use tornado_common_api::{Number, Value};
+
+const OPERATOR_NAME: &str = "in";
+// The floats in [I64_LOWER_BOUND, U64_UPPER_BOUND) can be converted to integers: -2^63 and 2^64
+const I64_LOWER_BOUND: f64 = -9_223_372_036_854_775_808.0;
+const U64_UPPER_BOUND: f64 = 18_446_744_073_709_551_616.0;
+
+/// A matching matcher.operator that checks whether the first argument is equal to one
+/// of the elements of the second argument.
+/// When the second argument is a constant array, its elements are compiled into a hash set
+/// when the operator is built; otherwise, the array is resolved at each evaluation.
+#[derive(Debug)]
+pub struct In {
+    target: Accessor,
+    values: InValues,
+}
+
+#[derive(Debug)]
+enum InValues {
+    Constant(HashSet<SetKey>),
+    Accessor(Accessor),
+}
+
+/// A hashable representation of the Values that can be part of an 'in' set.
+/// The numbers are normalized, so that the same number has the same key whatever its
+/// representation, e.g. 1 and 1.0.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum SetKey {
+    Text(String),
+    Null,
+    Bool(bool),
+    PosInt(u64),
+    NegInt(i64),
+    Float(u64),
+}
+
+impl SetKey {
+    fn from_value(value: &Value) -> Option<SetKey> {
+        match value {
+            Value::Text(text) => Some(SetKey::Text(text.to_owned())),
+            Value::Null => Some(SetKey::Null),
+            Value::Bool(boolean) => Some(SetKey::Bool(*boolean)),
+            Value::Number(number) => Some(SetKey::from_number(*number)),
+            Value::Map(..) | Value::Array(..) => None,
+        }
+    }
+
+    // The integral floats are converted to integers; the other floats are compared by their bits.
+    fn from_number(number: Number) -> SetKey {
+        match number {
+            Number::PosInt(number) => SetKey::PosInt(number),
+            Number::NegInt(number) if number >= 0 => SetKey::PosInt(number as u64),
+            Number::NegInt(number) => SetKey::NegInt(number),
+            Number::Float(number)
+                if number.fract() == 0.0 && (0.0..U64_UPPER_BOUND).contains(&number) =>
+            {
+                SetKey::PosInt(number as u64)
+            }
+            Number::Float(number)
+                if number.fract() == 0.0 && (I64_LOWER_BOUND..0.0).contains(&number) =>
+            {
+                SetKey::NegInt(number as i64)
+            }
+            Number::Float(number) => SetKey::Float(number.to_bits()),
+        }
+    }
+}
+
+impl In {
+    pub fn build(target: Accessor, values: Accessor) -> Result<In, MatcherError> {
+        let values = match values {
+            Accessor::Constant { value: Value::Array(array) } => {
+                let mut set = HashSet::with_capacity(array.len());
+                for value in &array {
+                    let key = SetKey::from_value(value).ok_or_else(|| {
+                        MatcherError::OperatorBuildFailError {
+                            message: "Cannot build the 'in' operator".to_owned(),
+                            cause: format!(
+                                "Maps and arrays are not allowed as elements of the set. Found [{:?}]",
+                                value
+                            ),
+                        }
+                    })?;
+                    set.insert(key);
+                }
+                InValues::Constant(set)
+            }
+            Accessor::Constant { value } => {
+                return Err(MatcherError::OperatorBuildFailError {
+                    message: "Cannot build the 'in' operator".to_owned(),
+                    cause: format!(
+                        "The second argument must be an array or a placeholder. Found [{:?}]",
+                        value
+                    ),
+                });
+            }
+            accessor => InValues::Accessor(accessor),
+        };
+        Ok(In { target, values })
+    }
+}
+
+impl Operator for In {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(target) => match &self.values {
+                InValues::Constant(set) => match SetKey::from_value(target.as_ref()) {
+                    Some(key) => set.contains(&key),
+                    None => false,
+                },
+                InValues::Accessor(accessor) => match accessor.get(event, extracted_vars) {
+                    Some(values) => match values.as_ref() {
+                        Value::Array(array) => match SetKey::from_value(target.as_ref()) {
+                            Some(key) => array
+                                .iter()
+                                .any(|value| SetKey::from_value(value).as_ref() == Some(&key)),
+                            None => array.contains(target.as_ref()),
+                        },
+                        _ => false,
+                    },
+                    None => false,
+                },
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use std::collections::HashMap;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = In {
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            values: InValues::Constant(HashSet::new()),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value(
+                    "",
+                    &Value::Array(vec![
+                        Value::Text("one".to_owned()),
+                        Value::Number(Number::PosInt(2)),
+                    ]),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+        match operator.values {
+            InValues::Constant(set) => {
+                assert_eq!(2, set.len());
+                assert!(set.contains(&SetKey::Text("one".to_owned())));
+                assert!(set.contains(&SetKey::PosInt(2)));
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn build_should_fail_if_second_argument_is_not_an_array() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        );
+
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_array_contains_a_map() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value("", &Value::Array(vec![Value::Map(HashMap::new())]))
+                .unwrap(),
+        );
+
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_target_is_in_the_constant_array() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.hostname}".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value(
+                    "",
+                    &Value::Array(vec![
+                        Value::Text("host-01".to_owned()),
+                        Value::Text("host-02".to_owned()),
+                        Value::Text("host-03".to_owned()),
+                    ]),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("hostname".to_owned(), Value::Text("host-02".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_target_is_not_in_the_constant_array() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.hostname}".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value(
+                    "",
+                    &Value::Array(vec![
+                        Value::Text("host-01".to_owned()),
+                        Value::Text("host-02".to_owned()),
+                    ]),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("hostname".to_owned(), Value::Text("host-04".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_compare_numbers_and_booleans() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value(
+                    "",
+                    &Value::Array(vec![
+                        Value::Number(Number::PosInt(1)),
+                        Value::Number(Number::Float(2.5)),
+                        Value::Bool(true),
+                    ]),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Number(Number::Float(2.5)));
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Bool(true));
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Text("1".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_compare_integers_and_integral_floats() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new()
+                .build_from_value(
+                    "",
+                    &Value::Array(vec![
+                        Value::Number(Number::PosInt(1)),
+                        Value::Number(Number::Float(-2.0)),
+                    ]),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        let event_operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.values}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event_with_value = |value: Number| {
+            let mut event = Event::new("test_type");
+            event.payload.insert("value".to_owned(), Value::Number(value));
+            event.payload.insert(
+                "values".to_owned(),
+                Value::Array(vec![
+                    Value::Number(Number::Float(1.0)),
+                    Value::Number(Number::NegInt(-2)),
+                ]),
+            );
+            InternalEvent::new(event)
+        };
+
+        for operator in &[operator, event_operator] {
+            assert!(operator.evaluate(&event_with_value(Number::Float(1.0)), None));
+            assert!(operator.evaluate(&event_with_value(Number::PosInt(1)), None));
+            assert!(operator.evaluate(&event_with_value(Number::NegInt(-2)), None));
+            assert!(operator.evaluate(&event_with_value(Number::Float(-2.0)), None));
+            assert!(!operator.evaluate(&event_with_value(Number::Float(1.5)), None));
+            assert!(!operator.evaluate(&event_with_value(Number::PosInt(2)), None));
+        }
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_target_is_in_the_array_from_the_event() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.types}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("email");
+        event.payload.insert(
+            "types".to_owned(),
+            Value::Array(vec![Value::Text("syslog".to_owned()), Value::Text("email".to_owned())]),
+        );
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_value_from_the_event_is_not_an_array() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.type}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.types}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("email");
+        event.payload.insert("types".to_owned(), Value::Text("email".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_return_false_if_fields_do_not_exist() {
+        let operator = In::build(
+            AccessorBuilder::new().build("", &"${event.payload.1}".to_owned()).unwrap(),
+            AccessorBuilder::new().build("", &"${event.payload.2}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
@@ -0,0 +1,335 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "ipInRange";
+
+/// A matching matcher.operator that checks whether an IPv4 or IPv6 address
+/// belongs to at least one of a given set of CIDR blocks.
+/// The IPv4-mapped IPv6 addresses, e.g. "::ffff:10.0.0.1", are also checked against the IPv4 blocks.
+#[derive(Debug)]
+pub struct IpInRange {
+    ranges: Vec<Cidr>,
+    target: Accessor,
+}
+
+/// A parsed CIDR block, e.g. "10.20.0.0/16" or "2001:db8::/32".
+#[derive(Debug, PartialEq)]
+enum Cidr {
+    V4 { network: u32, mask: u32 },
+    V6 { network: u128, mask: u128 },
+}
+
+impl Cidr {
+    fn parse(range: &str) -> Result<Cidr, MatcherError> {
+        let range = range.trim();
+        let mut parts = range.splitn(2, '/');
+        let address = parts.next().unwrap_or("");
+        let address: IpAddr = address.parse().map_err(|e: std::net::AddrParseError| {
+            MatcherError::OperatorBuildFailError {
+                message: format!("Cannot parse CIDR block [{}]", range),
+                cause: e.to_string(),
+            }
+        })?;
+        let max_prefix_len = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix_len = match parts.next() {
+            Some(prefix_len) => match prefix_len.parse::<u32>() {
+                Ok(prefix_len) if prefix_len <= max_prefix_len => prefix_len,
+                _ => {
+                    return Err(MatcherError::OperatorBuildFailError {
+                        message: format!("Cannot parse CIDR block [{}]", range),
+                        cause: format!(
+                            "The prefix length must be a number between 0 and {}",
+                            max_prefix_len
+                        ),
+                    })
+                }
+            },
+            None => max_prefix_len,
+        };
+
+        Ok(match address {
+            IpAddr::V4(address) => {
+                let mask = (!0u32).checked_shl(32 - prefix_len).unwrap_or(0);
+                Cidr::V4 { network: u32::from(address) & mask, mask }
+            }
+            IpAddr::V6(address) => {
+                let mask = (!0u128).checked_shl(128 - prefix_len).unwrap_or(0);
+                Cidr::V6 { network: u128::from(address) & mask, mask }
+            }
+        })
+    }
+
+    fn contains(&self, address: &IpAddr) -> bool {
+        match (self, address) {
+            (Cidr::V4 { network, mask }, IpAddr::V4(address)) => {
+                u32::from(*address) & mask == *network
+            }
+            (Cidr::V4 { network, mask }, IpAddr::V6(address)) => match to_mapped_ipv4(address) {
+                Some(address) => u32::from(address) & mask == *network,
+                None => false,
+            },
+            (Cidr::V6 { network, mask }, IpAddr::V6(address)) => {
+                u128::from(*address) & mask == *network
+            }
+            _ => false,
+        }
+    }
+}
+
+// Unlike Ipv6Addr::to_ipv4, it does not convert the IPv4-compatible addresses, e.g. "::1",
+// that are deprecated and would make the IPv6 loopback address match "0.0.0.0/8".
+fn to_mapped_ipv4(address: &Ipv6Addr) -> Option<Ipv4Addr> {
+    match address.segments() {
+        [0, 0, 0, 0, 0, 0xffff, _, _] => address.to_ipv4(),
+        _ => None,
+    }
+}
+
+impl IpInRange {
+    pub fn build(ranges: &[String], target: Accessor) -> Result<IpInRange, MatcherError> {
+        if ranges.is_empty() {
+            return Err(MatcherError::OperatorBuildFailError {
+                message: "Cannot build the 'ipInRange' operator".to_owned(),
+                cause: "At least one CIDR block is required".to_owned(),
+            });
+        }
+        let ranges =
+            ranges.iter().map(|range| Cidr::parse(range)).collect::<Result<Vec<_>, _>>()?;
+        Ok(IpInRange { ranges, target })
+    }
+}
+
+impl Operator for IpInRange {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        let cow_value = self.target.get(event, extracted_vars);
+        match cow_to_str(&cow_value).and_then(|text| text.trim().parse::<IpAddr>().ok()) {
+            Some(address) => self.ranges.iter().any(|range| range.contains(&address)),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = IpInRange {
+            ranges: vec![],
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned(), "2001:db8::/32".to_owned(), "192.168.1.1".to_owned()],
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+        assert_eq!(
+            vec![
+                Cidr::V4 { network: 0x0A14_0000, mask: 0xFFFF_0000 },
+                Cidr::V6 {
+                    network: 0x2001_0db8_0000_0000_0000_0000_0000_0000,
+                    mask: 0xFFFF_FFFF_0000_0000_0000_0000_0000_0000
+                },
+                Cidr::V4 { network: 0xC0A8_0101, mask: 0xFFFF_FFFF },
+            ],
+            operator.ranges
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_no_ranges() {
+        let operator =
+            IpInRange::build(&vec![], AccessorBuilder::new().build("", &"one".to_owned()).unwrap());
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_address() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned(), "10.300.0.0/16".to_owned()],
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_prefix_length() {
+        assert!(IpInRange::build(
+            &vec!["10.20.0.0/33".to_owned()],
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        )
+        .is_err());
+        assert!(IpInRange::build(
+            &vec!["10.20.0.0/".to_owned()],
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        )
+        .is_err());
+        assert!(IpInRange::build(
+            &vec!["2001:db8::/129".to_owned()],
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_ipv4_address_is_in_range() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("10.20.255.1".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_ipv4_address_is_not_in_range() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("10.21.0.1".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_address_is_in_any_of_the_ranges() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned(), "192.168.0.0/24".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("192.168.0.12".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_ipv6_addresses() {
+        let operator = IpInRange::build(
+            &vec!["2001:db8::/32".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("2001:db8:1::15".to_owned()));
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("2001:db9::15".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_not_match_ipv4_address_with_ipv6_range() {
+        let operator = IpInRange::build(
+            &vec!["::/0".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("10.20.0.1".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_match_ipv4_mapped_ipv6_address_with_ipv4_range() {
+        let operator = IpInRange::build(
+            &vec!["10.0.0.0/8".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event_with_ip = |ip: &str| {
+            let mut event = Event::new("test_type");
+            event.payload.insert("source_ip".to_owned(), Value::Text(ip.to_owned()));
+            InternalEvent::new(event)
+        };
+
+        assert!(operator.evaluate(&event_with_ip("::ffff:10.0.0.1"), None));
+        assert!(operator.evaluate(&event_with_ip("::ffff:a00:1"), None));
+        assert!(!operator.evaluate(&event_with_ip("::ffff:192.168.0.1"), None));
+        assert!(!operator.evaluate(&event_with_ip("::10.0.0.1"), None));
+        assert!(!operator.evaluate(&event_with_ip("2001:db8::a00:1"), None));
+    }
+
+    #[test]
+    fn should_match_every_address_with_zero_prefix_length() {
+        let operator = IpInRange::build(
+            &vec!["0.0.0.0/0".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("127.0.0.1".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_not_an_ip_address() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Text("10.20.0.1:8080".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("source_ip".to_owned(), Value::Number(Number::PosInt(10)));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_return_false_if_field_does_not_exist() {
+        let operator = IpInRange::build(
+            &vec!["10.20.0.0/16".to_owned()],
+            AccessorBuilder::new().build("", &"${event.payload.source_ip}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use tornado_common_api::Value;
+
+const OPERATOR_NAME: &str = "isNull";
+
+/// A matching matcher.operator that checks whether a field of the Event exists and is null
+#[derive(Debug)]
+pub struct IsNull {
+    target: Accessor,
+}
+
+impl IsNull {
+    pub fn build(target: Accessor) -> Result<IsNull, MatcherError> {
+        Ok(IsNull { target })
+    }
+}
+
+impl Operator for IsNull {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => value.as_ref() == &Value::Null,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = IsNull { target: AccessorBuilder::new().build("", &"".to_owned()).unwrap() };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator =
+            IsNull::build(AccessorBuilder::new().build("", &"one".to_owned()).unwrap()).unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_field_is_null() {
+        let operator = IsNull::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Null);
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_is_not_null() {
+        let operator = IsNull::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Text("null".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_does_not_exist() {
+        let operator = IsNull::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
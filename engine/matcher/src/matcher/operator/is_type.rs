@@ -0,0 +1,139 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::config::rule::ValueType;
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+
+const OPERATOR_NAME: &str = "isType";
+
+/// A matching matcher.operator that checks whether a field of the Event exists
+/// and is of the expected type
+#[derive(Debug)]
+pub struct IsType {
+    target: Accessor,
+    value_type: ValueType,
+}
+
+impl IsType {
+    pub fn build(target: Accessor, value_type: ValueType) -> Result<IsType, MatcherError> {
+        Ok(IsType { target, value_type })
+    }
+}
+
+impl Operator for IsType {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => match self.value_type {
+                ValueType::Text => value.get_text().is_some(),
+                ValueType::Number => value.get_number().is_some(),
+                ValueType::Bool => value.get_bool().is_some(),
+                ValueType::Array => value.get_array().is_some(),
+                ValueType::Map => value.get_map().is_some(),
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use std::collections::HashMap;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = IsType {
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            value_type: ValueType::Text,
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = IsType::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            ValueType::Number,
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+        assert_eq!(ValueType::Number, operator.value_type);
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_field_has_the_expected_type() {
+        let mut event = Event::new("test_type");
+        event.payload.insert("text".to_owned(), Value::Text("text".to_owned()));
+        event.payload.insert("number".to_owned(), Value::Number(Number::NegInt(-10)));
+        event.payload.insert("bool".to_owned(), Value::Bool(false));
+        event.payload.insert("array".to_owned(), Value::Array(vec![]));
+        event.payload.insert("map".to_owned(), Value::Map(HashMap::new()));
+        let event = InternalEvent::new(event);
+
+        for (field, value_type) in vec![
+            ("text", ValueType::Text),
+            ("number", ValueType::Number),
+            ("bool", ValueType::Bool),
+            ("array", ValueType::Array),
+            ("map", ValueType::Map),
+        ] {
+            let operator = IsType::build(
+                AccessorBuilder::new().build("", &format!("${{event.payload.{}}}", field)).unwrap(),
+                value_type,
+            )
+            .unwrap();
+            assert!(operator.evaluate(&event, None));
+        }
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_has_a_different_type() {
+        let operator = IsType::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            ValueType::Number,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Text("12".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_is_null() {
+        let operator = IsType::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            ValueType::Text,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("value".to_owned(), Value::Null);
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_does_not_exist() {
+        let operator = IsType::build(
+            AccessorBuilder::new().build("", &"${event.payload.value}".to_owned()).unwrap(),
+            ValueType::Map,
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
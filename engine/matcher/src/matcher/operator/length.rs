@@ -0,0 +1,175 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::between::Bounds;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use tornado_common_api::{Number, Value};
+
+const OPERATOR_NAME: &str = "length";
+
+/// A matching matcher.operator that checks whether the length of the target is within a range.
+/// The length of a text is its size in bytes, the length of an array or a map is
+/// its number of elements.
+#[derive(Debug)]
+pub struct Length {
+    target: Accessor,
+    bounds: Bounds,
+}
+
+impl Length {
+    pub fn build(target: Accessor, bounds: Bounds) -> Result<Length, MatcherError> {
+        Ok(Length { target, bounds })
+    }
+
+    fn length_of(value: &Value) -> Option<usize> {
+        match value {
+            Value::Text(text) => Some(text.len()),
+            Value::Array(array) => Some(array.len()),
+            Value::Map(map) => Some(map.len()),
+            Value::Number(..) | Value::Bool(..) | Value::Null => None,
+        }
+    }
+}
+
+impl Operator for Length {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => match Length::length_of(value.as_ref()) {
+                Some(length) => self.bounds.contains(
+                    &Value::Number(Number::PosInt(length as u64)),
+                    event,
+                    extracted_vars,
+                ),
+                None => false,
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use maplit::*;
+    use std::collections::HashMap;
+    use tornado_common_api::*;
+
+    fn bounds(min: Option<u64>, max: Option<u64>, exclusive_min: bool) -> Bounds {
+        let accessor = |bound: u64| {
+            AccessorBuilder::new()
+                .build_from_value("", &Value::Number(Number::PosInt(bound)))
+                .unwrap()
+        };
+        Bounds::build(min.map(accessor), max.map(accessor), exclusive_min, false).unwrap()
+    }
+
+    fn event_with_body(body: Value) -> InternalEvent {
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), body);
+        InternalEvent::new(event)
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = Length {
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            bounds: bounds(None, None, false),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"one".to_owned()).unwrap(),
+            bounds(Some(1), Some(3), false),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("test_type"));
+
+        assert_eq!("one", operator.target.get(&event, None).unwrap().as_ref());
+        assert!(operator.evaluate(&event, None));
+    }
+
+    #[test]
+    fn should_evaluate_the_length_of_a_text_in_bytes() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            bounds(Some(10), None, true),
+        )
+        .unwrap();
+
+        assert!(!operator.evaluate(&event_with_body(Value::Text("0123456789".to_owned())), None));
+        assert!(operator.evaluate(&event_with_body(Value::Text("0123456789a".to_owned())), None));
+        assert!(operator.evaluate(&event_with_body(Value::Text("èèèèèè".to_owned())), None));
+    }
+
+    #[test]
+    fn should_evaluate_the_length_of_an_array() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            bounds(None, Some(2), false),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(&event_with_body(Value::Array(vec![])), None));
+        assert!(
+            operator.evaluate(&event_with_body(Value::Array(vec![Value::Null, Value::Null])), None)
+        );
+        assert!(!operator.evaluate(
+            &event_with_body(Value::Array(vec![Value::Null, Value::Null, Value::Null])),
+            None
+        ));
+    }
+
+    #[test]
+    fn should_evaluate_the_length_of_a_map() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            bounds(Some(2), Some(2), false),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(
+            &event_with_body(Value::Map(hashmap!(
+                "one".to_owned() => Value::Null,
+                "two".to_owned() => Value::Null,
+            ))),
+            None
+        ));
+        assert!(!operator.evaluate(&event_with_body(Value::Map(HashMap::new())), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_value_has_no_length() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            bounds(None, None, false),
+        )
+        .unwrap();
+
+        assert!(!operator.evaluate(&event_with_body(Value::Number(Number::PosInt(1))), None));
+        assert!(!operator.evaluate(&event_with_body(Value::Bool(true)), None));
+        assert!(!operator.evaluate(&event_with_body(Value::Null), None));
+    }
+
+    #[test]
+    fn should_return_false_if_field_does_not_exist() {
+        let operator = Length::build(
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            bounds(None, None, false),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
@@ -4,24 +4,41 @@
 //! An *Operator* is linked to the "WHERE" clause of a Rule and determines whether the rule
 //! is matched by an Event.
 
-use crate::accessor::AccessorBuilder;
+use crate::accessor::{Accessor, AccessorBuilder, ExtractedVars};
 use crate::config::rule;
 use crate::error::MatcherError;
 use crate::model::InternalEvent;
 use log::*;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 use tornado_common_api::Value;
 
+pub mod all;
 pub mod and;
+pub mod any;
+pub mod between;
 pub mod contain;
+pub mod contains_ignore_case;
+pub mod ends_with;
 pub mod equal;
+pub mod equals_ignore_case;
+pub mod exists;
 pub mod ge;
 pub mod gt;
+pub mod in_operator;
+pub mod ip_in_range;
+pub mod is_null;
+pub mod is_type;
 pub mod le;
+pub mod length;
 pub mod lt;
+pub mod not;
 pub mod or;
 pub mod regex;
+pub mod regex_any;
+pub mod starts_with;
+pub mod time_window;
 pub mod true_operator;
 
 /// The Trait for a generic matcher.operator
@@ -30,22 +47,116 @@ pub trait Operator: fmt::Debug + Send + Sync {
     fn name(&self) -> &str;
 
     /// Executes the current matcher.operator on a target Event and returns whether the Event matches it.
-    fn evaluate(
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool;
+
+    /// Executes the current matcher.operator like `evaluate` and, if the Event matches it,
+    /// adds to `new_vars` the variables produced by the operator, if any.
+    /// When the Event does not match, the content of `new_vars` must be discarded.
+    /// Operators that produce variables, or that contain other operators, override it so that
+    /// the Event is evaluated only once.
+    fn evaluate_and_extract(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
-    ) -> bool;
+        extracted_vars: Option<&ExtractedVars>,
+        _new_vars: &mut HashMap<String, Value>,
+    ) -> bool {
+        self.evaluate(event, extracted_vars)
+    }
+}
+
+/// A factory of custom operators.
+/// Custom operators are registered in the OperatorBuilder with a unique name and are
+/// referenced in the rules through the 'custom' operator type.
+pub trait OperatorFactory: Send + Sync {
+    /// Builds a new Operator instance from the arguments of the 'custom' operator definition.
+    fn build(
+        &self,
+        rule_name: &str,
+        args: &HashMap<String, Value>,
+        accessor: &AccessorBuilder,
+    ) -> Result<Box<dyn Operator>, MatcherError>;
+}
+
+impl<F> OperatorFactory for F
+where
+    F: Fn(
+            &str,
+            &HashMap<String, Value>,
+            &AccessorBuilder,
+        ) -> Result<Box<dyn Operator>, MatcherError>
+        + Send
+        + Sync,
+{
+    fn build(
+        &self,
+        rule_name: &str,
+        args: &HashMap<String, Value>,
+        accessor: &AccessorBuilder,
+    ) -> Result<Box<dyn Operator>, MatcherError> {
+        self(rule_name, args, accessor)
+    }
 }
 
 /// The Operator instance builder
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct OperatorBuilder {
     accessor: AccessorBuilder,
+    custom_operators: HashMap<String, Arc<dyn OperatorFactory>>,
 }
 
 impl OperatorBuilder {
     pub fn new() -> OperatorBuilder {
-        OperatorBuilder { accessor: AccessorBuilder::new() }
+        OperatorBuilder::with_accessor(AccessorBuilder::new())
+    }
+
+    /// Returns a new OperatorBuilder that builds the operator arguments with the given
+    /// AccessorBuilder.
+    pub fn with_accessor(accessor: AccessorBuilder) -> OperatorBuilder {
+        OperatorBuilder { accessor, custom_operators: HashMap::new() }
+    }
+
+    /// Returns a copy of this builder whose operators can read the "${item}" accessor.
+    /// It builds the operators nested in the 'any' and 'all' operators.
+    pub(crate) fn with_item_allowed(&self) -> OperatorBuilder {
+        OperatorBuilder { accessor: self.accessor.with_item_allowed(), ..self.clone() }
+    }
+
+    /// Returns the AccessorBuilder used to build the operator arguments.
+    pub fn accessor(&self) -> &AccessorBuilder {
+        &self.accessor
+    }
+
+    /// Registers a factory for the custom operator with the given name.
+    /// A previously registered factory with the same name is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    ///
+    /// use tornado_engine_matcher::accessor::AccessorBuilder;
+    /// use tornado_engine_matcher::matcher::operator::OperatorBuilder;
+    /// use tornado_engine_matcher::matcher::operator::equal::Equal;
+    /// use tornado_engine_matcher::config::rule;
+    /// use tornado_common_api::Value;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut builder = OperatorBuilder::new();
+    /// builder.register_custom_operator("is_email", |rule_name: &str, _args: &HashMap<String, Value>, accessor: &AccessorBuilder| {
+    ///     Ok(Box::new(Equal::build(
+    ///         accessor.build(rule_name, "${event.type}")?,
+    ///         accessor.build(rule_name, "email")?,
+    ///     )?) as Box<_>)
+    /// });
+    ///
+    /// let ops = rule::Operator::Custom { name: "is_email".to_owned(), args: HashMap::new() };
+    /// let operator = builder.build("rule_name", &ops).unwrap(); // operator is an instance of Equal
+    /// ```
+    pub fn register_custom_operator<N: Into<String>, F: OperatorFactory + 'static>(
+        &mut self,
+        name: N,
+        factory: F,
+    ) {
+        self.custom_operators.insert(name.into(), Arc::new(factory));
     }
 
     pub fn build_option(
@@ -90,11 +201,41 @@ impl OperatorBuilder {
         config: &rule::Operator,
     ) -> Result<Box<dyn Operator>, MatcherError> {
         let result: Result<Box<dyn Operator>, MatcherError> = match config {
-            rule::Operator::And { operators } => {
-                Ok(Box::new(crate::matcher::operator::and::And::build("", &operators, self)?))
-            }
+            rule::Operator::And { operators } => Ok(Box::new(
+                crate::matcher::operator::and::And::build(rule_name, &operators, self)?,
+            )),
             rule::Operator::Or { operators } => {
-                Ok(Box::new(crate::matcher::operator::or::Or::build("", &operators, self)?))
+                Ok(Box::new(crate::matcher::operator::or::Or::build(rule_name, &operators, self)?))
+            }
+            rule::Operator::Not { operator } => {
+                Ok(Box::new(crate::matcher::operator::not::Not::build(rule_name, operator, self)?))
+            }
+            rule::Operator::Any { target, operator } => {
+                Ok(Box::new(crate::matcher::operator::any::Any::build(
+                    rule_name,
+                    self.accessor.build(rule_name, target)?,
+                    operator,
+                    self,
+                )?))
+            }
+            rule::Operator::All { target, operator } => {
+                Ok(Box::new(crate::matcher::operator::all::All::build(
+                    rule_name,
+                    self.accessor.build(rule_name, target)?,
+                    operator,
+                    self,
+                )?))
+            }
+            rule::Operator::Between { target, min, max, exclusive_min, exclusive_max } => {
+                Ok(Box::new(crate::matcher::operator::between::Between::build(
+                    self.accessor.build(rule_name, target)?,
+                    crate::matcher::operator::between::Bounds::build(
+                        Some(self.accessor.build_from_value(rule_name, min)?),
+                        Some(self.accessor.build_from_value(rule_name, max)?),
+                        *exclusive_min,
+                        *exclusive_max,
+                    )?,
+                )?))
             }
             rule::Operator::Equal { first, second } => {
                 Ok(Box::new(crate::matcher::operator::equal::Equal::build(
@@ -114,12 +255,51 @@ impl OperatorBuilder {
                     self.accessor.build_from_value(rule_name, second)?,
                 )?))
             }
+            rule::Operator::In { first, second } => {
+                Ok(Box::new(crate::matcher::operator::in_operator::In::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::IpInRange { ranges, target } => {
+                Ok(Box::new(crate::matcher::operator::ip_in_range::IpInRange::build(
+                    ranges,
+                    self.accessor.build(rule_name, target)?,
+                )?))
+            }
+            rule::Operator::Exists { target } => {
+                Ok(Box::new(crate::matcher::operator::exists::Exists::build(
+                    self.accessor.build(rule_name, target)?,
+                )?))
+            }
+            rule::Operator::IsNull { target } => {
+                Ok(Box::new(crate::matcher::operator::is_null::IsNull::build(
+                    self.accessor.build(rule_name, target)?,
+                )?))
+            }
+            rule::Operator::IsType { target, value_type } => {
+                Ok(Box::new(crate::matcher::operator::is_type::IsType::build(
+                    self.accessor.build(rule_name, target)?,
+                    *value_type,
+                )?))
+            }
             rule::Operator::LessEqualThan { first, second } => {
                 Ok(Box::new(crate::matcher::operator::le::LessEqualThan::build(
                     self.accessor.build_from_value(rule_name, first)?,
                     self.accessor.build_from_value(rule_name, second)?,
                 )?))
             }
+            rule::Operator::Length { target, min, max, exclusive_min, exclusive_max } => {
+                Ok(Box::new(crate::matcher::operator::length::Length::build(
+                    self.accessor.build(rule_name, target)?,
+                    crate::matcher::operator::between::Bounds::build(
+                        self.build_bound(rule_name, min)?,
+                        self.build_bound(rule_name, max)?,
+                        *exclusive_min,
+                        *exclusive_max,
+                    )?,
+                )?))
+            }
             rule::Operator::LessThan { first, second } => {
                 Ok(Box::new(crate::matcher::operator::lt::LessThan::build(
                     self.accessor.build_from_value(rule_name, first)?,
@@ -132,10 +312,57 @@ impl OperatorBuilder {
                     self.accessor.build_from_value(rule_name, second)?,
                 )?))
             }
-            rule::Operator::Regex { regex, target } => {
-                Ok(Box::new(crate::matcher::operator::regex::Regex::build(
+            rule::Operator::ContainsIgnoreCase { first, second } => Ok(Box::new(
+                crate::matcher::operator::contains_ignore_case::ContainsIgnoreCase::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?,
+            )),
+            rule::Operator::EndsWith { first, second } => {
+                Ok(Box::new(crate::matcher::operator::ends_with::EndsWith::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::EqualsIgnoreCase { first, second } => {
+                Ok(Box::new(crate::matcher::operator::equals_ignore_case::EqualsIgnoreCase::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::StartsWith { first, second } => {
+                Ok(Box::new(crate::matcher::operator::starts_with::StartsWith::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::TimeWindow { timezone, days, from, to } => {
+                Ok(Box::new(crate::matcher::operator::time_window::TimeWindow::build(
+                    Accessor::CreatedMs,
+                    timezone,
+                    days,
+                    from,
+                    to,
+                )?))
+            }
+            rule::Operator::Custom { name, args } => match self.custom_operators.get(name) {
+                Some(factory) => factory.build(rule_name, args, &self.accessor),
+                None => Err(MatcherError::UnknownOperatorError { operator: name.to_owned() }),
+            },
+            rule::Operator::Regex { regex, target, flags } => {
+                Ok(Box::new(crate::matcher::operator::regex::Regex::build_with_flags(
                     regex,
+                    flags.as_ref().map_or("", String::as_str),
+                    self.accessor.build(rule_name, target)?,
+                )?))
+            }
+            rule::Operator::RegexAny { regexes, target, flags, variable } => {
+                Ok(Box::new(crate::matcher::operator::regex_any::RegexAny::build(
+                    rule_name,
+                    regexes,
+                    flags.as_ref().map_or("", String::as_str),
                     self.accessor.build(rule_name, target)?,
+                    variable.as_ref().map(String::as_str),
                 )?))
             }
         };
@@ -147,12 +374,24 @@ impl OperatorBuilder {
         );
         result
     }
+
+    fn build_bound(
+        &self,
+        rule_name: &str,
+        bound: &Option<Value>,
+    ) -> Result<Option<Accessor>, MatcherError> {
+        match bound {
+            Some(bound) => Ok(Some(self.accessor.build_from_value(rule_name, bound)?)),
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
 
     use super::*;
+    use tornado_common_api::Number;
 
     #[test]
     fn build_should_return_error_if_wrong_operator() {
@@ -204,6 +443,134 @@ mod test {
         assert_eq!("gt", operator.name());
     }
 
+    #[test]
+    fn build_should_return_the_in_operator() {
+        let ops = rule::Operator::In {
+            first: Value::Text("${event.type}".to_owned()),
+            second: Value::Array(vec![Value::Text("email".to_owned())]),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("in", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_ip_in_range_operator() {
+        let ops = rule::Operator::IpInRange {
+            ranges: vec!["10.20.0.0/16".to_owned()],
+            target: "${event.payload.source_ip}".to_owned(),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("ipInRange", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_error_if_invalid_ip_range() {
+        let ops = rule::Operator::IpInRange {
+            ranges: vec!["10.20.0.0/40".to_owned()],
+            target: "${event.payload.source_ip}".to_owned(),
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_return_the_any_operator() {
+        let ops = rule::Operator::Any {
+            target: "${event.payload.attachments}".to_owned(),
+            operator: Box::new(rule::Operator::Equal {
+                first: Value::Text("${item.filename}".to_owned()),
+                second: Value::Text("report.pdf".to_owned()),
+            }),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("any", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_all_operator() {
+        let ops = rule::Operator::All {
+            target: "${event.payload.attachments}".to_owned(),
+            operator: Box::new(rule::Operator::Equal {
+                first: Value::Text("${item.filename}".to_owned()),
+                second: Value::Text("report.pdf".to_owned()),
+            }),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("all", operator.name());
+    }
+
+    #[test]
+    fn build_should_fail_if_the_item_is_used_outside_any_and_all() {
+        let builder = OperatorBuilder::new();
+
+        let equal = rule::Operator::Equal {
+            first: Value::Text("${item.filename}".to_owned()),
+            second: Value::Text("report.pdf".to_owned()),
+        };
+        assert!(builder.build_option("", &Some(equal)).is_err());
+
+        let any_of_item = rule::Operator::Any {
+            target: "${item.tags}".to_owned(),
+            operator: Box::new(rule::Operator::Equal {
+                first: Value::Text("${item}".to_owned()),
+                second: Value::Text("urgent".to_owned()),
+            }),
+        };
+        assert!(builder.build_option("", &Some(any_of_item.clone())).is_err());
+
+        let nested_any = rule::Operator::All {
+            target: "${event.payload.attachments}".to_owned(),
+            operator: Box::new(any_of_item),
+        };
+        assert!(builder.build_option("", &Some(nested_any)).is_ok());
+    }
+
+    #[test]
+    fn build_should_return_the_exists_operator() {
+        let ops = rule::Operator::Exists { target: "${event.payload.oid}".to_owned() };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("exists", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_is_null_operator() {
+        let ops = rule::Operator::IsNull { target: "${event.payload.oid}".to_owned() };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("isNull", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_is_type_operator() {
+        let ops = rule::Operator::IsType {
+            target: "${event.payload.value}".to_owned(),
+            value_type: rule::ValueType::Number,
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("isType", operator.name());
+    }
+
     #[test]
     fn build_should_return_the_less_equal_operator() {
         let ops = rule::Operator::LessEqualThan {
@@ -244,9 +611,201 @@ mod test {
     }
 
     #[test]
-    fn build_should_return_the_regex_operator() {
+    fn build_should_return_the_contains_ignore_case_operator() {
+        let ops = rule::Operator::ContainsIgnoreCase {
+            first: Value::Text("first_arg=".to_owned()),
+            second: Value::Text("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("containsIgnoreCase", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_ends_with_operator() {
+        let ops = rule::Operator::EndsWith {
+            first: Value::Text("first_arg=".to_owned()),
+            second: Value::Text("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("endsWith", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_equals_ignore_case_operator() {
+        let ops = rule::Operator::EqualsIgnoreCase {
+            first: Value::Text("first_arg=".to_owned()),
+            second: Value::Text("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("equalsIgnoreCase", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_starts_with_operator() {
+        let ops = rule::Operator::StartsWith {
+            first: Value::Text("first_arg=".to_owned()),
+            second: Value::Text("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("startsWith", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_between_operator() {
+        let ops = rule::Operator::Between {
+            target: "${event.payload.value}".to_owned(),
+            min: Value::Number(Number::PosInt(80)),
+            max: Value::Number(Number::PosInt(95)),
+            exclusive_min: false,
+            exclusive_max: true,
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("between", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_error_if_between_bounds_are_inverted() {
+        let ops = rule::Operator::Between {
+            target: "${event.payload.value}".to_owned(),
+            min: Value::Number(Number::PosInt(95)),
+            max: Value::Number(Number::PosInt(80)),
+            exclusive_min: false,
+            exclusive_max: false,
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_return_the_length_operator() {
+        let ops = rule::Operator::Length {
+            target: "${event.payload.body}".to_owned(),
+            min: Some(Value::Number(Number::PosInt(10240))),
+            max: None,
+            exclusive_min: true,
+            exclusive_max: false,
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("length", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_time_window_operator() {
+        let ops = rule::Operator::TimeWindow {
+            timezone: "Europe/Rome".to_owned(),
+            days: vec!["mon".to_owned(), "fri".to_owned()],
+            from: "08:00".to_owned(),
+            to: "18:00".to_owned(),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("timeWindow", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_error_if_invalid_time_window() {
+        let ops = rule::Operator::TimeWindow {
+            timezone: "Europe/Rome".to_owned(),
+            days: vec![],
+            from: "8 o'clock".to_owned(),
+            to: "18:00".to_owned(),
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_return_a_registered_custom_operator() {
+        let mut builder = OperatorBuilder::new();
+        builder.register_custom_operator(
+            "asset_is_critical",
+            |rule_name: &str, args: &HashMap<String, Value>, accessor: &AccessorBuilder| {
+                let asset = args.get("asset").cloned().unwrap_or(Value::Null);
+                Ok(Box::new(crate::matcher::operator::in_operator::In::build(
+                    accessor.build_from_value(rule_name, &asset)?,
+                    accessor.build_from_value(
+                        rule_name,
+                        &Value::Array(vec![Value::Text("db-01".to_owned())]),
+                    )?,
+                )?) as Box<dyn Operator>)
+            },
+        );
+
+        let mut args = HashMap::new();
+        args.insert("asset".to_owned(), Value::Text("${event.payload.asset}".to_owned()));
+        let ops = rule::Operator::Custom { name: "asset_is_critical".to_owned(), args };
+
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("in", operator.name());
+
+        let mut event = tornado_common_api::Event::new("test_type");
+        event.payload.insert("asset".to_owned(), Value::Text("db-01".to_owned()));
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn build_should_return_the_error_of_a_custom_operator_factory() {
+        let mut builder = OperatorBuilder::new();
+        builder.register_custom_operator(
+            "failing",
+            |_rule_name: &str, _args: &HashMap<String, Value>, _accessor: &AccessorBuilder| {
+                Err(MatcherError::ConfigurationError { message: "failing".to_owned() })
+            },
+        );
+
+        let ops = rule::Operator::Custom { name: "failing".to_owned(), args: HashMap::new() };
+
+        match builder.build_option("", &Some(ops)) {
+            Err(MatcherError::ConfigurationError { message }) => assert_eq!("failing", message),
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn build_should_return_error_if_unknown_custom_operator() {
         let ops =
-            rule::Operator::Regex { regex: "[a-fA-F0-9]".to_owned(), target: "target".to_owned() };
+            rule::Operator::Custom { name: "asset_is_critical".to_owned(), args: HashMap::new() };
+
+        let builder = OperatorBuilder::new();
+
+        match builder.build_option("", &Some(ops)) {
+            Err(MatcherError::UnknownOperatorError { operator }) => {
+                assert_eq!("asset_is_critical", operator)
+            }
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn build_should_return_the_regex_operator() {
+        let ops = rule::Operator::Regex {
+            regex: "[a-fA-F0-9]".to_owned(),
+            target: "target".to_owned(),
+            flags: None,
+        };
 
         let builder = OperatorBuilder::new();
         let operator = builder.build_option("", &Some(ops)).unwrap();
@@ -254,6 +813,33 @@ mod test {
         assert_eq!("regex", operator.name());
     }
 
+    #[test]
+    fn build_should_return_error_if_invalid_regex_flags() {
+        let ops = rule::Operator::Regex {
+            regex: "[a-fA-F0-9]".to_owned(),
+            target: "target".to_owned(),
+            flags: Some("iz".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_return_the_regex_any_operator() {
+        let ops = rule::Operator::RegexAny {
+            regexes: vec!["[a-f]".to_owned(), "[0-9]".to_owned()],
+            target: "target".to_owned(),
+            flags: Some("i".to_owned()),
+            variable: Some("matched".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("rule", &Some(ops)).unwrap();
+
+        assert_eq!("regexAny", operator.name());
+    }
+
     #[test]
     fn build_should_return_the_and_operator() {
         let ops = rule::Operator::And {
@@ -279,6 +865,21 @@ mod test {
         assert_eq!("or", operator.name());
     }
 
+    #[test]
+    fn build_should_return_the_not_operator() {
+        let ops = rule::Operator::Not {
+            operator: Box::new(rule::Operator::Equal {
+                first: Value::Text("first_arg".to_owned()),
+                second: Value::Text("second_arg".to_owned()),
+            }),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("not", operator.name());
+    }
+
     #[test]
     fn build_should_return_the_true_operator() {
         let builder = OperatorBuilder::new();
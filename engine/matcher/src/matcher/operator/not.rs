@@ -0,0 +1,199 @@
+use crate::accessor::ExtractedVars;
+use crate::config;
+use crate::error::MatcherError;
+use crate::matcher::operator::{Operator, OperatorBuilder};
+use crate::model::InternalEvent;
+
+const OPERATOR_NAME: &str = "not";
+
+/// A matching matcher.operator that negates the result of a child operator
+#[derive(Debug)]
+pub struct Not {
+    operator: Box<dyn Operator>,
+}
+
+impl Not {
+    pub fn build(
+        rule_name: &str,
+        args: &config::rule::Operator,
+        builder: &OperatorBuilder,
+    ) -> Result<Not, MatcherError> {
+        let operator = builder.build(rule_name, args)?;
+        Ok(Not { operator })
+    }
+}
+
+impl Operator for Not {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        !self.operator.evaluate(event, extracted_vars)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use tornado_common_api::{Event, Value};
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = Not { operator: Box::new(crate::matcher::operator::true_operator::True {}) };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_not_with_expected_arguments() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Equal {
+                first: Value::Text("first_arg=".to_owned()),
+                second: Value::Text("second_arg".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+        assert_eq!("equal", operator.operator.name());
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_nested_operator() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Equal {
+                first: Value::Text("${NOT_EXISTING}".to_owned()),
+                second: Value::Text("second_arg".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_be_recursive() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Or {
+                operators: vec![config::rule::Operator::Equal {
+                    first: Value::Text("3".to_owned()),
+                    second: Value::Text("4".to_owned()),
+                }],
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        assert_eq!("not", operator.name());
+        assert_eq!("or", operator.operator.name());
+
+        assert!(format!("{:?}", operator.operator).contains(
+            r#"Equal { first_arg: Constant { value: Text("3") }, second_arg: Constant { value: Text("4") } }"#
+        ))
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_child_matches() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Equal {
+                first: Value::Text("1".to_owned()),
+                second: Value::Text("1".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_child_does_not_match() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Equal {
+                first: Value::Text("1".to_owned()),
+                second: Value::Text("2".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_child_field_does_not_exist() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Contain {
+                first: Value::Text("${event.payload.subject}".to_owned()),
+                second: Value::Text("test".to_owned()),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = Event::new("email");
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors_recursively() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::And {
+                operators: vec![
+                    config::rule::Operator::Equal {
+                        first: Value::Text("${event.type}".to_owned()),
+                        second: Value::Text("email".to_owned()),
+                    },
+                    config::rule::Operator::Contain {
+                        first: Value::Text("${event.payload.subject}".to_owned()),
+                        second: Value::Text("test".to_owned()),
+                    },
+                ],
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let mut matching_event = Event::new("email");
+        matching_event
+            .payload
+            .insert("subject".to_owned(), Value::Text("production alert".to_owned()));
+
+        let mut not_matching_event = Event::new("email");
+        not_matching_event
+            .payload
+            .insert("subject".to_owned(), Value::Text("this is a test".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(matching_event), None));
+        assert!(!operator.evaluate(&InternalEvent::new(not_matching_event), None));
+    }
+
+    #[test]
+    fn double_negation_should_evaluate_as_the_child() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Not {
+                operator: Box::new(config::rule::Operator::Equal {
+                    first: Value::Text("${event.type}".to_owned()),
+                    second: Value::Text("type".to_owned()),
+                }),
+            },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        assert!(operator.evaluate(&InternalEvent::new(Event::new("type")), None));
+        assert!(!operator.evaluate(&InternalEvent::new(Event::new("other")), None));
+    }
+}
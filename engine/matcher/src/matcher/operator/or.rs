@@ -1,3 +1,4 @@
+use crate::accessor::ExtractedVars;
 use crate::config;
 use crate::error::MatcherError;
 use crate::matcher::operator::{Operator, OperatorBuilder};
@@ -33,12 +34,25 @@ impl Operator for Or {
         OPERATOR_NAME
     }
 
-    fn evaluate(
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        self.operators.iter().any(|op| op.evaluate(event, extracted_vars))
+    }
+
+    fn evaluate_and_extract(
         &self,
         event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
+        extracted_vars: Option<&ExtractedVars>,
+        new_vars: &mut HashMap<String, Value>,
     ) -> bool {
-        self.operators.iter().any(|op| op.evaluate(event, extracted_vars))
+        // Only the variables of the first matching operator are kept
+        self.operators.iter().any(|op| {
+            let mut operator_vars = HashMap::new();
+            let matched = op.evaluate_and_extract(event, extracted_vars, &mut operator_vars);
+            if matched {
+                new_vars.extend(operator_vars);
+            }
+            matched
+        })
     }
 }
 
@@ -378,4 +392,79 @@ mod test {
         assert!(!operator.evaluate(&InternalEvent::new(event), None));
     }
 
+    #[test]
+    fn should_extract_the_variables_of_the_first_matching_operator() {
+        let operator = Or::build(
+            "rule",
+            &vec![
+                config::rule::Operator::RegexAny {
+                    regexes: vec!["email".to_owned()],
+                    target: "${event.type}".to_owned(),
+                    flags: None,
+                    variable: Some("first".to_owned()),
+                },
+                config::rule::Operator::RegexAny {
+                    regexes: vec!["type".to_owned()],
+                    target: "${event.type}".to_owned(),
+                    flags: None,
+                    variable: Some("second".to_owned()),
+                },
+                config::rule::Operator::RegexAny {
+                    regexes: vec!["type".to_owned()],
+                    target: "${event.type}".to_owned(),
+                    flags: None,
+                    variable: Some("third".to_owned()),
+                },
+            ],
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("type"));
+        let mut extracted_vars = HashMap::new();
+
+        assert!(operator.evaluate_and_extract(&event, None, &mut extracted_vars));
+
+        assert_eq!(1, extracted_vars.len());
+        assert_eq!("type", extracted_vars.get("rule.second").unwrap());
+    }
+
+    #[test]
+    fn should_discard_the_variables_of_the_operators_that_do_not_match() {
+        let operator = Or::build(
+            "rule",
+            &vec![
+                config::rule::Operator::And {
+                    operators: vec![
+                        config::rule::Operator::RegexAny {
+                            regexes: vec!["type".to_owned()],
+                            target: "${event.type}".to_owned(),
+                            flags: None,
+                            variable: Some("first".to_owned()),
+                        },
+                        config::rule::Operator::Equal {
+                            first: Value::Text("a".to_owned()),
+                            second: Value::Text("b".to_owned()),
+                        },
+                    ],
+                },
+                config::rule::Operator::RegexAny {
+                    regexes: vec!["type".to_owned()],
+                    target: "${event.type}".to_owned(),
+                    flags: None,
+                    variable: Some("second".to_owned()),
+                },
+            ],
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = InternalEvent::new(Event::new("type"));
+        let mut extracted_vars = HashMap::new();
+
+        assert!(operator.evaluate_and_extract(&event, None, &mut extracted_vars));
+        assert_eq!(1, extracted_vars.len());
+        assert_eq!("type", extracted_vars.get("rule.second").unwrap());
+    }
+
 }
@@ -1,10 +1,9 @@
-use crate::accessor::Accessor;
+use crate::accessor::{Accessor, ExtractedVars};
 use crate::error::MatcherError;
 use crate::matcher::operator::Operator;
 use crate::model::InternalEvent;
-use regex::Regex as RustRegex;
-use std::collections::HashMap;
-use tornado_common_api::{cow_to_str, Value};
+use regex::{Regex as RustRegex, RegexBuilder};
+use tornado_common_api::cow_to_str;
 
 const OPERATOR_NAME: &str = "regex";
 
@@ -15,12 +14,69 @@ pub struct Regex {
     target: Accessor,
 }
 
+/// The flags that modify the behaviour of a regular expression.
+/// They are parsed from a string where each char enables a flag:
+/// - i: case-insensitive matching
+/// - m: multi-line mode, '^' and '$' match the begin and end of each line
+/// - s: allow '.' to match '\n'
+/// - x: ignore whitespaces and allow line comments starting with '#'
+/// - U: swap the meaning of greedy and lazy quantifiers
+#[derive(Debug, Default, PartialEq)]
+pub struct RegexFlags {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub dot_matches_new_line: bool,
+    pub ignore_whitespace: bool,
+    pub swap_greed: bool,
+}
+
+impl RegexFlags {
+    pub fn parse(flags: &str) -> Result<RegexFlags, MatcherError> {
+        let mut result = RegexFlags::default();
+        for flag in flags.chars() {
+            match flag {
+                'i' => result.case_insensitive = true,
+                'm' => result.multi_line = true,
+                's' => result.dot_matches_new_line = true,
+                'x' => result.ignore_whitespace = true,
+                'U' => result.swap_greed = true,
+                _ => {
+                    return Err(MatcherError::OperatorBuildFailError {
+                        message: format!("Cannot parse regex flags [{}]", flags),
+                        cause: format!(
+                            "Unknown flag [{}]. Valid flags are 'i', 'm', 's', 'x' and 'U'",
+                            flag
+                        ),
+                    })
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
 impl Regex {
     pub fn build(regex: &str, target: Accessor) -> Result<Regex, MatcherError> {
-        let regex = RustRegex::new(regex).map_err(|e| MatcherError::OperatorBuildFailError {
-            message: format!("Cannot parse regex [{}]", regex),
-            cause: e.to_string(),
-        })?;
+        Regex::build_with_flags(regex, "", target)
+    }
+
+    pub fn build_with_flags(
+        regex: &str,
+        flags: &str,
+        target: Accessor,
+    ) -> Result<Regex, MatcherError> {
+        let flags = RegexFlags::parse(flags)?;
+        let regex = RegexBuilder::new(regex)
+            .case_insensitive(flags.case_insensitive)
+            .multi_line(flags.multi_line)
+            .dot_matches_new_line(flags.dot_matches_new_line)
+            .ignore_whitespace(flags.ignore_whitespace)
+            .swap_greed(flags.swap_greed)
+            .build()
+            .map_err(|e| MatcherError::OperatorBuildFailError {
+                message: format!("Cannot parse regex [{}]", regex),
+                cause: e.to_string(),
+            })?;
 
         Ok(Regex { target, regex })
     }
@@ -31,11 +87,7 @@ impl Operator for Regex {
         OPERATOR_NAME
     }
 
-    fn evaluate(
-        &self,
-        event: &InternalEvent,
-        extracted_vars: Option<&HashMap<String, Value>>,
-    ) -> bool {
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
         let cow_value = self.target.get(event, extracted_vars);
         cow_to_str(&cow_value).map_or(false, |text| self.regex.is_match(text))
     }
@@ -169,4 +221,68 @@ mod test {
         assert!(!operator.evaluate(&InternalEvent::new(event), None));
     }
 
+    #[test]
+    fn should_parse_regex_flags() {
+        assert_eq!(RegexFlags::default(), RegexFlags::parse("").unwrap());
+        assert_eq!(
+            RegexFlags {
+                case_insensitive: true,
+                multi_line: true,
+                dot_matches_new_line: true,
+                ignore_whitespace: true,
+                swap_greed: true,
+            },
+            RegexFlags::parse("imsxU").unwrap()
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_unknown_flag() {
+        let operator = Regex::build_with_flags(
+            &"[a-f]".to_owned(),
+            &"ig".to_owned(),
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_using_case_insensitive_flag() {
+        let operator = Regex::build_with_flags(
+            &"^critical$".to_owned(),
+            &"i".to_owned(),
+            AccessorBuilder::new().build("", &"${event.payload.severity}".to_owned()).unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("severity".to_owned(), Value::Text("CRITICAL".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_multi_line_flag() {
+        let mut event = Event::new("test_type");
+        event
+            .payload
+            .insert("body".to_owned(), Value::Text("first line\nERROR: disk full".to_owned()));
+        let event = InternalEvent::new(event);
+
+        let operator = Regex::build(
+            &"^ERROR".to_owned(),
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+        )
+        .unwrap();
+        assert!(!operator.evaluate(&event, None));
+
+        let operator = Regex::build_with_flags(
+            &"^ERROR".to_owned(),
+            &"m".to_owned(),
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+        )
+        .unwrap();
+        assert!(operator.evaluate(&event, None));
+    }
+
 }
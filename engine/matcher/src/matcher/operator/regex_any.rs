@@ -0,0 +1,277 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::regex::RegexFlags;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use crate::validator::id::IdValidator;
+use regex::{RegexSet, RegexSetBuilder};
+use std::collections::HashMap;
+use tornado_common_api::{cow_to_str, Value};
+
+const OPERATOR_NAME: &str = "regexAny";
+
+/// A matching matcher.operator that checks whether a string matches at least one regex
+/// of a given list.
+/// All the regexes are compiled into a single RegexSet so that the string is scanned only once.
+/// Optionally, the first matching pattern is stored as an extracted variable of the rule.
+#[derive(Debug)]
+pub struct RegexAny {
+    regex_set: RegexSet,
+    target: Accessor,
+    variable_key: Option<String>,
+}
+
+impl RegexAny {
+    pub fn build(
+        rule_name: &str,
+        regexes: &[String],
+        flags: &str,
+        target: Accessor,
+        variable: Option<&str>,
+    ) -> Result<RegexAny, MatcherError> {
+        if regexes.is_empty() {
+            return Err(MatcherError::OperatorBuildFailError {
+                message: "Cannot build the 'regexAny' operator".to_owned(),
+                cause: "At least one regex is required".to_owned(),
+            });
+        }
+
+        let variable_key = match variable {
+            Some(variable) => {
+                IdValidator::new().validate_extracted_var_name(variable, rule_name)?;
+                Some(format!("{}.{}", rule_name, variable))
+            }
+            None => None,
+        };
+
+        let flags = RegexFlags::parse(flags)?;
+        let regex_set = RegexSetBuilder::new(regexes)
+            .case_insensitive(flags.case_insensitive)
+            .multi_line(flags.multi_line)
+            .dot_matches_new_line(flags.dot_matches_new_line)
+            .ignore_whitespace(flags.ignore_whitespace)
+            .swap_greed(flags.swap_greed)
+            .build()
+            .map_err(|e| MatcherError::OperatorBuildFailError {
+                message: format!("Cannot parse regexes {:?}", regexes),
+                cause: e.to_string(),
+            })?;
+
+        Ok(RegexAny { regex_set, target, variable_key })
+    }
+}
+
+impl Operator for RegexAny {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        let cow_value = self.target.get(event, extracted_vars);
+        match cow_to_str(&cow_value) {
+            Some(text) => self.regex_set.is_match(text),
+            None => false,
+        }
+    }
+
+    fn evaluate_and_extract(
+        &self,
+        event: &InternalEvent,
+        extracted_vars: Option<&ExtractedVars>,
+        new_vars: &mut HashMap<String, Value>,
+    ) -> bool {
+        let variable_key = match &self.variable_key {
+            Some(variable_key) => variable_key,
+            None => return self.evaluate(event, extracted_vars),
+        };
+        let cow_value = self.target.get(event, extracted_vars);
+        let matched_index =
+            cow_to_str(&cow_value).and_then(|text| self.regex_set.matches(text).into_iter().next());
+        match matched_index {
+            Some(index) => {
+                new_vars.insert(
+                    variable_key.to_owned(),
+                    Value::Text(self.regex_set.patterns()[index].to_owned()),
+                );
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use tornado_common_api::*;
+
+    fn patterns() -> Vec<String> {
+        vec!["disk (full|failure)".to_owned(), "out of memory".to_owned(), "^kernel".to_owned()]
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = RegexAny {
+            regex_set: RegexSet::new(&[""]).unwrap(),
+            target: AccessorBuilder::new().build("", &"".to_owned()).unwrap(),
+            variable_key: None,
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+            Some("matched"),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert_eq!(&patterns()[..], operator.regex_set.patterns());
+        assert_eq!("two", operator.target.get(&InternalEvent::new(event), None).unwrap().as_ref());
+        assert_eq!(Some("rule.matched".to_owned()), operator.variable_key);
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_regex() {
+        let operator = RegexAny::build(
+            "rule",
+            &vec!["[a-z]".to_owned(), "[".to_owned()],
+            "",
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+            None,
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_no_regexes() {
+        let operator = RegexAny::build(
+            "rule",
+            &vec![],
+            "",
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+            None,
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_variable_name() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"two".to_owned()).unwrap(),
+            Some("not.valid"),
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_it_matches_any_regex() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), Value::Text("process: out of memory".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_it_does_not_match_any_regex() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), Value::Text("Disk Full".to_owned()));
+
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_evaluate_using_flags() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "i",
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), Value::Text("Disk Full".to_owned()));
+
+        assert!(operator.evaluate(&InternalEvent::new(event), None));
+    }
+
+    #[test]
+    fn should_extract_the_first_matching_pattern() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            Some("matched"),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "body".to_owned(),
+            Value::Text("kernel: out of memory, disk failure".to_owned()),
+        );
+        let event = InternalEvent::new(event);
+        let mut extracted_vars = HashMap::new();
+
+        assert!(operator.evaluate_and_extract(&event, None, &mut extracted_vars));
+
+        assert_eq!(1, extracted_vars.len());
+        assert_eq!("disk (full|failure)", extracted_vars.get("rule.matched").unwrap());
+    }
+
+    #[test]
+    fn should_not_extract_variables_if_no_variable_name() {
+        let operator = RegexAny::build(
+            "rule",
+            &patterns(),
+            "",
+            AccessorBuilder::new().build("", &"${event.payload.body}".to_owned()).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("body".to_owned(), Value::Text("out of memory".to_owned()));
+        let mut extracted_vars = HashMap::new();
+
+        assert!(operator.evaluate_and_extract(
+            &InternalEvent::new(event),
+            None,
+            &mut extracted_vars
+        ));
+
+        assert!(extracted_vars.is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::accessor::{Accessor, ExtractedVars};
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+use chrono::{Datelike, NaiveTime, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use std::convert::TryFrom;
+
+const OPERATOR_NAME: &str = "timeWindow";
+const TIME_FORMAT: &str = "%H:%M";
+
+/// A matching matcher.operator that checks whether the creation time of the Event,
+/// converted to a given timezone, falls on one of the configured weekdays and
+/// within a time-of-day range
+#[derive(Debug)]
+pub struct TimeWindow {
+    target: Accessor,
+    timezone: Tz,
+    days: Vec<Weekday>,
+    from: NaiveTime,
+    to: NaiveTime,
+}
+
+impl TimeWindow {
+    pub fn build(
+        target: Accessor,
+        timezone: &str,
+        days: &[String],
+        from: &str,
+        to: &str,
+    ) -> Result<TimeWindow, MatcherError> {
+        let timezone =
+            timezone.parse::<Tz>().map_err(|e| MatcherError::OperatorBuildFailError {
+                message: format!("Cannot parse timezone [{}]", timezone),
+                cause: e.to_string(),
+            })?;
+        let days = days
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>().map_err(|_| MatcherError::OperatorBuildFailError {
+                    message: format!("Cannot parse weekday [{}]", day),
+                    cause: "Expected a day name such as 'mon' or 'monday'".to_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TimeWindow {
+            target,
+            timezone,
+            days,
+            from: TimeWindow::parse_time(from)?,
+            to: TimeWindow::parse_time(to)?,
+        })
+    }
+
+    fn parse_time(time: &str) -> Result<NaiveTime, MatcherError> {
+        NaiveTime::parse_from_str(time, TIME_FORMAT).map_err(|e| {
+            MatcherError::OperatorBuildFailError {
+                message: format!("Cannot parse time [{}]. Expected format is HH:MM", time),
+                cause: e.to_string(),
+            }
+        })
+    }
+
+    fn is_in_window(&self, timestamp_ms: u64) -> bool {
+        // A timestamp out of the supported range never falls in the window
+        let date_time = match i64::try_from(timestamp_ms)
+            .ok()
+            .and_then(|timestamp_ms| Utc.timestamp_millis_opt(timestamp_ms).single())
+        {
+            Some(date_time) => date_time.with_timezone(&self.timezone),
+            None => return false,
+        };
+
+        let time = NaiveTime::from_hms(date_time.hour(), date_time.minute(), date_time.second());
+        let window_day = if self.from < self.to {
+            if time < self.from || self.to <= time {
+                return false;
+            }
+            date_time.weekday()
+        } else if self.from > self.to {
+            // The window crosses midnight, e.g. from 22:00 to 06:00;
+            // the hours after midnight belong to the window opened the day before
+            if self.from <= time {
+                date_time.weekday()
+            } else if time < self.to {
+                date_time.weekday().pred()
+            } else {
+                return false;
+            }
+        } else {
+            date_time.weekday()
+        };
+
+        self.days.is_empty() || self.days.contains(&window_day)
+    }
+}
+
+impl Operator for TimeWindow {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent, extracted_vars: Option<&ExtractedVars>) -> bool {
+        match self.target.get(event, extracted_vars) {
+            Some(value) => match value.get_number().and_then(|number| number.as_u64()) {
+                Some(timestamp_ms) => self.is_in_window(timestamp_ms),
+                None => false,
+            },
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use tornado_common_api::*;
+
+    // 2019-09-02T10:30:00Z, a Monday
+    const MONDAY_10_30_UTC: u64 = 1_567_420_200_000;
+
+    fn event_created_at(created_ms: u64) -> InternalEvent {
+        let mut event = Event::new("test_type");
+        event.created_ms = created_ms;
+        InternalEvent::new(event)
+    }
+
+    fn business_hours(timezone: &str) -> TimeWindow {
+        TimeWindow::build(
+            Accessor::CreatedMs,
+            timezone,
+            &vec![
+                "mon".to_owned(),
+                "tue".to_owned(),
+                "wed".to_owned(),
+                "thu".to_owned(),
+                "fri".to_owned(),
+            ],
+            "08:00",
+            "18:00",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = business_hours("UTC");
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_build_the_operator_with_expected_arguments() {
+        let operator = TimeWindow::build(
+            Accessor::CreatedMs,
+            "Europe/Rome",
+            &vec!["Mon".to_owned(), "saturday".to_owned()],
+            "08:15",
+            "18:00",
+        )
+        .unwrap();
+
+        assert_eq!(Accessor::CreatedMs, operator.target);
+        assert_eq!(Tz::Europe__Rome, operator.timezone);
+        assert_eq!(vec![Weekday::Mon, Weekday::Sat], operator.days);
+        assert_eq!(NaiveTime::from_hms(8, 15, 0), operator.from);
+        assert_eq!(NaiveTime::from_hms(18, 0, 0), operator.to);
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_timezone() {
+        let operator =
+            TimeWindow::build(Accessor::CreatedMs, "Europe/Nowhere", &vec![], "08:00", "18:00");
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_weekday() {
+        let operator = TimeWindow::build(
+            Accessor::CreatedMs,
+            "UTC",
+            &vec!["funday".to_owned()],
+            "08:00",
+            "18:00",
+        );
+        assert!(operator.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_time() {
+        assert!(TimeWindow::build(Accessor::CreatedMs, "UTC", &vec![], "8", "18:00").is_err());
+        assert!(TimeWindow::build(Accessor::CreatedMs, "UTC", &vec![], "08:00", "25:00").is_err());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_event_is_in_the_window() {
+        let operator = business_hours("UTC");
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_event_is_outside_the_hours() {
+        let operator = business_hours("UTC");
+        // Monday 19:30 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 9 * 3_600_000), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_event_is_outside_the_days() {
+        let operator = business_hours("UTC");
+        // Sunday 10:30 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC - 24 * 3_600_000), None));
+    }
+
+    #[test]
+    fn should_include_from_and_exclude_to() {
+        let operator = business_hours("UTC");
+        // Monday 08:00 UTC
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC - 150 * 60_000), None));
+        // Monday 18:00 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 450 * 60_000), None));
+    }
+
+    #[test]
+    fn should_evaluate_the_time_in_the_given_timezone() {
+        let operator = business_hours("America/New_York");
+        // Monday 06:30 in New York
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC), None));
+        // Monday 08:30 in New York
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 2 * 3_600_000), None));
+    }
+
+    #[test]
+    fn should_evaluate_windows_crossing_midnight() {
+        let operator =
+            TimeWindow::build(Accessor::CreatedMs, "UTC", &vec![], "22:00", "06:00").unwrap();
+
+        // Monday 23:30 UTC
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 13 * 3_600_000), None));
+        // Tuesday 05:30 UTC
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 19 * 3_600_000), None));
+        // Monday 10:30 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC), None));
+    }
+
+    #[test]
+    fn should_check_the_day_in_which_a_window_crossing_midnight_starts() {
+        let operator = TimeWindow::build(
+            Accessor::CreatedMs,
+            "UTC",
+            &vec!["fri".to_owned()],
+            "22:00",
+            "06:00",
+        )
+        .unwrap();
+
+        // Friday 23:30 UTC
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 109 * 3_600_000), None));
+        // Saturday 05:30 UTC
+        assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 115 * 3_600_000), None));
+        // Friday 05:30 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 91 * 3_600_000), None));
+        // Saturday 23:30 UTC
+        assert!(!operator.evaluate(&event_created_at(MONDAY_10_30_UTC + 133 * 3_600_000), None));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_timestamp_is_out_of_range() {
+        let operator =
+            TimeWindow::build(Accessor::CreatedMs, "UTC", &vec![], "00:00", "00:00").unwrap();
+        assert!(!operator.evaluate(&event_created_at(u64::max_value()), None));
+        assert!(!operator.evaluate(&event_created_at(i64::max_value() as u64), None));
+    }
+
+    #[test]
+    fn should_match_every_day_if_no_days_are_specified() {
+        let operator =
+            TimeWindow::build(Accessor::CreatedMs, "UTC", &vec![], "10:00", "11:00").unwrap();
+
+        for day in 0..7 {
+            assert!(operator.evaluate(&event_created_at(MONDAY_10_30_UTC + day * 86_400_000), None));
+        }
+    }
+
+    #[test]
+    fn should_return_false_if_target_is_not_a_number() {
+        let operator = TimeWindow::build(
+            Accessor::Payload { keys: vec!["timestamp".into()] },
+            "UTC",
+            &vec![],
+            "00:00",
+            "00:00",
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert("timestamp".to_owned(), Value::Text("1567420200000".to_owned()));
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+
+        let event = Event::new("test_type");
+        assert!(!operator.evaluate(&InternalEvent::new(event), None));
+    }
+}
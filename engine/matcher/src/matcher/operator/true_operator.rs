@@ -1,7 +1,6 @@
+use crate::accessor::ExtractedVars;
 use crate::matcher::operator::Operator;
 use crate::model::InternalEvent;
-use std::collections::HashMap;
-use tornado_common_api::Value;
 
 const OPERATOR_NAME: &str = "true";
 
@@ -14,11 +13,7 @@ impl Operator for True {
         OPERATOR_NAME
     }
 
-    fn evaluate(
-        &self,
-        _event: &InternalEvent,
-        _extracted_vars: Option<&HashMap<String, Value>>,
-    ) -> bool {
+    fn evaluate(&self, _event: &InternalEvent, _extracted_vars: Option<&ExtractedVars>) -> bool {
         true
     }
 }
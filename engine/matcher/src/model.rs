@@ -70,6 +70,8 @@ pub struct ProcessedRule {
     pub name: String,
     pub status: ProcessedRuleStatus,
     pub actions: Vec<Action>,
+    /// The ids of the actions whose 'when' condition evaluated to false
+    pub skipped_actions: Vec<String>,
     pub message: Option<String>,
 }
 
@@ -79,6 +81,7 @@ impl ProcessedRule {
             name: rule_name,
             status: ProcessedRuleStatus::NotProcessed,
             actions: vec![],
+            skipped_actions: vec![],
             message: None,
         }
     }
@@ -0,0 +1,337 @@
+//! The rule_test module executes the RuleTests declared in the Rules of a configuration
+//! and compares the result of the Matcher with the expected outcome.
+
+use crate::config::rule::{Rule, RuleTest};
+use crate::config::MatcherConfig;
+use crate::matcher::Matcher;
+use crate::model::{ProcessedFilterStatus, ProcessedNode, ProcessedRule, ProcessedRuleStatus};
+use std::collections::HashMap;
+use std::fmt;
+use tornado_common_api::{Action, Value};
+
+/// The result of the execution of the RuleTests of a configuration.
+#[derive(Debug, Default)]
+pub struct RuleTestReport {
+    pub executed: usize,
+    pub failures: Vec<RuleTestFailure>,
+}
+
+impl RuleTestReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// A RuleTest whose result differs from the expected outcome.
+#[derive(Debug)]
+pub struct RuleTestFailure {
+    /// The names of the nodes from the root to the Ruleset that contains the Rule.
+    pub path: Vec<String>,
+    pub rule_name: String,
+    /// The position of the test in the tests of the Rule.
+    pub test_index: usize,
+    pub description: String,
+    pub mismatches: Vec<String>,
+}
+
+impl fmt::Display for RuleTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rule [{}/{}] test [{}]", self.path.join("/"), self.rule_name, self.test_index)?;
+        if !self.description.is_empty() {
+            write!(f, " ({})", self.description)?;
+        }
+        write!(f, ": {}", self.mismatches.join("; "))
+    }
+}
+
+/// Processes the Event of every RuleTest with the Matcher and compares the result
+/// with the expected outcome.
+/// The Matcher must be built from the same configuration.
+/// The tests of inactive Rules, and of Rules inside inactive Filters, are not executed.
+pub fn run_rule_tests(config: &MatcherConfig, matcher: &Matcher) -> RuleTestReport {
+    let mut report = RuleTestReport::default();
+    run_node_tests(config, matcher, &mut vec![], &mut report);
+    report
+}
+
+fn run_node_tests(
+    config: &MatcherConfig,
+    matcher: &Matcher,
+    path: &mut Vec<String>,
+    report: &mut RuleTestReport,
+) {
+    match config {
+        MatcherConfig::Filter { name, filter, nodes } => {
+            if filter.active {
+                path.push(name.to_owned());
+                for node in nodes {
+                    run_node_tests(node, matcher, path, report);
+                }
+                path.pop();
+            }
+        }
+        MatcherConfig::Ruleset { name, rules } => {
+            path.push(name.to_owned());
+            for rule in rules.iter().filter(|rule| rule.active) {
+                for (test_index, test) in rule.tests.iter().enumerate() {
+                    report.executed += 1;
+                    let mismatches = run_rule_test(matcher, path, rule, test);
+                    if !mismatches.is_empty() {
+                        report.failures.push(RuleTestFailure {
+                            path: path.clone(),
+                            rule_name: rule.name.to_owned(),
+                            test_index,
+                            description: test.description.to_owned(),
+                            mismatches,
+                        });
+                    }
+                }
+            }
+            path.pop();
+        }
+    }
+}
+
+fn run_rule_test(matcher: &Matcher, path: &[String], rule: &Rule, test: &RuleTest) -> Vec<String> {
+    let processed_event = matcher.process(test.event.clone());
+    // If the Rule is not found, the Event did not reach its Ruleset
+    let processed = find_processed_rule(&processed_event.result, path, &rule.name);
+    let mut mismatches = vec![];
+
+    let matched = processed
+        .map(|(processed_rule, _)| processed_rule.status == ProcessedRuleStatus::Matched)
+        .unwrap_or(false);
+    if matched != test.expected.matched {
+        let mut mismatch =
+            format!("expected matched [{}], found [{}]", test.expected.matched, matched);
+        if let Some(message) =
+            processed.and_then(|(processed_rule, _)| processed_rule.message.as_ref())
+        {
+            mismatch.push_str(&format!(". {}", message));
+        }
+        mismatches.push(mismatch);
+    }
+
+    if let Some(expected_vars) = &test.expected.extracted_vars {
+        let extracted_vars = processed
+            .map(|(_, extracted_vars)| rule_extracted_vars(&rule.name, extracted_vars))
+            .unwrap_or_default();
+        if &extracted_vars != expected_vars {
+            mismatches.push(format!(
+                "expected extracted variables {:?}, found {:?}",
+                expected_vars, extracted_vars
+            ));
+        }
+    }
+
+    if let Some(expected_actions) = &test.expected.actions {
+        let actions: &[Action] =
+            processed.map(|(processed_rule, _)| processed_rule.actions.as_slice()).unwrap_or(&[]);
+        if actions != expected_actions.as_slice() {
+            mismatches
+                .push(format!("expected actions {:?}, found {:?}", expected_actions, actions));
+        }
+    }
+
+    mismatches
+}
+
+fn find_processed_rule<'a>(
+    node: &'a ProcessedNode,
+    path: &[String],
+    rule_name: &str,
+) -> Option<(&'a ProcessedRule, &'a HashMap<String, Value>)> {
+    let (node_name, children_path) = path.split_first()?;
+    match node {
+        ProcessedNode::Filter { name, filter, nodes } => {
+            if name != node_name || filter.status != ProcessedFilterStatus::Matched {
+                return None;
+            }
+            nodes.iter().find_map(|node| find_processed_rule(node, children_path, rule_name))
+        }
+        ProcessedNode::Ruleset { name, rules } => {
+            if name != node_name || !children_path.is_empty() {
+                return None;
+            }
+            rules
+                .rules
+                .iter()
+                .find(|processed_rule| processed_rule.name == rule_name)
+                .map(|processed_rule| (processed_rule, &rules.extracted_vars))
+        }
+    }
+}
+
+// The extracted variables of a ruleset are prefixed by the name of the rule that extracted them
+fn rule_extracted_vars(
+    rule_name: &str,
+    extracted_vars: &HashMap<String, Value>,
+) -> HashMap<String, Value> {
+    let prefix = format!("{}.", rule_name);
+    extracted_vars
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(&prefix).map(|name| (name.to_owned(), value.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::config::filter::Filter;
+    use maplit::*;
+
+    fn email_rule() -> Rule {
+        let mut rule = Rule::from_json(
+            r#"{
+                "description": "",
+                "continue": true,
+                "active": true,
+                "constraint": {
+                    "WHERE": { "type": "equal", "first": "${event.type}", "second": "email" },
+                    "WITH": {
+                        "temperature": {
+                            "from": "${event.payload.body}",
+                            "regex": { "match": "([0-9]+)\\sDegrees", "group_match_idx": 1 }
+                        }
+                    }
+                },
+                "actions": [
+                    { "id": "logger", "payload": { "temperature": "${_variables.temperature}" } }
+                ],
+                "tests": [
+                    {
+                        "description": "an email with the temperature",
+                        "event": { "type": "email", "created_ms": 0, "payload": { "body": "25 Degrees" } },
+                        "expected": {
+                            "matched": true,
+                            "extracted_vars": { "temperature": "25" },
+                            "actions": [ { "id": "logger", "payload": { "temperature": "25" } } ]
+                        }
+                    },
+                    {
+                        "event": { "type": "email", "created_ms": 0, "payload": { "body": "no temperature" } },
+                        "expected": { "matched": false }
+                    },
+                    {
+                        "event": { "type": "sms", "created_ms": 0, "payload": { "body": "25 Degrees" } },
+                        "expected": { "matched": false, "extracted_vars": {}, "actions": [] }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        rule.name = "email".to_owned();
+        rule
+    }
+
+    fn filtered_config(filter_active: bool, rules: Vec<Rule>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: Filter { description: "".to_owned(), active: true, filter: None },
+            nodes: vec![MatcherConfig::Filter {
+                name: "emails".to_owned(),
+                filter: Filter {
+                    description: "".to_owned(),
+                    active: filter_active,
+                    filter: Some(
+                        serde_json::from_str(
+                            r#"{ "type": "equal", "first": "${event.type}", "second": "email" }"#,
+                        )
+                        .unwrap(),
+                    ),
+                },
+                nodes: vec![MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules }],
+            }],
+        }
+    }
+
+    #[test]
+    fn should_run_the_tests_of_all_the_rules() {
+        // Arrange
+        let config = filtered_config(true, vec![email_rule()]);
+        let matcher = Matcher::build(&config).unwrap();
+
+        // Act
+        let report = run_rule_tests(&config, &matcher);
+
+        // Assert
+        assert_eq!(3, report.executed);
+        assert!(report.is_success(), "{:?}", report.failures);
+    }
+
+    #[test]
+    fn should_report_the_tests_with_an_unexpected_result() {
+        // Arrange
+        let mut rule = email_rule();
+        rule.tests[0].expected.extracted_vars =
+            Some(hashmap!["temperature".to_owned() => Value::Text("30".to_owned())]);
+        rule.tests[1].expected.matched = true;
+        let config = filtered_config(true, vec![rule]);
+        let matcher = Matcher::build(&config).unwrap();
+
+        // Act
+        let report = run_rule_tests(&config, &matcher);
+
+        // Assert
+        assert_eq!(3, report.executed);
+        assert_eq!(2, report.failures.len());
+
+        let failure = &report.failures[0];
+        assert_eq!(vec!["root", "emails", "ruleset"], failure.path);
+        assert_eq!("email", failure.rule_name);
+        assert_eq!(0, failure.test_index);
+        assert_eq!("an email with the temperature", failure.description);
+        assert_eq!(1, failure.mismatches.len());
+        assert!(failure.mismatches[0].contains("expected extracted variables"));
+
+        let failure = &report.failures[1];
+        assert_eq!(1, failure.test_index);
+        assert_eq!(1, failure.mismatches.len());
+        assert!(failure.mismatches[0].starts_with("expected matched [true], found [false]"));
+        assert!(format!("{}", failure).starts_with("Rule [root/emails/ruleset/email] test [1]: "));
+    }
+
+    #[test]
+    fn should_consider_not_matched_the_events_that_do_not_pass_the_filters() {
+        // Arrange
+        let mut rule = email_rule();
+        rule.tests[2].expected.matched = true;
+        let config = filtered_config(true, vec![rule]);
+        let matcher = Matcher::build(&config).unwrap();
+
+        // Act
+        let report = run_rule_tests(&config, &matcher);
+
+        // Assert
+        assert_eq!(1, report.failures.len());
+        assert_eq!(2, report.failures[0].test_index);
+        assert_eq!(
+            vec!["expected matched [true], found [false]".to_owned()],
+            report.failures[0].mismatches
+        );
+    }
+
+    #[test]
+    fn should_skip_the_tests_of_inactive_rules_and_filters() {
+        // Arrange
+        let mut inactive_rule = email_rule();
+        inactive_rule.name = "inactive_email".to_owned();
+        inactive_rule.active = false;
+        let config = filtered_config(true, vec![inactive_rule]);
+        let inactive_filter_config = filtered_config(false, vec![email_rule()]);
+
+        // Act
+        let report = run_rule_tests(&config, &Matcher::build(&config).unwrap());
+        let inactive_filter_report = run_rule_tests(
+            &inactive_filter_config,
+            &Matcher::build(&inactive_filter_config).unwrap(),
+        );
+
+        // Assert
+        assert_eq!(0, report.executed);
+        assert_eq!(0, inactive_filter_report.executed);
+    }
+}
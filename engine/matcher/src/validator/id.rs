@@ -5,6 +5,7 @@ const ID_REGEX_PATTERN: &str = "^[a-zA-Z0-9_]+$";
 
 /// A validator for name and ID
 /// It checks that a string is composed only of alphabetical characters, numbers, and the '_' character.
+#[derive(Clone)]
 pub struct IdValidator {
     regex: RustRegex,
 }
@@ -55,6 +56,15 @@ impl IdValidator {
         self.validate(ruleset_name, error_message)
     }
 
+    /// Validates an action template name.
+    pub fn validate_action_template_name(&self, template_name: &str) -> Result<(), MatcherError> {
+        let error_message = format!(
+            "Action template name [{}] is not valid. It should respect the pattern {}",
+            template_name, ID_REGEX_PATTERN
+        );
+        self.validate(template_name, error_message)
+    }
+
     /// Validates an extracted variable name.
     pub fn validate_extracted_var_name(
         &self,
@@ -142,4 +152,15 @@ mod test {
         assert!(id.validate_action_id("!", "rule").is_err());
     }
 
+    #[test]
+    fn should_check_if_valid_action_template_name() {
+        let id = IdValidator::new();
+
+        assert!(id.validate_action_template_name("archive").is_ok());
+        assert!(id.validate_action_template_name("icinga2_process_check").is_ok());
+
+        assert!(id.validate_action_template_name("").is_err());
+        assert!(id.validate_action_template_name("icinga2.process").is_err());
+    }
+
 }
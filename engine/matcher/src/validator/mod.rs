@@ -1,20 +1,31 @@
 pub mod id;
 
+use crate::accessor::AccessorBuilder;
 use crate::config::filter::Filter;
-use crate::config::rule::Rule;
+use crate::config::rule::{Operator, Rule};
+use crate::config::template::ActionTemplate;
 use crate::config::MatcherConfig;
 use crate::error::MatcherError;
+use crate::matcher::extractor::extraction_order;
 use log::*;
+use std::collections::HashMap;
 
 /// A validator for a MatcherConfig
 #[derive(Default)]
 pub struct MatcherConfigValidator {
     id: id::IdValidator,
+    accessor: AccessorBuilder,
 }
 
 impl MatcherConfigValidator {
     pub fn new() -> MatcherConfigValidator {
-        MatcherConfigValidator { id: id::IdValidator::new() }
+        MatcherConfigValidator::with_accessor(AccessorBuilder::new())
+    }
+
+    /// Returns a new MatcherConfigValidator that validates the accessors of the rules
+    /// with the given AccessorBuilder.
+    pub fn with_accessor(accessor: AccessorBuilder) -> MatcherConfigValidator {
+        MatcherConfigValidator { id: id::IdValidator::new(), accessor }
     }
 
     pub fn validate(&self, config: &MatcherConfig) -> Result<(), MatcherError> {
@@ -26,6 +37,20 @@ impl MatcherConfigValidator {
         }
     }
 
+    /// Validates a set of ActionTemplates.
+    /// It checks that each template has a valid name and a valid action ID.
+    pub fn validate_action_templates(
+        &self,
+        templates: &HashMap<String, ActionTemplate>,
+    ) -> Result<(), MatcherError> {
+        for (name, template) in templates {
+            debug!("MatcherConfigValidator - Validating action template: [{}]", name);
+            self.id.validate_action_template_name(name)?;
+            self.id.validate_action_id(&template.id, name)?;
+        }
+        Ok(())
+    }
+
     /// Validates that a Filter has a valid name and triggers the validation recursively
     /// for all filter's nodes.
     fn validate_filter(
@@ -68,7 +93,10 @@ impl MatcherConfigValidator {
     /// Checks that a rule:
     /// - has a valid name
     /// - has valid extracted variable names
+    /// - has no operator variables with the same name of an extracted variable
+    /// - has no circular dependencies between its extracted variables
     /// - has valid action IDs
+    /// - has no actions that reference an unresolved action template
     fn validate_rule(&self, rule: &Rule) -> Result<(), MatcherError> {
         let rule_name = &rule.name;
 
@@ -79,7 +107,36 @@ impl MatcherConfigValidator {
             self.id.validate_extracted_var_name(var_name, rule_name)?
         }
 
+        if let Some(operator) = &rule.constraint.where_operator {
+            let mut operator_vars = vec![];
+            operator_variables(operator, &mut operator_vars);
+            if let Some(var_name) =
+                operator_vars.iter().find(|var_name| rule.constraint.with.contains_key(**var_name))
+            {
+                return Err(MatcherError::ConfigurationError {
+                    message: format!(
+                        "The variable [{}] of a 'regexAny' operator of rule [{}] has the same name of a variable of the WITH clause",
+                        var_name, rule_name
+                    ),
+                });
+            }
+        }
+
+        let mut targets = HashMap::new();
+        for (var_name, extractor) in &rule.constraint.with {
+            targets.insert(var_name.to_owned(), self.accessor.build(rule_name, &extractor.from)?);
+        }
+        extraction_order(rule_name, &targets)?;
+
         for action in &rule.actions {
+            if let Some(template) = &action.template {
+                return Err(MatcherError::ConfigurationError {
+                    message: format!(
+                        "The action template [{}] used by rule [{}] is not resolved",
+                        template, rule_name
+                    ),
+                });
+            }
             self.id.validate_action_id(&action.id, rule_name)?
         }
 
@@ -100,10 +157,28 @@ impl MatcherConfigValidator {
     }
 }
 
+/// Collects the names of the variables set by an operator and by its children.
+fn operator_variables<'o>(operator: &'o Operator, variables: &mut Vec<&'o str>) {
+    match operator {
+        Operator::And { operators } | Operator::Or { operators } => {
+            for operator in operators {
+                operator_variables(operator, variables);
+            }
+        }
+        Operator::Not { operator }
+        | Operator::Any { operator, .. }
+        | Operator::All { operator, .. } => operator_variables(operator, variables),
+        Operator::RegexAny { variable: Some(variable), .. } => variables.push(variable),
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::config::rule::{Action, Constraint, Extractor, ExtractorRegex, Operator};
+    use crate::config::rule::{
+        Action, Constraint, Extractor, ExtractorKind, ExtractorRegex, Operator,
+    };
     use std::collections::HashMap;
     use tornado_common_api::Value;
 
@@ -256,7 +331,12 @@ mod test {
             "var.with.dot".to_owned(),
             Extractor {
                 from: String::from("${event.type}"),
-                regex: ExtractorRegex { regex: String::from(r"[0-9]+"), group_match_idx: 0 },
+                kind: ExtractorKind::Regex(ExtractorRegex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: false,
+                }),
+                modifiers_post: vec![],
             },
         );
 
@@ -267,6 +347,82 @@ mod test {
         assert!(matcher.is_err());
     }
 
+    #[test]
+    fn build_should_fail_if_operator_variable_has_the_name_of_an_extracted_var() {
+        // Arrange
+        let mut rule_1 = new_rule(
+            "rule_name",
+            Operator::Any {
+                target: "${event.payload.messages}".to_owned(),
+                operator: Box::new(Operator::RegexAny {
+                    regexes: vec!["disk".to_owned()],
+                    target: "${item}".to_owned(),
+                    flags: None,
+                    variable: Some("reason".to_owned()),
+                }),
+            },
+        );
+        rule_1.constraint.with.insert("reason".to_owned(), new_extractor("${event.type}"));
+
+        // Act
+        let matcher = MatcherConfigValidator::new().validate_ruleset("ruleset", &vec![rule_1]);
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
+    #[test]
+    fn should_validate_extracted_vars_that_read_other_vars_of_the_same_rule() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_name", None);
+        rule_1.constraint.with.insert("first".to_owned(), new_extractor("${event.type}"));
+        rule_1.constraint.with.insert("second".to_owned(), new_extractor("${_variables.first}"));
+        rule_1
+            .constraint
+            .with
+            .insert("third".to_owned(), new_extractor("${_variables.rule_name.second}"));
+
+        // Act
+        let matcher = MatcherConfigValidator::new().validate_ruleset("ruleset", &vec![rule_1]);
+
+        // Assert
+        assert!(matcher.is_ok());
+    }
+
+    #[test]
+    fn build_should_fail_if_circular_dependencies_between_extracted_vars() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_name", None);
+        rule_1.constraint.with.insert("first".to_owned(), new_extractor("${_variables.third}"));
+        rule_1.constraint.with.insert("second".to_owned(), new_extractor("${_variables.first}"));
+        rule_1.constraint.with.insert("third".to_owned(), new_extractor("${_variables.second}"));
+
+        // Act
+        let matcher = MatcherConfigValidator::new().validate_ruleset("ruleset", &vec![rule_1]);
+
+        // Assert
+        assert_eq!(
+            Err(MatcherError::CircularExtractedVariablesError {
+                rule_name: "rule_name".to_owned(),
+                variables: vec!["first".to_owned(), "third".to_owned(), "second".to_owned()],
+            }),
+            matcher
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_extracted_var_reads_itself() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_name", None);
+        rule_1.constraint.with.insert("first".to_owned(), new_extractor("${_variables.first}"));
+
+        // Act
+        let matcher = MatcherConfigValidator::new().validate_ruleset("ruleset", &vec![rule_1]);
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
     #[test]
     fn build_should_fail_if_not_correct_action_id() {
         // Arrange
@@ -279,6 +435,27 @@ mod test {
         rule_1.actions.push(Action {
             id: "id.with.dot.and.question.mark?".to_owned(),
             payload: HashMap::new(),
+            when: None,
+            template: None,
+        });
+
+        // Act
+        let matcher = MatcherConfigValidator::new().validate_ruleset("ruleset", &vec![rule_1]);
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_action_template_is_not_resolved() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_name", None);
+
+        rule_1.actions.push(Action {
+            id: "".to_owned(),
+            payload: HashMap::new(),
+            when: None,
+            template: Some("archive".to_owned()),
         });
 
         // Act
@@ -288,6 +465,41 @@ mod test {
         assert!(matcher.is_err());
     }
 
+    #[test]
+    fn should_validate_the_action_templates() {
+        // Arrange
+        let mut templates = HashMap::new();
+        templates.insert(
+            "archive".to_owned(),
+            ActionTemplate { id: "archive".to_owned(), payload: HashMap::new() },
+        );
+
+        // Act & Assert
+        assert!(MatcherConfigValidator::new().validate_action_templates(&templates).is_ok());
+
+        templates.insert(
+            "not.valid".to_owned(),
+            ActionTemplate { id: "archive".to_owned(), payload: HashMap::new() },
+        );
+        assert!(MatcherConfigValidator::new().validate_action_templates(&templates).is_err());
+    }
+
+    #[test]
+    fn should_fail_if_the_action_template_id_is_not_valid() {
+        // Arrange
+        let mut templates = HashMap::new();
+        templates.insert(
+            "archive".to_owned(),
+            ActionTemplate { id: "".to_owned(), payload: HashMap::new() },
+        );
+
+        // Act
+        let result = MatcherConfigValidator::new().validate_action_templates(&templates);
+
+        // Assert
+        assert!(result.is_err());
+    }
+
     #[test]
     fn build_should_fail_if_wrong_filter_name() {
         // Arrange
@@ -405,6 +617,18 @@ mod test {
         assert!(matcher.is_err());
     }
 
+    fn new_extractor(from: &str) -> Extractor {
+        Extractor {
+            from: from.to_owned(),
+            kind: ExtractorKind::Regex(ExtractorRegex {
+                regex: String::from(r"[0-9]+"),
+                group_match_idx: Some(0),
+                all_matches: false,
+            }),
+            modifiers_post: vec![],
+        }
+    }
+
     fn new_rule<O: Into<Option<Operator>>>(name: &str, operator: O) -> Rule {
         let constraint = Constraint { where_operator: operator.into(), with: HashMap::new() };
 
@@ -415,6 +639,7 @@ mod test {
             actions: vec![],
             description: "".to_owned(),
             constraint,
+            tests: vec![],
         }
     }
 }
@@ -1,14 +1,19 @@
 use crate::engine::{EventMessageWithReply, MatcherActor};
+use crate::reload::MatcherReloader;
 use actix::Addr;
 use futures::future::{Future, FutureResult};
-use tornado_engine_api::api::handler::{ApiHandler, SendEventRequest};
+use log::*;
+use std::sync::Arc;
+use tornado_engine_api::api::handler::{ApiHandler, DraftEdit, SendEventRequest};
 use tornado_engine_api::error::ApiError;
 use tornado_engine_matcher::config::{MatcherConfig, MatcherConfigManager};
+use tornado_engine_matcher::error::MatcherError;
 use tornado_engine_matcher::model::ProcessedEvent;
 
 pub struct MatcherApiHandler {
     matcher: Addr<MatcherActor>,
-    config_manager: Box<dyn MatcherConfigManager>,
+    config_manager: Arc<dyn MatcherConfigManager>,
+    reloader: Arc<MatcherReloader>,
 }
 
 impl ApiHandler for MatcherApiHandler {
@@ -16,6 +21,66 @@ impl ApiHandler for MatcherApiHandler {
         Box::new(FutureResult::from(self.config_manager.read().map_err(ApiError::from)))
     }
 
+    fn reload_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        Box::new(FutureResult::from(self.reloader.reload().map_err(ApiError::from)))
+    }
+
+    fn get_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        Box::new(FutureResult::from(self.config_manager.get_draft().map_err(ApiError::from)))
+    }
+
+    fn update_draft(
+        &self,
+        config: MatcherConfig,
+    ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        let result = self.config_manager.update_draft(&config).map(|_| config);
+        Box::new(FutureResult::from(result.map_err(ApiError::from)))
+    }
+
+    fn edit_draft(
+        &self,
+        edit: DraftEdit,
+    ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        // The config manager accepts only MatcherErrors; the ApiError of the edit
+        // is kept aside to be returned as it is.
+        let mut edit_error = None;
+        let result = self.config_manager.edit_draft(Box::new(|config| {
+            edit(config).map_err(|err| {
+                let message = format!("{}", err);
+                edit_error = Some(err);
+                MatcherError::ConfigurationError { message }
+            })
+        }));
+        let result = match edit_error {
+            Some(err) => Err(err),
+            None => result.map_err(ApiError::from),
+        };
+        Box::new(FutureResult::from(result))
+    }
+
+    fn delete_draft(&self) -> Box<dyn Future<Item = (), Error = ApiError>> {
+        Box::new(FutureResult::from(self.config_manager.delete_draft().map_err(ApiError::from)))
+    }
+
+    fn deploy_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        let result = self.config_manager.deploy_draft().and_then(|_| {
+            let reloaded = self.reloader.reload();
+            if reloaded.is_err() {
+                // The deployed configuration cannot be loaded; it is reverted to a draft.
+                if let Err(err) = self.config_manager.rollback() {
+                    error!("Cannot roll back the deployed configuration. Err: {}", err);
+                }
+            }
+            reloaded
+        });
+        Box::new(FutureResult::from(result.map_err(ApiError::from)))
+    }
+
+    fn rollback_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+        let result = self.config_manager.rollback().and_then(|_| self.reloader.reload());
+        Box::new(FutureResult::from(result.map_err(ApiError::from)))
+    }
+
     fn send_event(
         &self,
         event: SendEventRequest,
@@ -37,10 +102,11 @@ impl ApiHandler for MatcherApiHandler {
 
 impl MatcherApiHandler {
     pub fn new(
-        config_manager: Box<dyn MatcherConfigManager>,
+        config_manager: Arc<dyn MatcherConfigManager>,
+        reloader: Arc<MatcherReloader>,
         matcher: Addr<MatcherActor>,
     ) -> MatcherApiHandler {
-        MatcherApiHandler { config_manager, matcher }
+        MatcherApiHandler { config_manager, reloader, matcher }
     }
 }
 
@@ -48,8 +114,9 @@ impl MatcherApiHandler {
 mod test {
     use super::*;
     use crate::dispatcher::{ActixEventBus, DispatcherActor};
+    use crate::reload::SharedMatcher;
     use actix::{Arbiter, SyncArbiter, System};
-    use std::sync::Arc;
+    use std::collections::HashMap;
     use tornado_common_api::Event;
     use tornado_engine_api::api::handler::ProcessType;
     use tornado_engine_matcher::config::fs::FsMatcherConfigManager;
@@ -60,8 +127,11 @@ mod test {
     fn should_send_an_event_to_the_matcher_and_return_the_processed_event() {
         // Arrange
         let path = "./config/rules.d";
-        let config = FsMatcherConfigManager::new(path);
-        let matcher = Arc::new(config.read().and_then(|config| Matcher::build(&config)).unwrap());
+        let config = Arc::new(FsMatcherConfigManager::new(path));
+        let matcher =
+            SharedMatcher::new(config.read().and_then(|config| Matcher::build(&config)).unwrap());
+        let reloader =
+            Arc::new(MatcherReloader::new(config.clone(), HashMap::new(), matcher.clone()));
 
         System::run(move || {
             let event_bus = Arc::new(ActixEventBus { callback: |_| {} });
@@ -76,7 +146,7 @@ mod test {
                 dispatcher_addr: dispatcher_addr.clone(),
             });
 
-            let api = MatcherApiHandler { matcher: matcher_addr, config_manager: Box::new(config) };
+            let api = MatcherApiHandler::new(config, reloader, matcher_addr);
 
             let send_event_request = SendEventRequest {
                 process_type: ProcessType::SkipActions,
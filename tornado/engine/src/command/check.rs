@@ -1,15 +1,32 @@
 use crate::config::parse_config_files;
 use failure::Fail;
 use tornado_engine_matcher::matcher::Matcher;
+use tornado_engine_matcher::rule_test::run_rule_tests;
 
-pub fn check(config_dir: &str, rules_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub fn check(
+    config_dir: &str,
+    rules_dir: &str,
+    actions_dir: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("Check Tornado configuration");
-    let configs = parse_config_files(config_dir, rules_dir)?;
-    let _matcher = configs
-        .matcher_config
-        .read()
-        .and_then(|config| Matcher::build(&config))
+    let configs = parse_config_files(config_dir, rules_dir, actions_dir)?;
+    let config = configs.matcher_config.read().map_err(Fail::compat)?;
+    let matcher = Matcher::build_with_constants(&config, configs.tornado.tornado.constants.clone())
         .map_err(Fail::compat)?;
+
+    let report = run_rule_tests(&config, &matcher);
+    if report.executed > 0 {
+        println!("Executed {} rule tests", report.executed);
+    }
+    if !report.is_success() {
+        for failure in &report.failures {
+            println!("FAILED: {}", failure);
+        }
+        return Err(
+            format!("{} of {} rule tests failed", report.failures.len(), report.executed).into()
+        );
+    }
+
     println!("The configuration is correct.");
     Ok(())
 }
@@ -6,6 +6,7 @@ use crate::executor::icinga2::{Icinga2ApiClientActor, Icinga2ApiClientMessage};
 use crate::executor::ActionMessage;
 use crate::executor::ExecutorActor;
 use crate::monitoring::monitoring_endpoints;
+use crate::reload::{reload_on_sighup, MatcherReloader, SharedMatcher};
 use actix::prelude::*;
 use actix_cors::Cors;
 use actix_web::{web, App, HttpServer};
@@ -18,17 +19,26 @@ use tornado_common_logger::setup_logger;
 use tornado_engine_matcher::dispatcher::Dispatcher;
 use tornado_engine_matcher::matcher::Matcher;
 
-pub fn daemon(config_dir: &str, rules_dir: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let configs = config::parse_config_files(config_dir, rules_dir)?;
+pub fn daemon(
+    config_dir: &str,
+    rules_dir: &str,
+    actions_dir: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let configs = config::parse_config_files(config_dir, rules_dir, actions_dir)?;
 
     setup_logger(&configs.tornado.logger).map_err(Fail::compat)?;
 
+    // The deployed versions are prepared before anything reads the configuration
+    configs.matcher_config.prepare_versions().map_err(Fail::compat)?;
+
     // Start matcher
-    let matcher = Arc::new(
+    let matcher = SharedMatcher::new(
         configs
             .matcher_config
             .read()
-            .and_then(|config| Matcher::build(&config))
+            .and_then(|config| {
+                Matcher::build_with_constants(&config, configs.tornado.tornado.constants.clone())
+            })
             .map_err(Fail::compat)?,
     );
 
@@ -89,8 +99,9 @@ pub fn daemon(config_dir: &str, rules_dir: &str) -> Result<(), Box<dyn std::erro
         });
 
         // Start matcher actor
+        let actor_matcher = matcher.clone();
         let matcher_addr = SyncArbiter::start(cpus, move || MatcherActor {
-            matcher: matcher.clone(),
+            matcher: actor_matcher.clone(),
             dispatcher_addr: dispatcher_addr.clone(),
         });
 
@@ -118,7 +129,16 @@ pub fn daemon(config_dir: &str, rules_dir: &str) -> Result<(), Box<dyn std::erro
         let web_server_port = daemon_config.web_server_port;
         let matcher_config = configs.matcher_config;
 
-        let api_handler = Arc::new(MatcherApiHandler::new(matcher_config, matcher_addr.clone()));
+        // Reload the matcher configuration on SIGHUP and through the API
+        let reloader = Arc::new(MatcherReloader::new(
+            matcher_config.clone(),
+            configs.tornado.tornado.constants,
+            matcher.clone(),
+        ));
+        reload_on_sighup(reloader.clone());
+
+        let api_handler =
+            Arc::new(MatcherApiHandler::new(matcher_config, reloader, matcher_addr.clone()));
 
         // Start API and monitoring endpoint
         HttpServer::new(move || {
@@ -2,9 +2,14 @@ use crate::executor::icinga2::Icinga2ClientConfig;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use config_rs::{Config, ConfigError, File};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tornado_common_api::Value;
 use tornado_common_logger::LoggerConfig;
+use tornado_engine_matcher::accessor::AccessorBuilder;
 use tornado_engine_matcher::config::fs::FsMatcherConfigManager;
 use tornado_engine_matcher::config::MatcherConfigManager;
+use tornado_engine_matcher::matcher::operator::OperatorBuilder;
 use tornado_executor_archive::config::ArchiveConfig;
 
 pub const CONFIG_DIR_DEFAULT: Option<&'static str> = option_env!("TORNADO_CONFIG_DIR_DEFAULT");
@@ -19,10 +24,14 @@ pub fn arg_matches<'a>() -> ArgMatches<'a> {
             .long("rules-dir")
             .help("The folder where the processing tree configuration is saved in JSON format. This folder is relative to the `config-dir`")
             .default_value("/rules.d/"))
+        .arg(Arg::with_name("actions-dir")
+            .long("actions-dir")
+            .help("The folder where the action templates referenced by the Rules are saved in JSON format. This folder is relative to the `config-dir`")
+            .default_value("/actions.d/"))
         .subcommand(SubCommand::with_name("daemon" )
             .help("Starts the Tornado daemon"))
         .subcommand(SubCommand::with_name("check" )
-            .help("Checks that the configuration is valid"))
+            .help("Checks that the configuration is valid and runs the tests of the Rules"))
         .get_matches()
 }
 
@@ -44,6 +53,9 @@ pub struct GlobalConfig {
 #[derive(Deserialize, Serialize, Clone)]
 pub struct TornadoConfig {
     pub daemon: DaemonCommandConfig,
+    /// The global constants that the Rules can read through the "${constants.*}" accessor
+    #[serde(default)]
+    pub constants: HashMap<String, Value>,
 }
 
 pub fn build_config(config_dir: &str) -> Result<GlobalConfig, ConfigError> {
@@ -68,7 +80,7 @@ fn build_icinga2_client_config(config_dir: &str) -> Result<Icinga2ClientConfig,
 }
 
 pub struct ComponentsConfig {
-    pub matcher_config: Box<dyn MatcherConfigManager>,
+    pub matcher_config: Arc<dyn MatcherConfigManager>,
     pub tornado: GlobalConfig,
     pub archive_executor_config: ArchiveConfig,
     pub icinga2_executor_config: Icinga2ClientConfig,
@@ -77,9 +89,15 @@ pub struct ComponentsConfig {
 pub fn parse_config_files(
     config_dir: &str,
     rules_dir: &str,
+    actions_dir: &str,
 ) -> Result<ComponentsConfig, Box<dyn std::error::Error>> {
-    let matcher_config = Box::new(build_matcher_config(config_dir, rules_dir));
     let tornado = build_config(config_dir)?;
+    let matcher_config = Arc::new(build_matcher_config(
+        config_dir,
+        rules_dir,
+        actions_dir,
+        tornado.tornado.constants.clone(),
+    ));
     let archive_executor_config = build_archive_config(config_dir)?;
     let icinga2_executor_config = build_icinga2_client_config(config_dir)?;
     Ok(ComponentsConfig {
@@ -90,8 +108,19 @@ pub fn parse_config_files(
     })
 }
 
-fn build_matcher_config(config_dir: &str, rules_dir: &str) -> impl MatcherConfigManager {
-    FsMatcherConfigManager::new(format!("{}/{}", config_dir, rules_dir))
+fn build_matcher_config(
+    config_dir: &str,
+    rules_dir: &str,
+    actions_dir: &str,
+    constants: HashMap<String, Value>,
+) -> impl MatcherConfigManager {
+    FsMatcherConfigManager::with_action_templates(
+        format!("{}/{}", config_dir, rules_dir),
+        format!("{}/{}", config_dir, actions_dir),
+    )
+    .with_operator_builder(OperatorBuilder::with_accessor(AccessorBuilder::with_constants(
+        constants,
+    )))
 }
 
 #[cfg(test)]
@@ -142,9 +171,10 @@ mod test {
         // Arrange
         let config_dir = "./config";
         let rules_dir = "/rules.d";
+        let actions_dir = "/actions.d";
 
         // Act
-        let config = parse_config_files(config_dir, rules_dir).unwrap();
+        let config = parse_config_files(config_dir, rules_dir, actions_dir).unwrap();
 
         // Assert
         assert_eq!(
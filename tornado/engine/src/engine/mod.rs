@@ -1,11 +1,11 @@
 use crate::dispatcher::{DispatcherActor, ProcessedEventMessage};
+use crate::reload::SharedMatcher;
 use actix::prelude::*;
 use log::*;
-use std::sync::Arc;
 use tornado_common_api;
 use tornado_engine_api::api::handler::ProcessType;
+use tornado_engine_matcher::error;
 use tornado_engine_matcher::model::ProcessedEvent;
-use tornado_engine_matcher::{error, matcher};
 
 pub struct EventMessageWithReply {
     pub event: tornado_common_api::Event,
@@ -26,7 +26,7 @@ impl Message for EventMessage {
 
 pub struct MatcherActor {
     pub dispatcher_addr: Addr<DispatcherActor>,
-    pub matcher: Arc<matcher::Matcher>,
+    pub matcher: SharedMatcher,
 }
 
 impl Actor for MatcherActor {
@@ -42,7 +42,7 @@ impl Handler<EventMessage> for MatcherActor {
     fn handle(&mut self, msg: EventMessage, _: &mut SyncContext<Self>) -> Self::Result {
         trace!("MatcherActor - received new EventMessage [{:?}]", &msg.event);
 
-        let processed_event = self.matcher.process(msg.event);
+        let processed_event = self.matcher.get().process(msg.event);
         self.dispatcher_addr.do_send(ProcessedEventMessage { event: processed_event });
         Ok(())
     }
@@ -54,7 +54,7 @@ impl Handler<EventMessageWithReply> for MatcherActor {
     fn handle(&mut self, msg: EventMessageWithReply, _: &mut SyncContext<Self>) -> Self::Result {
         trace!("MatcherActor - received new EventMessageWithReply [{:?}]", &msg.event);
 
-        let processed_event = self.matcher.process(msg.event);
+        let processed_event = self.matcher.get().process(msg.event);
 
         match msg.process_type {
             ProcessType::Full => self
@@ -7,17 +7,19 @@ pub mod dispatcher;
 pub mod engine;
 pub mod executor;
 mod monitoring;
+pub mod reload;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let arg_matches = config::arg_matches();
 
     let config_dir = arg_matches.value_of("config-dir").expect("config-dir should be provided");
     let rules_dir = arg_matches.value_of("rules-dir").expect("rules-dir should be provided");
+    let actions_dir = arg_matches.value_of("actions-dir").expect("actions-dir should be provided");
 
     let subcommand = arg_matches.subcommand();
     match subcommand {
-        ("check", _) => command::check::check(config_dir, rules_dir),
-        ("daemon", _) => command::daemon::daemon(config_dir, rules_dir),
+        ("check", _) => command::check::check(config_dir, rules_dir, actions_dir),
+        ("daemon", _) => command::daemon::daemon(config_dir, rules_dir, actions_dir),
         _ => {
             error!("Unknown subcommand [{}]", subcommand.0);
             Ok(())
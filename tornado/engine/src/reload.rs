@@ -0,0 +1,251 @@
+use futures::{Future, Stream};
+use log::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use tornado_common_api::Value;
+use tornado_engine_matcher::config::{MatcherConfig, MatcherConfigManager};
+use tornado_engine_matcher::error::MatcherError;
+use tornado_engine_matcher::matcher::Matcher;
+
+/// A reference to the Matcher used to process the incoming events.
+/// The Matcher can be replaced at runtime; the events already being processed
+/// complete with the Matcher that was active when their processing started.
+#[derive(Clone)]
+pub struct SharedMatcher {
+    matcher: Arc<RwLock<Arc<Matcher>>>,
+}
+
+impl SharedMatcher {
+    pub fn new(matcher: Matcher) -> SharedMatcher {
+        SharedMatcher { matcher: Arc::new(RwLock::new(Arc::new(matcher))) }
+    }
+
+    /// Returns the currently active Matcher.
+    pub fn get(&self) -> Arc<Matcher> {
+        self.matcher.read().expect("The SharedMatcher lock should not be poisoned").clone()
+    }
+
+    /// Atomically replaces the active Matcher.
+    pub fn replace(&self, matcher: Matcher) {
+        *self.matcher.write().expect("The SharedMatcher lock should not be poisoned") =
+            Arc::new(matcher);
+    }
+}
+
+/// Rebuilds the Matcher from the configuration and swaps it into a SharedMatcher.
+/// Concurrent reloads are serialized, so that the last one always applies the latest configuration.
+pub struct MatcherReloader {
+    config_manager: Arc<dyn MatcherConfigManager>,
+    constants: HashMap<String, Value>,
+    matcher: SharedMatcher,
+    reload_lock: Mutex<()>,
+}
+
+impl MatcherReloader {
+    pub fn new(
+        config_manager: Arc<dyn MatcherConfigManager>,
+        constants: HashMap<String, Value>,
+        matcher: SharedMatcher,
+    ) -> MatcherReloader {
+        MatcherReloader { config_manager, constants, matcher, reload_lock: Mutex::new(()) }
+    }
+
+    /// Reads the configuration, builds a new Matcher and, if successful, replaces the active one.
+    /// If the configuration cannot be read or is not valid, the active Matcher is kept
+    /// and the error is returned.
+    pub fn reload(&self) -> Result<MatcherConfig, MatcherError> {
+        let _guard = self.reload_lock.lock().expect("The reload lock should not be poisoned");
+        info!("Reloading the Matcher configuration");
+        let result = self.config_manager.read().and_then(|config| {
+            Matcher::build_with_constants(&config, self.constants.clone()).map(|matcher| {
+                self.matcher.replace(matcher);
+                config
+            })
+        });
+        match &result {
+            Ok(_) => info!("Matcher configuration reloaded"),
+            Err(err) => {
+                error!(
+                    "Cannot reload the Matcher configuration. The current one is kept. Err: {}",
+                    err
+                )
+            }
+        };
+        result
+    }
+}
+
+/// Reloads the Matcher configuration every time the process receives a SIGHUP signal.
+/// It must be called from within a running actix System.
+/// The reload is executed in the actix thread pool, so that it does not block the event loop.
+pub fn reload_on_sighup(reloader: Arc<MatcherReloader>) {
+    let sighup = tokio_signal::unix::Signal::new(tokio_signal::unix::SIGHUP)
+        .flatten_stream()
+        .for_each(move |_| {
+            info!("Received SIGHUP signal");
+            let reloader = reloader.clone();
+            // The error is already logged by the reloader
+            actix_web::web::block(move || reloader.reload()).then(|_| Ok(()))
+        })
+        .map_err(|err| error!("Cannot listen for the SIGHUP signal. Err: {}", err));
+    actix::Arbiter::spawn(sighup);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+    use tornado_common_api::Event;
+    use tornado_engine_matcher::config::rule::{Constraint, Rule};
+    use tornado_engine_matcher::config::DraftEdit;
+
+    struct TestConfigManager {
+        config: RwLock<Option<MatcherConfig>>,
+        running_reads: Mutex<usize>,
+        max_running_reads: Mutex<usize>,
+    }
+
+    impl TestConfigManager {
+        fn new(config: Option<MatcherConfig>) -> TestConfigManager {
+            TestConfigManager {
+                config: RwLock::new(config),
+                running_reads: Mutex::new(0),
+                max_running_reads: Mutex::new(0),
+            }
+        }
+    }
+
+    impl MatcherConfigManager for TestConfigManager {
+        fn read(&self) -> Result<MatcherConfig, MatcherError> {
+            {
+                let mut running_reads = self.running_reads.lock().unwrap();
+                *running_reads += 1;
+                let mut max_running_reads = self.max_running_reads.lock().unwrap();
+                *max_running_reads = (*max_running_reads).max(*running_reads);
+            }
+            thread::sleep(Duration::from_millis(10));
+            *self.running_reads.lock().unwrap() -= 1;
+            self.config.read().unwrap().clone().ok_or_else(|| MatcherError::ConfigurationError {
+                message: "Cannot read the configuration".to_owned(),
+            })
+        }
+
+        fn get_draft(&self) -> Result<MatcherConfig, MatcherError> {
+            not_supported()
+        }
+
+        fn update_draft(&self, _config: &MatcherConfig) -> Result<(), MatcherError> {
+            not_supported()
+        }
+
+        fn edit_draft(&self, _edit: DraftEdit<'_>) -> Result<MatcherConfig, MatcherError> {
+            not_supported()
+        }
+
+        fn delete_draft(&self) -> Result<(), MatcherError> {
+            not_supported()
+        }
+
+        fn deploy_draft(&self) -> Result<MatcherConfig, MatcherError> {
+            not_supported()
+        }
+
+        fn rollback(&self) -> Result<MatcherConfig, MatcherError> {
+            not_supported()
+        }
+    }
+
+    // The draft operations are not used by the reloader
+    fn not_supported<T>() -> Result<T, MatcherError> {
+        Err(MatcherError::ConfigurationError {
+            message: "The TestConfigManager does not support the drafts".to_owned(),
+        })
+    }
+
+    fn ruleset(rule_name: &str) -> MatcherConfig {
+        MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![Rule {
+                name: rule_name.to_owned(),
+                description: "".to_owned(),
+                do_continue: true,
+                active: true,
+                constraint: Constraint { where_operator: None, with: HashMap::new() },
+                actions: vec![],
+                tests: vec![],
+            }],
+        }
+    }
+
+    fn rule_names(matcher: &Matcher) -> Vec<String> {
+        match matcher.process(Event::new("test_type")).result {
+            tornado_engine_matcher::model::ProcessedNode::Ruleset { rules, .. } => {
+                rules.rules.into_iter().map(|rule| rule.name).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn should_replace_the_matcher_with_the_reloaded_one() {
+        // Arrange
+        let shared_matcher = SharedMatcher::new(Matcher::build(&ruleset("first")).unwrap());
+        let config_manager = Arc::new(TestConfigManager::new(Some(ruleset("second"))));
+        let reloader = MatcherReloader::new(config_manager, HashMap::new(), shared_matcher.clone());
+        let matcher_before_reload = shared_matcher.get();
+
+        // Act
+        let result = reloader.reload();
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(vec!["second".to_owned()], rule_names(&shared_matcher.get()));
+        assert_eq!(vec!["first".to_owned()], rule_names(&matcher_before_reload));
+    }
+
+    #[test]
+    fn should_keep_the_current_matcher_if_the_config_is_not_valid() {
+        // Arrange
+        let shared_matcher = SharedMatcher::new(Matcher::build(&ruleset("first")).unwrap());
+        let config_manager = Arc::new(TestConfigManager::new(Some(ruleset("invalid name"))));
+        let reloader =
+            MatcherReloader::new(config_manager.clone(), HashMap::new(), shared_matcher.clone());
+
+        // Act
+        let invalid_result = reloader.reload();
+        *config_manager.config.write().unwrap() = None;
+        let unreadable_result = reloader.reload();
+
+        // Assert
+        assert!(invalid_result.is_err());
+        assert!(unreadable_result.is_err());
+        assert_eq!(vec!["first".to_owned()], rule_names(&shared_matcher.get()));
+    }
+
+    #[test]
+    fn should_execute_concurrent_reloads_one_at_a_time() {
+        // Arrange
+        let shared_matcher = SharedMatcher::new(Matcher::build(&ruleset("first")).unwrap());
+        let config_manager = Arc::new(TestConfigManager::new(Some(ruleset("second"))));
+        let reloader = Arc::new(MatcherReloader::new(
+            config_manager.clone(),
+            HashMap::new(),
+            shared_matcher.clone(),
+        ));
+
+        // Act
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let reloader = reloader.clone();
+                thread::spawn(move || reloader.reload())
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        // Assert
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(1, *config_manager.max_running_reads.lock().unwrap());
+        assert_eq!(vec!["second".to_owned()], rule_names(&shared_matcher.get()));
+    }
+}
@@ -1,5 +1,6 @@
 use self::handler::ApiHandler;
 use self::http::HttpHandler;
+use crate::error::ApiError;
 use actix_web::{web, Scope};
 use std::sync::Arc;
 
@@ -9,11 +10,70 @@ mod http;
 pub fn new_endpoints<T: ApiHandler + 'static>(mut scope: Scope, api_handler: Arc<T>) -> Scope {
     let http = HttpHandler { api_handler };
 
+    // The request bodies that cannot be deserialized are reported as JSON ApiErrorDtos
+    scope = scope.data(web::JsonConfig::default().error_handler(|err, _req| {
+        ApiError::InvalidRequestError { message: format!("{}", err) }.into()
+    }));
+
     let http_clone = http.clone();
     scope = scope.service(
         web::resource("/config").route(web::get().to_async(move |req| http_clone.get_config(req))),
     );
 
+    let http_clone = http.clone();
+    scope = scope.service(
+        web::resource("/config/reload")
+            .route(web::post().to_async(move |req| http_clone.reload_config(req))),
+    );
+
+    let (get_clone, put_clone, delete_clone) = (http.clone(), http.clone(), http.clone());
+    scope = scope.service(
+        web::resource("/config/draft")
+            .route(web::get().to_async(move |req| get_clone.get_draft(req)))
+            .route(web::put().to_async(move |req, body| put_clone.update_draft(req, body)))
+            .route(web::delete().to_async(move |req| delete_clone.delete_draft(req))),
+    );
+
+    let http_clone = http.clone();
+    scope = scope.service(
+        web::resource("/config/draft/deploy")
+            .route(web::post().to_async(move |req| http_clone.deploy_draft(req))),
+    );
+
+    let http_clone = http.clone();
+    scope = scope.service(
+        web::resource("/config/rollback")
+            .route(web::post().to_async(move |req| http_clone.rollback_config(req))),
+    );
+
+    let (post_clone, put_clone, delete_clone) = (http.clone(), http.clone(), http.clone());
+    scope = scope.service(
+        web::resource("/config/draft/nodes/{path:.*}")
+            .route(web::post().to_async(move |req, body| post_clone.create_node(req, body)))
+            .route(web::put().to_async(move |req, body| put_clone.update_node(req, body)))
+            .route(web::delete().to_async(move |req| delete_clone.delete_node(req))),
+    );
+
+    let (post_clone, put_clone, delete_clone) = (http.clone(), http.clone(), http.clone());
+    scope = scope.service(
+        web::resource("/config/draft/rules/{path:.*}")
+            .route(web::post().to_async(move |req, body| post_clone.create_rule(req, body)))
+            .route(web::put().to_async(move |req, body| put_clone.update_rule(req, body)))
+            .route(web::delete().to_async(move |req| delete_clone.delete_rule(req))),
+    );
+
+    let http_clone = http.clone();
+    scope = scope.service(
+        web::resource("/config/draft/rules_order/{path:.*}")
+            .route(web::put().to_async(move |req, body| http_clone.reorder_rules(req, body))),
+    );
+
+    let http_clone = http.clone();
+    scope = scope.service(
+        web::resource("/config/draft/active/{path:.*}")
+            .route(web::put().to_async(move |req, body| http_clone.set_active(req, body))),
+    );
+
     let http_clone = http.clone();
     scope = scope.service(
         web::resource("/send_event")
@@ -26,16 +86,20 @@ pub fn new_endpoints<T: ApiHandler + 'static>(mut scope: Scope, api_handler: Arc
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::api::handler::SendEventRequest;
-    use crate::error::ApiError;
+    use crate::api::handler::{DraftEdit, SendEventRequest};
     use actix_web::{
         http::{header, StatusCode},
         test, App,
     };
     use futures::{future::FutureResult, Future};
     use std::collections::HashMap;
+    use tornado_engine_api_dto::config::{
+        ActiveStatusDto, ConstraintDto, MatcherConfigDto, RuleDto,
+    };
+    use tornado_engine_api_dto::error::ApiErrorDto;
     use tornado_engine_api_dto::event::{EventDto, ProcessType, SendEventRequestDto};
     use tornado_engine_matcher::config::MatcherConfig;
+    use tornado_engine_matcher::error::MatcherError;
     use tornado_engine_matcher::model::{ProcessedEvent, ProcessedNode, ProcessedRules};
 
     struct TestApiHandler {}
@@ -48,6 +112,48 @@ mod test {
             })))
         }
 
+        fn reload_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            Box::new(FutureResult::from(Ok(MatcherConfig::Ruleset {
+                name: "reloaded_ruleset".to_owned(),
+                rules: vec![],
+            })))
+        }
+
+        fn get_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            Box::new(FutureResult::from(Ok(MatcherConfig::Ruleset {
+                name: "draft".to_owned(),
+                rules: vec![],
+            })))
+        }
+
+        fn update_draft(
+            &self,
+            config: MatcherConfig,
+        ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            Box::new(FutureResult::from(Ok(config)))
+        }
+
+        fn edit_draft(
+            &self,
+            edit: DraftEdit,
+        ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            Box::new(self.get_draft().and_then(|mut draft| edit(&mut draft).map(|_| draft)))
+        }
+
+        fn delete_draft(&self) -> Box<dyn Future<Item = (), Error = ApiError>> {
+            Box::new(FutureResult::from(Ok(())))
+        }
+
+        fn deploy_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            Box::new(FutureResult::from(Err(ApiError::MatcherError {
+                cause: MatcherError::NotUniqueRuleNameError { name: "rule".to_owned() },
+            })))
+        }
+
+        fn rollback_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>> {
+            self.get_config()
+        }
+
         fn send_event(
             &self,
             event: SendEventRequest,
@@ -108,6 +214,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_return_the_reloaded_matcher_config() {
+        // Arrange
+        let mut srv = test::init_service(
+            App::new().service(new_endpoints(web::scope("/api"), Arc::new(TestApiHandler {}))),
+        );
+
+        // Act
+        let request = test::TestRequest::post().uri("/api/config/reload").to_request();
+
+        // Assert
+        let dto: tornado_engine_api_dto::config::MatcherConfigDto =
+            test::read_response_json(&mut srv, request);
+        assert_eq!(
+            tornado_engine_api_dto::config::MatcherConfigDto::Ruleset {
+                name: "reloaded_ruleset".to_owned(),
+                rules: vec![]
+            },
+            dto
+        );
+    }
+
     #[test]
     fn should_return_the_processed_event() {
         // Arrange
@@ -136,4 +264,102 @@ mod test {
             test::read_response_json(&mut srv, request);
         assert_eq!("my_test_event", dto.event.event_type);
     }
+
+    fn new_rule_dto(name: &str) -> RuleDto {
+        RuleDto {
+            name: name.to_owned(),
+            description: "".to_owned(),
+            do_continue: true,
+            active: true,
+            constraint: ConstraintDto { where_operator: None, with: HashMap::new() },
+            actions: vec![],
+            tests: vec![],
+        }
+    }
+
+    #[test]
+    fn should_add_a_rule_to_the_draft() {
+        // Arrange
+        let mut srv = test::init_service(
+            App::new().service(new_endpoints(web::scope("/api"), Arc::new(TestApiHandler {}))),
+        );
+
+        // Act
+        let request = test::TestRequest::post()
+            .uri("/api/config/draft/rules/")
+            .header(header::CONTENT_TYPE, "application/json")
+            .set_payload(serde_json::to_string(&new_rule_dto("new_rule")).unwrap())
+            .to_request();
+
+        // Assert
+        let dto: MatcherConfigDto = test::read_response_json(&mut srv, request);
+        assert_eq!(
+            MatcherConfigDto::Ruleset {
+                name: "draft".to_owned(),
+                rules: vec![new_rule_dto("new_rule")]
+            },
+            dto
+        );
+    }
+
+    #[test]
+    fn should_return_not_found_if_the_rule_does_not_exist() {
+        // Arrange
+        let mut srv = test::init_service(
+            App::new().service(new_endpoints(web::scope("/api"), Arc::new(TestApiHandler {}))),
+        );
+
+        // Act
+        let request = test::TestRequest::put()
+            .uri("/api/config/draft/active/missing_rule")
+            .header(header::CONTENT_TYPE, "application/json")
+            .set_payload(serde_json::to_string(&ActiveStatusDto { active: false }).unwrap())
+            .to_request();
+        let response = test::call_service(&mut srv, request);
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let dto: ApiErrorDto = serde_json::from_slice(&test::read_body(response)).unwrap();
+        assert_eq!("NotFound", dto.code);
+        assert_eq!("Rule [/missing_rule] not found", dto.message);
+    }
+
+    #[test]
+    fn should_return_the_validation_error_as_json() {
+        // Arrange
+        let mut srv = test::init_service(
+            App::new().service(new_endpoints(web::scope("/api"), Arc::new(TestApiHandler {}))),
+        );
+
+        // Act
+        let request = test::TestRequest::post().uri("/api/config/draft/deploy").to_request();
+        let response = test::call_service(&mut srv, request);
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let dto: ApiErrorDto = serde_json::from_slice(&test::read_body(response)).unwrap();
+        assert_eq!("MatcherError", dto.code);
+        assert!(dto.message.contains("NotUniqueRuleNameError"));
+    }
+
+    #[test]
+    fn should_return_an_invalid_request_error_if_the_body_is_not_valid() {
+        // Arrange
+        let mut srv = test::init_service(
+            App::new().service(new_endpoints(web::scope("/api"), Arc::new(TestApiHandler {}))),
+        );
+
+        // Act
+        let request = test::TestRequest::post()
+            .uri("/api/config/draft/rules/")
+            .header(header::CONTENT_TYPE, "application/json")
+            .set_payload(r#"{ "name": "new_rule" }"#)
+            .to_request();
+        let response = test::call_service(&mut srv, request);
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let dto: ApiErrorDto = serde_json::from_slice(&test::read_body(response)).unwrap();
+        assert_eq!("InvalidRequest", dto.code);
+    }
 }
@@ -9,12 +9,37 @@ use tornado_engine_matcher::model::ProcessedEvent;
 /// It permits to decouple the backend from a specific implementation.
 pub trait ApiHandler {
     fn get_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Rebuilds the processing tree from the current configuration and replaces the active one.
+    /// If the configuration is not valid, the active processing tree is kept.
+    fn reload_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Returns the draft configuration.
+    fn get_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Validates and saves the draft configuration. It returns the saved draft.
+    fn update_draft(
+        &self,
+        config: MatcherConfig,
+    ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Applies the edit to the draft configuration, then validates and saves it.
+    /// No other change of the draft can happen in the meantime. It returns the saved draft.
+    fn edit_draft(
+        &self,
+        edit: DraftEdit,
+    ) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Discards the draft configuration.
+    fn delete_draft(&self) -> Box<dyn Future<Item = (), Error = ApiError>>;
+    /// Deploys the draft configuration and makes it the active one.
+    fn deploy_draft(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
+    /// Restores the previously deployed configuration and makes it the active one.
+    fn rollback_config(&self) -> Box<dyn Future<Item = MatcherConfig, Error = ApiError>>;
     fn send_event(
         &self,
         event: SendEventRequest,
     ) -> Box<dyn Future<Item = ProcessedEvent, Error = ApiError>>;
 }
 
+/// A change applied to the draft by ApiHandler::edit_draft.
+pub type DraftEdit = Box<dyn FnOnce(&mut MatcherConfig) -> Result<(), ApiError>>;
+
 pub struct SendEventRequest {
     pub event: Event,
     pub process_type: ProcessType,
@@ -1,6 +1,7 @@
 use crate::api::handler::ApiHandler;
-use crate::convert::config::matcher_config_into_dto;
+use crate::convert::config::{dto_into_matcher_config, dto_into_rule, matcher_config_into_dto};
 use crate::convert::event::{dto_into_send_event_request, processed_event_into_dto};
+use crate::editor;
 use crate::error::ApiError;
 use actix_web::web::Json;
 use actix_web::{Error as AWError, HttpRequest, HttpResponse};
@@ -9,7 +10,9 @@ use futures::Future;
 use log::*;
 use std::ops::Deref;
 use std::sync::Arc;
+use tornado_engine_api_dto::config::{ActiveStatusDto, MatcherConfigDto, RuleDto, RulesOrderDto};
 use tornado_engine_api_dto::event::SendEventRequestDto;
+use tornado_engine_matcher::config::MatcherConfig;
 
 /// The HttpHandler wraps an ApiHandler hiding the low level HTTP Request details
 /// and handling the DTOs conversions.
@@ -29,17 +32,163 @@ impl<T: ApiHandler> HttpHandler<T> {
         _req: HttpRequest,
     ) -> impl Future<Item = HttpResponse, Error = AWError> {
         debug!("API - received get_config request");
-        self.api_handler.get_config().map_err(AWError::from).and_then(|matcher_config| {
-            match matcher_config_into_dto(matcher_config) {
-                Ok(dto) => HttpResponse::Ok().json(dto),
-                Err(err) => {
-                    error!("Cannot convert the MatcherConfig into a DTO. Err: {}", err);
-                    HttpResponse::InternalServerError().finish()
-                }
-            }
+        self.api_handler.get_config().map_err(AWError::from).and_then(matcher_config_response)
+    }
+
+    pub fn reload_config(
+        &self,
+        _req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received reload_config request");
+        self.api_handler.reload_config().map_err(AWError::from).and_then(matcher_config_response)
+    }
+
+    pub fn get_draft(
+        &self,
+        _req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received get_draft request");
+        self.api_handler.get_draft().map_err(AWError::from).and_then(matcher_config_response)
+    }
+
+    pub fn update_draft(
+        &self,
+        _req: HttpRequest,
+        body: Json<MatcherConfigDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received update_draft request");
+        let api_handler = self.api_handler.clone();
+        FutureResult::from(dto_into_matcher_config(body.into_inner()))
+            .map_err(ApiError::from)
+            .and_then(move |config| api_handler.update_draft(config))
+            .map_err(AWError::from)
+            .and_then(matcher_config_response)
+    }
+
+    pub fn delete_draft(
+        &self,
+        _req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received delete_draft request");
+        self.api_handler.delete_draft().map_err(AWError::from).map(|_| HttpResponse::Ok().finish())
+    }
+
+    pub fn deploy_draft(
+        &self,
+        _req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received deploy_draft request");
+        self.api_handler.deploy_draft().map_err(AWError::from).and_then(matcher_config_response)
+    }
+
+    pub fn rollback_config(
+        &self,
+        _req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        debug!("API - received rollback_config request");
+        self.api_handler.rollback_config().map_err(AWError::from).and_then(matcher_config_response)
+    }
+
+    pub fn create_node(
+        &self,
+        req: HttpRequest,
+        body: Json<MatcherConfigDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received create_node request for path {:?}", path);
+        let node = body.into_inner();
+        self.edit_draft(move |config| {
+            editor::create_node(config, &path, dto_into_matcher_config(node)?)
         })
     }
 
+    pub fn update_node(
+        &self,
+        req: HttpRequest,
+        body: Json<MatcherConfigDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received update_node request for path {:?}", path);
+        let node = body.into_inner();
+        self.edit_draft(move |config| {
+            editor::update_node(config, &path, dto_into_matcher_config(node)?)
+        })
+    }
+
+    pub fn delete_node(
+        &self,
+        req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received delete_node request for path {:?}", path);
+        self.edit_draft(move |config| editor::delete_node(config, &path))
+    }
+
+    pub fn create_rule(
+        &self,
+        req: HttpRequest,
+        body: Json<RuleDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received create_rule request for path {:?}", path);
+        let rule = body.into_inner();
+        self.edit_draft(move |config| editor::create_rule(config, &path, dto_into_rule(rule)?))
+    }
+
+    pub fn update_rule(
+        &self,
+        req: HttpRequest,
+        body: Json<RuleDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received update_rule request for path {:?}", path);
+        let rule = body.into_inner();
+        self.edit_draft(move |config| editor::update_rule(config, &path, dto_into_rule(rule)?))
+    }
+
+    pub fn delete_rule(
+        &self,
+        req: HttpRequest,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received delete_rule request for path {:?}", path);
+        self.edit_draft(move |config| editor::delete_rule(config, &path))
+    }
+
+    pub fn reorder_rules(
+        &self,
+        req: HttpRequest,
+        body: Json<RulesOrderDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received reorder_rules request for path {:?}", path);
+        let order = body.into_inner();
+        self.edit_draft(move |config| editor::reorder_rules(config, &path, &order.rules))
+    }
+
+    pub fn set_active(
+        &self,
+        req: HttpRequest,
+        body: Json<ActiveStatusDto>,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        let path = tree_path(&req);
+        debug!("API - received set_active request for path {:?}", path);
+        let status = body.into_inner();
+        self.edit_draft(move |config| editor::set_active(config, &path, status.active))
+    }
+
+    // Applies the edit to the current draft and saves it.
+    // The response contains the updated draft.
+    fn edit_draft<F: FnOnce(&mut MatcherConfig) -> Result<(), ApiError> + 'static>(
+        &self,
+        edit: F,
+    ) -> impl Future<Item = HttpResponse, Error = AWError> {
+        self.api_handler
+            .edit_draft(Box::new(edit))
+            .map_err(AWError::from)
+            .and_then(matcher_config_response)
+    }
+
     pub fn send_event(
         &self,
         _req: HttpRequest,
@@ -68,3 +217,23 @@ impl<T: ApiHandler> HttpHandler<T> {
             })
     }
 }
+
+fn matcher_config_response(matcher_config: MatcherConfig) -> HttpResponse {
+    match matcher_config_into_dto(matcher_config) {
+        Ok(dto) => HttpResponse::Ok().json(dto),
+        Err(err) => {
+            error!("Cannot convert the MatcherConfig into a DTO. Err: {}", err);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+// Returns the node names of the processing tree path matched by the '{path:.*}' segment.
+fn tree_path(req: &HttpRequest) -> Vec<String> {
+    req.match_info()
+        .query("path")
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
@@ -1,11 +1,14 @@
 use serde_json::Error;
+use std::collections::HashMap;
 use tornado_engine_api_dto::config::{
-    ActionDto, ConstraintDto, ExtractorDto, ExtractorRegexDto, FilterDto, MatcherConfigDto,
-    OperatorDto, RuleDto,
+    ActionDto, ConstraintDto, ExtractorDto, ExtractorJmespathDto, ExtractorJsonDto,
+    ExtractorKeyValueDto, ExtractorRegexDto, FilterDto, MatcherConfigDto, ModifierDto, OperatorDto,
+    RuleDto, RuleTestDto, RuleTestExpectationDto, ValueTypeDto,
 };
 use tornado_engine_matcher::config::filter::Filter;
 use tornado_engine_matcher::config::rule::{
-    Action, Constraint, Extractor, ExtractorRegex, Operator, Rule,
+    Action, Constraint, Extractor, ExtractorKind, ExtractorRegex, Modifier, Operator, Rule,
+    RuleTest, ValueType,
 };
 use tornado_engine_matcher::config::MatcherConfig;
 
@@ -23,6 +26,28 @@ pub fn matcher_config_into_dto(config: MatcherConfig) -> Result<MatcherConfigDto
     })
 }
 
+pub fn dto_into_matcher_config(dto: MatcherConfigDto) -> Result<MatcherConfig, Error> {
+    Ok(match dto {
+        MatcherConfigDto::Ruleset { name, rules } => MatcherConfig::Ruleset {
+            name,
+            rules: rules.into_iter().map(dto_into_rule).collect::<Result<Vec<_>, _>>()?,
+        },
+        MatcherConfigDto::Filter { name, filter, nodes } => MatcherConfig::Filter {
+            name,
+            filter: dto_into_filter(filter)?,
+            nodes: nodes.into_iter().map(dto_into_matcher_config).collect::<Result<Vec<_>, _>>()?,
+        },
+    })
+}
+
+pub fn dto_into_filter(dto: FilterDto) -> Result<Filter, Error> {
+    serde_json::from_value(serde_json::to_value(dto)?)
+}
+
+pub fn dto_into_rule(dto: RuleDto) -> Result<Rule, Error> {
+    serde_json::from_value(serde_json::to_value(dto)?)
+}
+
 pub fn filter_into_dto(filter: Filter) -> Result<FilterDto, Error> {
     Ok(FilterDto {
         description: filter.description,
@@ -39,11 +64,47 @@ pub fn rule_into_dto(rule: Rule) -> Result<RuleDto, Error> {
         description: rule.description,
         do_continue: rule.do_continue,
         name: rule.name,
+        tests: rule.tests.into_iter().map(rule_test_into_dto).collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+pub fn rule_test_into_dto(rule_test: RuleTest) -> Result<RuleTestDto, Error> {
+    Ok(RuleTestDto {
+        description: rule_test.description,
+        event: serde_json::from_value(serde_json::to_value(rule_test.event)?)?,
+        expected: RuleTestExpectationDto {
+            matched: rule_test.expected.matched,
+            extracted_vars: rule_test
+                .expected
+                .extracted_vars
+                .map(|extracted_vars| {
+                    extracted_vars
+                        .into_iter()
+                        .map(|(key, value)| Ok((key, serde_json::to_value(value)?)))
+                        .collect::<Result<HashMap<_, _>, Error>>()
+                })
+                .transpose()?,
+            actions: rule_test
+                .expected
+                .actions
+                .map(|actions| {
+                    actions
+                        .into_iter()
+                        .map(crate::convert::event::action_into_dto)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+        },
     })
 }
 
 pub fn action_into_dto(action: Action) -> Result<ActionDto, Error> {
-    Ok(ActionDto { id: action.id, payload: serde_json::to_value(action.payload)? })
+    Ok(ActionDto {
+        id: action.id,
+        payload: serde_json::to_value(action.payload)?,
+        when: action.when.map(operator_into_dto).transpose()?,
+        template: action.template,
+    })
 }
 
 pub fn constraint_into_dto(constraint: Constraint) -> Result<ConstraintDto, Error> {
@@ -52,8 +113,8 @@ pub fn constraint_into_dto(constraint: Constraint) -> Result<ConstraintDto, Erro
         with: constraint
             .with
             .into_iter()
-            .map(|(key, value)| (key, extractor_into_dto(value)))
-            .collect(),
+            .map(|(key, value)| Ok((key, extractor_into_dto(value)?)))
+            .collect::<Result<HashMap<_, _>, Error>>()?,
     })
 }
 
@@ -71,14 +132,52 @@ pub fn operator_into_dto(operator: Operator) -> Result<OperatorDto, Error> {
                 .map(operator_into_dto)
                 .collect::<Result<Vec<_>, _>>()?,
         },
+        Operator::Not { operator } => {
+            OperatorDto::Not { operator: Box::new(operator_into_dto(*operator)?) }
+        }
+        Operator::Any { target, operator } => {
+            OperatorDto::Any { target, operator: Box::new(operator_into_dto(*operator)?) }
+        }
+        Operator::All { target, operator } => {
+            OperatorDto::All { target, operator: Box::new(operator_into_dto(*operator)?) }
+        }
+        Operator::Between { target, min, max, exclusive_min, exclusive_max } => {
+            OperatorDto::Between {
+                target,
+                min: serde_json::to_value(&min)?,
+                max: serde_json::to_value(&max)?,
+                exclusive_min,
+                exclusive_max,
+            }
+        }
         Operator::Contain { first, second } => OperatorDto::Contain {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
         },
+        Operator::ContainsIgnoreCase { first, second } => OperatorDto::ContainsIgnoreCase {
+            first: serde_json::to_value(&first)?,
+            second: serde_json::to_value(&second)?,
+        },
+        Operator::Custom { name, args } => OperatorDto::Custom {
+            name,
+            args: args
+                .into_iter()
+                .map(|(key, value)| Ok((key, serde_json::to_value(&value)?)))
+                .collect::<Result<HashMap<_, _>, Error>>()?,
+        },
+        Operator::EndsWith { first, second } => OperatorDto::EndsWith {
+            first: serde_json::to_value(&first)?,
+            second: serde_json::to_value(&second)?,
+        },
+        Operator::Exists { target } => OperatorDto::Exists { target },
         Operator::Equal { first, second } => OperatorDto::Equal {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
         },
+        Operator::EqualsIgnoreCase { first, second } => OperatorDto::EqualsIgnoreCase {
+            first: serde_json::to_value(&first)?,
+            second: serde_json::to_value(&second)?,
+        },
         Operator::GreaterEqualThan { first, second } => OperatorDto::GreaterEqualThan {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
@@ -87,26 +186,145 @@ pub fn operator_into_dto(operator: Operator) -> Result<OperatorDto, Error> {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
         },
+        Operator::In { first, second } => OperatorDto::In {
+            first: serde_json::to_value(&first)?,
+            second: serde_json::to_value(&second)?,
+        },
+        Operator::IsNull { target } => OperatorDto::IsNull { target },
+        Operator::IsType { target, value_type } => {
+            OperatorDto::IsType { target, value_type: value_type_into_dto(value_type) }
+        }
+        Operator::IpInRange { ranges, target } => OperatorDto::IpInRange { ranges, target },
         Operator::LessEqualThan { first, second } => OperatorDto::LessEqualThan {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
         },
+        Operator::Length { target, min, max, exclusive_min, exclusive_max } => {
+            OperatorDto::Length {
+                target,
+                min: min.map(|min| serde_json::to_value(&min)).transpose()?,
+                max: max.map(|max| serde_json::to_value(&max)).transpose()?,
+                exclusive_min,
+                exclusive_max,
+            }
+        }
         Operator::LessThan { first, second } => OperatorDto::LessThan {
             first: serde_json::to_value(&first)?,
             second: serde_json::to_value(&second)?,
         },
-        Operator::Regex { regex, target } => OperatorDto::Regex { regex, target },
+        Operator::Regex { regex, target, flags } => OperatorDto::Regex { regex, target, flags },
+        Operator::RegexAny { regexes, target, flags, variable } => {
+            OperatorDto::RegexAny { regexes, target, flags, variable }
+        }
+        Operator::TimeWindow { timezone, days, from, to } => {
+            OperatorDto::TimeWindow { timezone, days, from, to }
+        }
+        Operator::StartsWith { first, second } => OperatorDto::StartsWith {
+            first: serde_json::to_value(&first)?,
+            second: serde_json::to_value(&second)?,
+        },
     };
     Ok(result)
 }
 
-pub fn extractor_into_dto(extractor: Extractor) -> ExtractorDto {
-    ExtractorDto { from: extractor.from, regex: extractor_regex_into_dto(extractor.regex) }
+pub fn value_type_into_dto(value_type: ValueType) -> ValueTypeDto {
+    match value_type {
+        ValueType::Text => ValueTypeDto::Text,
+        ValueType::Number => ValueTypeDto::Number,
+        ValueType::Bool => ValueTypeDto::Bool,
+        ValueType::Array => ValueTypeDto::Array,
+        ValueType::Map => ValueTypeDto::Map,
+    }
+}
+
+pub fn extractor_into_dto(extractor: Extractor) -> Result<ExtractorDto, Error> {
+    let mut dto = ExtractorDto {
+        from: extractor.from,
+        regex: None,
+        json: None,
+        key_value: None,
+        jmespath: None,
+        modifiers_post: extractor
+            .modifiers_post
+            .into_iter()
+            .map(modifier_into_dto)
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    match extractor.kind {
+        ExtractorKind::Regex(regex) => dto.regex = Some(extractor_regex_into_dto(regex)),
+        ExtractorKind::Json(_) => dto.json = Some(ExtractorJsonDto {}),
+        ExtractorKind::KeyValue(key_value) => {
+            dto.key_value = Some(ExtractorKeyValueDto {
+                pair_separator: key_value.pair_separator,
+                key_value_separator: key_value.key_value_separator,
+            })
+        }
+        ExtractorKind::Jmespath(jmespath) => {
+            dto.jmespath = Some(ExtractorJmespathDto { expression: jmespath.expression })
+        }
+    }
+    Ok(dto)
+}
+
+pub fn modifier_into_dto(modifier: Modifier) -> Result<ModifierDto, Error> {
+    Ok(match modifier {
+        Modifier::Trim => ModifierDto::Trim,
+        Modifier::Lowercase => ModifierDto::Lowercase,
+        Modifier::Uppercase => ModifierDto::Uppercase,
+        Modifier::ToNumber => ModifierDto::ToNumber,
+        Modifier::Replace { find, replace } => ModifierDto::Replace { find, replace },
+        Modifier::Default { value } => {
+            ModifierDto::Default { value: serde_json::to_value(&value)? }
+        }
+    })
 }
 
 pub fn extractor_regex_into_dto(extractor_regex: ExtractorRegex) -> ExtractorRegexDto {
     ExtractorRegexDto {
         group_match_idx: extractor_regex.group_match_idx,
+        all_matches: extractor_regex.all_matches,
         regex: extractor_regex.regex,
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn should_keep_the_rule_tests_in_the_dto_roundtrip() {
+        // Arrange
+        let rule = Rule::from_json(
+            r#"{
+                "description": "",
+                "continue": true,
+                "active": true,
+                "constraint": { "WHERE": null, "WITH": {} },
+                "actions": [],
+                "tests": [
+                    {
+                        "description": "an email",
+                        "event": { "type": "email", "created_ms": 10, "payload": { "body": "hi" } },
+                        "expected": {
+                            "matched": true,
+                            "extracted_vars": { "temperature": "25" },
+                            "actions": [ { "id": "logger", "payload": { "body": "hi" } } ]
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let expected = serde_json::to_value(&rule).unwrap();
+
+        // Act
+        let dto = rule_into_dto(rule).unwrap();
+        let result = dto_into_rule(dto.clone()).unwrap();
+
+        // Assert
+        assert_eq!(1, dto.tests.len());
+        assert_eq!("email", dto.tests[0].event.event_type);
+        assert_eq!(expected, serde_json::to_value(&result).unwrap());
+    }
+}
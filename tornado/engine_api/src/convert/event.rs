@@ -74,6 +74,7 @@ pub fn processed_rule_into_dto(node: ProcessedRule) -> Result<ProcessedRuleDto,
         message: node.message,
         name: node.name,
         actions: node.actions.into_iter().map(action_into_dto).collect::<Result<Vec<_>, _>>()?,
+        skipped_actions: node.skipped_actions,
         status: processed_rule_status_into_dto(node.status),
     })
 }
@@ -88,7 +89,12 @@ pub fn processed_rule_status_into_dto(node: ProcessedRuleStatus) -> ProcessedRul
 }
 
 pub fn action_into_dto(action: Action) -> Result<ActionDto, Error> {
-    Ok(ActionDto { id: action.id, payload: serde_json::to_value(action.payload)? })
+    Ok(ActionDto {
+        id: action.id,
+        payload: serde_json::to_value(action.payload)?,
+        when: None,
+        template: None,
+    })
 }
 
 pub fn processed_filter_into_dto(node: ProcessedFilter) -> ProcessedFilterDto {
@@ -0,0 +1,439 @@
+use crate::error::ApiError;
+use tornado_engine_matcher::config::rule::Rule;
+use tornado_engine_matcher::config::MatcherConfig;
+
+// Functions to edit single nodes and rules of a processing tree.
+// A node is identified by the path of node names from the root node, the root node excluded;
+// e.g. the path of the root node is [] and the path of its child "emails" is ["emails"].
+// A rule is identified by the path of its Ruleset followed by the rule name.
+
+/// Adds a node to the Filter identified by parent_path.
+pub fn create_node(
+    config: &mut MatcherConfig,
+    parent_path: &[String],
+    node: MatcherConfig,
+) -> Result<(), ApiError> {
+    match get_node_mut(config, parent_path)? {
+        MatcherConfig::Filter { nodes, .. } => {
+            if nodes.iter().any(|current| node_name(current) == node_name(&node)) {
+                return Err(ApiError::InvalidRequestError {
+                    message: format!(
+                        "A node named [{}] already exists in [{}]",
+                        node_name(&node),
+                        path_to_string(parent_path)
+                    ),
+                });
+            }
+            nodes.push(node);
+            Ok(())
+        }
+        MatcherConfig::Ruleset { .. } => Err(ApiError::InvalidRequestError {
+            message: format!(
+                "The node [{}] is a Ruleset and cannot contain other nodes",
+                path_to_string(parent_path)
+            ),
+        }),
+    }
+}
+
+/// Replaces the node identified by path.
+pub fn update_node(
+    config: &mut MatcherConfig,
+    path: &[String],
+    node: MatcherConfig,
+) -> Result<(), ApiError> {
+    match path.split_last() {
+        Some((name, parent_path)) => {
+            let nodes = get_filter_nodes_mut(config, parent_path)?;
+            if node_name(&node) != name
+                && nodes.iter().any(|current| node_name(current) == node_name(&node))
+            {
+                return Err(ApiError::InvalidRequestError {
+                    message: format!(
+                        "A node named [{}] already exists in [{}]",
+                        node_name(&node),
+                        path_to_string(parent_path)
+                    ),
+                });
+            }
+            let current = find_node_mut(nodes, name, path)?;
+            *current = node;
+        }
+        None => *config = node,
+    }
+    Ok(())
+}
+
+/// Removes the node identified by path. The root node cannot be removed.
+pub fn delete_node(config: &mut MatcherConfig, path: &[String]) -> Result<(), ApiError> {
+    match path.split_last() {
+        Some((name, parent_path)) => {
+            let nodes = get_filter_nodes_mut(config, parent_path)?;
+            let len = nodes.len();
+            nodes.retain(|node| node_name(node) != name);
+            if nodes.len() == len {
+                return Err(node_not_found(path));
+            }
+            Ok(())
+        }
+        None => Err(ApiError::InvalidRequestError {
+            message: "The root node cannot be deleted".to_owned(),
+        }),
+    }
+}
+
+/// Appends a rule to the Ruleset identified by ruleset_path.
+pub fn create_rule(
+    config: &mut MatcherConfig,
+    ruleset_path: &[String],
+    rule: Rule,
+) -> Result<(), ApiError> {
+    let rules = get_ruleset_rules_mut(config, ruleset_path)?;
+    if rules.iter().any(|current| current.name == rule.name) {
+        return Err(ApiError::InvalidRequestError {
+            message: format!(
+                "A rule named [{}] already exists in [{}]",
+                rule.name,
+                path_to_string(ruleset_path)
+            ),
+        });
+    }
+    rules.push(rule);
+    Ok(())
+}
+
+/// Replaces the rule identified by path.
+pub fn update_rule(
+    config: &mut MatcherConfig,
+    path: &[String],
+    rule: Rule,
+) -> Result<(), ApiError> {
+    let (name, ruleset_path) = split_rule_path(path)?;
+    let rules = get_ruleset_rules_mut(config, ruleset_path)?;
+    if &rule.name != name && rules.iter().any(|current| current.name == rule.name) {
+        return Err(ApiError::InvalidRequestError {
+            message: format!(
+                "A rule named [{}] already exists in [{}]",
+                rule.name,
+                path_to_string(ruleset_path)
+            ),
+        });
+    }
+    let current = rules
+        .iter_mut()
+        .find(|current| &current.name == name)
+        .ok_or_else(|| rule_not_found(path))?;
+    *current = rule;
+    Ok(())
+}
+
+/// Removes the rule identified by path.
+pub fn delete_rule(config: &mut MatcherConfig, path: &[String]) -> Result<(), ApiError> {
+    let (name, ruleset_path) = split_rule_path(path)?;
+    let rules = get_ruleset_rules_mut(config, ruleset_path)?;
+    let len = rules.len();
+    rules.retain(|rule| &rule.name != name);
+    if rules.len() == len {
+        return Err(rule_not_found(path));
+    }
+    Ok(())
+}
+
+/// Sorts the rules of the Ruleset identified by ruleset_path in the given order.
+/// The rule_names must contain the names of all the rules of the Ruleset.
+pub fn reorder_rules(
+    config: &mut MatcherConfig,
+    ruleset_path: &[String],
+    rule_names: &[String],
+) -> Result<(), ApiError> {
+    let rules = get_ruleset_rules_mut(config, ruleset_path)?;
+    let mut current_names: Vec<&String> = rules.iter().map(|rule| &rule.name).collect();
+    let mut new_names: Vec<&String> = rule_names.iter().collect();
+    current_names.sort();
+    new_names.sort();
+    if current_names != new_names {
+        return Err(ApiError::InvalidRequestError {
+            message: format!(
+                "The new order {:?} must contain exactly the rules of [{}]",
+                rule_names,
+                path_to_string(ruleset_path)
+            ),
+        });
+    }
+    rules.sort_by_key(|rule| rule_names.iter().position(|name| name == &rule.name));
+    Ok(())
+}
+
+/// Enables or disables the Rule or the Filter identified by path.
+pub fn set_active(
+    config: &mut MatcherConfig,
+    path: &[String],
+    active: bool,
+) -> Result<(), ApiError> {
+    if let Some((name, ruleset_path)) = path.split_last() {
+        if let MatcherConfig::Ruleset { rules, .. } = get_node_mut(config, ruleset_path)? {
+            let rule = rules
+                .iter_mut()
+                .find(|rule| &rule.name == name)
+                .ok_or_else(|| rule_not_found(path))?;
+            rule.active = active;
+            return Ok(());
+        }
+    }
+    match get_node_mut(config, path)? {
+        MatcherConfig::Filter { filter, .. } => {
+            filter.active = active;
+            Ok(())
+        }
+        MatcherConfig::Ruleset { .. } => Err(ApiError::InvalidRequestError {
+            message: format!(
+                "The node [{}] is a Ruleset and cannot be enabled or disabled",
+                path_to_string(path)
+            ),
+        }),
+    }
+}
+
+fn get_node_mut<'a>(
+    config: &'a mut MatcherConfig,
+    path: &[String],
+) -> Result<&'a mut MatcherConfig, ApiError> {
+    let mut node = config;
+    for name in path {
+        node = match node {
+            MatcherConfig::Filter { nodes, .. } => find_node_mut(nodes, name, path)?,
+            MatcherConfig::Ruleset { .. } => return Err(node_not_found(path)),
+        };
+    }
+    Ok(node)
+}
+
+fn get_filter_nodes_mut<'a>(
+    config: &'a mut MatcherConfig,
+    path: &[String],
+) -> Result<&'a mut Vec<MatcherConfig>, ApiError> {
+    match get_node_mut(config, path)? {
+        MatcherConfig::Filter { nodes, .. } => Ok(nodes),
+        MatcherConfig::Ruleset { .. } => Err(ApiError::InvalidRequestError {
+            message: format!("The node [{}] is not a Filter", path_to_string(path)),
+        }),
+    }
+}
+
+fn get_ruleset_rules_mut<'a>(
+    config: &'a mut MatcherConfig,
+    path: &[String],
+) -> Result<&'a mut Vec<Rule>, ApiError> {
+    match get_node_mut(config, path)? {
+        MatcherConfig::Ruleset { rules, .. } => Ok(rules),
+        MatcherConfig::Filter { .. } => Err(ApiError::InvalidRequestError {
+            message: format!("The node [{}] is not a Ruleset", path_to_string(path)),
+        }),
+    }
+}
+
+fn find_node_mut<'a>(
+    nodes: &'a mut [MatcherConfig],
+    name: &str,
+    path: &[String],
+) -> Result<&'a mut MatcherConfig, ApiError> {
+    nodes.iter_mut().find(|node| node_name(node) == name).ok_or_else(|| node_not_found(path))
+}
+
+fn split_rule_path(path: &[String]) -> Result<(&String, &[String]), ApiError> {
+    path.split_last().ok_or_else(|| ApiError::InvalidRequestError {
+        message: "The path of a rule must contain the rule name".to_owned(),
+    })
+}
+
+fn node_name(node: &MatcherConfig) -> &str {
+    match node {
+        MatcherConfig::Filter { name, .. } => name,
+        MatcherConfig::Ruleset { name, .. } => name,
+    }
+}
+
+fn path_to_string(path: &[String]) -> String {
+    format!("/{}", path.join("/"))
+}
+
+fn node_not_found(path: &[String]) -> ApiError {
+    ApiError::NotFoundError { message: format!("Node [{}] not found", path_to_string(path)) }
+}
+
+fn rule_not_found(path: &[String]) -> ApiError {
+    ApiError::NotFoundError { message: format!("Rule [{}] not found", path_to_string(path)) }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::collections::HashMap;
+    use tornado_engine_matcher::config::filter::Filter;
+    use tornado_engine_matcher::config::rule::Constraint;
+
+    fn new_rule(name: &str) -> Rule {
+        Rule {
+            name: name.to_owned(),
+            description: "".to_owned(),
+            do_continue: true,
+            active: true,
+            constraint: Constraint { where_operator: None, with: HashMap::new() },
+            actions: vec![],
+            tests: vec![],
+        }
+    }
+
+    fn new_filter(name: &str, nodes: Vec<MatcherConfig>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: name.to_owned(),
+            filter: Filter { description: "".to_owned(), active: true, filter: None },
+            nodes,
+        }
+    }
+
+    fn new_ruleset(name: &str, rule_names: &[&str]) -> MatcherConfig {
+        MatcherConfig::Ruleset {
+            name: name.to_owned(),
+            rules: rule_names.iter().map(|name| new_rule(name)).collect(),
+        }
+    }
+
+    fn path(path: &[&str]) -> Vec<String> {
+        path.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn tree() -> MatcherConfig {
+        new_filter(
+            "root",
+            vec![
+                new_ruleset("emails", &["rule_one", "rule_two", "rule_three"]),
+                new_filter("syslog", vec![new_ruleset("errors", &["rule_one"])]),
+            ],
+        )
+    }
+
+    fn rule_names(config: &mut MatcherConfig, ruleset_path: &[&str]) -> Vec<String> {
+        get_ruleset_rules_mut(config, &path(ruleset_path))
+            .unwrap()
+            .iter()
+            .map(|rule| rule.name.to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn should_create_a_node() {
+        let mut config = tree();
+
+        create_node(&mut config, &path(&["syslog"]), new_ruleset("warnings", &[])).unwrap();
+
+        assert!(get_node_mut(&mut config, &path(&["syslog", "warnings"])).is_ok());
+    }
+
+    #[test]
+    fn should_not_create_a_node_with_a_duplicated_name_or_in_a_ruleset() {
+        let mut config = tree();
+
+        assert!(create_node(&mut config, &path(&[]), new_ruleset("emails", &[])).is_err());
+        assert!(create_node(&mut config, &path(&["emails"]), new_ruleset("other", &[])).is_err());
+    }
+
+    #[test]
+    fn should_update_a_node() {
+        let mut config = tree();
+
+        update_node(&mut config, &path(&["syslog", "errors"]), new_ruleset("failures", &["one"]))
+            .unwrap();
+
+        assert_eq!(vec!["one".to_owned()], rule_names(&mut config, &["syslog", "failures"]));
+        assert!(get_node_mut(&mut config, &path(&["syslog", "errors"])).is_err());
+    }
+
+    #[test]
+    fn should_delete_a_node() {
+        let mut config = tree();
+
+        delete_node(&mut config, &path(&["emails"])).unwrap();
+
+        assert!(get_node_mut(&mut config, &path(&["emails"])).is_err());
+        assert_eq!(
+            Err(ApiError::NotFoundError { message: "Node [/emails] not found".to_owned() }),
+            delete_node(&mut config, &path(&["emails"]))
+        );
+        assert!(delete_node(&mut config, &path(&[])).is_err());
+    }
+
+    #[test]
+    fn should_create_update_and_delete_rules() {
+        let mut config = tree();
+
+        create_rule(&mut config, &path(&["syslog", "errors"]), new_rule("rule_two")).unwrap();
+        update_rule(&mut config, &path(&["syslog", "errors", "rule_one"]), new_rule("rule_zero"))
+            .unwrap();
+        delete_rule(&mut config, &path(&["emails", "rule_two"])).unwrap();
+
+        assert_eq!(
+            vec!["rule_zero".to_owned(), "rule_two".to_owned()],
+            rule_names(&mut config, &["syslog", "errors"])
+        );
+        assert_eq!(
+            vec!["rule_one".to_owned(), "rule_three".to_owned()],
+            rule_names(&mut config, &["emails"])
+        );
+    }
+
+    #[test]
+    fn should_not_create_or_rename_rules_with_duplicated_names() {
+        let mut config = tree();
+
+        assert!(create_rule(&mut config, &path(&["emails"]), new_rule("rule_one")).is_err());
+        assert!(
+            update_rule(&mut config, &path(&["emails", "rule_two"]), new_rule("rule_one")).is_err()
+        );
+        assert!(
+            update_rule(&mut config, &path(&["emails", "rule_ten"]), new_rule("rule_ten")).is_err()
+        );
+    }
+
+    #[test]
+    fn should_reorder_the_rules() {
+        let mut config = tree();
+
+        reorder_rules(
+            &mut config,
+            &path(&["emails"]),
+            &path(&["rule_three", "rule_one", "rule_two"]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            path(&["rule_three", "rule_one", "rule_two"]),
+            rule_names(&mut config, &["emails"])
+        );
+        assert!(reorder_rules(&mut config, &path(&["emails"]), &path(&["rule_three", "rule_one"]))
+            .is_err());
+    }
+
+    #[test]
+    fn should_enable_and_disable_rules_and_filters() {
+        let mut config = tree();
+
+        set_active(&mut config, &path(&["emails", "rule_two"]), false).unwrap();
+        set_active(&mut config, &path(&["syslog"]), false).unwrap();
+
+        match get_node_mut(&mut config, &path(&["emails"])).unwrap() {
+            MatcherConfig::Ruleset { rules, .. } => {
+                assert!(rules[0].active);
+                assert!(!rules[1].active);
+            }
+            _ => assert!(false),
+        }
+        match get_node_mut(&mut config, &path(&["syslog"])).unwrap() {
+            MatcherConfig::Filter { filter, .. } => assert!(!filter.active),
+            _ => assert!(false),
+        }
+        assert!(set_active(&mut config, &path(&["emails"]), false).is_err());
+        assert!(set_active(&mut config, &path(&["emails", "rule_ten"]), false).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use actix::MailboxError;
 use actix_web::HttpResponse;
 use failure_derive::Fail;
+use tornado_engine_api_dto::error::ApiErrorDto;
 use tornado_engine_matcher::error::MatcherError;
 
 #[derive(Fail, Debug, PartialEq)]
@@ -11,6 +12,33 @@ pub enum ApiError {
     ActixMailboxError { cause: String },
     #[fail(display = "JsonError: [{}]", cause)]
     JsonError { cause: String },
+    #[fail(display = "NotFoundError: [{}]", message)]
+    NotFoundError { message: String },
+    #[fail(display = "InvalidRequestError: [{}]", message)]
+    InvalidRequestError { message: String },
+}
+
+impl ApiError {
+    /// Returns the identifier of the error kind reported to the API clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::MatcherError { .. } => "MatcherError",
+            ApiError::ActixMailboxError { .. } => "InternalError",
+            ApiError::JsonError { .. } => "InternalError",
+            ApiError::NotFoundError { .. } => "NotFound",
+            ApiError::InvalidRequestError { .. } => "InvalidRequest",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ApiError::MatcherError { cause } => format!("{}", cause),
+            ApiError::ActixMailboxError { cause } => cause.to_owned(),
+            ApiError::JsonError { cause } => cause.to_owned(),
+            ApiError::NotFoundError { message } => message.to_owned(),
+            ApiError::InvalidRequestError { message } => message.to_owned(),
+        }
+    }
 }
 
 impl From<MatcherError> for ApiError {
@@ -31,13 +59,21 @@ impl From<serde_json::Error> for ApiError {
     }
 }
 
-// Use default implementation for `error_response()` method.
+// The errors are returned as a JSON ApiErrorDto.
 impl actix_web::error::ResponseError for ApiError {
     fn error_response(&self) -> HttpResponse {
-        match *self {
-            ApiError::MatcherError { .. } => HttpResponse::BadRequest().finish(),
-            ApiError::ActixMailboxError { .. } => HttpResponse::InternalServerError().finish(),
-            ApiError::JsonError { .. } => HttpResponse::InternalServerError().finish(),
-        }
+        let mut response = match *self {
+            ApiError::MatcherError { .. } => HttpResponse::BadRequest(),
+            ApiError::ActixMailboxError { .. } => HttpResponse::InternalServerError(),
+            ApiError::JsonError { .. } => HttpResponse::InternalServerError(),
+            ApiError::NotFoundError { .. } => HttpResponse::NotFound(),
+            ApiError::InvalidRequestError { .. } => HttpResponse::BadRequest(),
+        };
+        response.json(ApiErrorDto { code: self.code().to_owned(), message: self.message() })
+    }
+
+    // The default implementation replaces the JSON body with a plain text one
+    fn render_response(&self) -> HttpResponse {
+        self.error_response()
     }
 }
@@ -1,3 +1,4 @@
 pub mod api;
 pub mod convert;
+pub mod editor;
 pub mod error;
@@ -1,3 +1,4 @@
+use crate::event::EventDto;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -13,6 +14,25 @@ pub struct RuleDto {
     pub active: bool,
     pub constraint: ConstraintDto,
     pub actions: Vec<ActionDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tests: Vec<RuleTestDto>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct RuleTestDto {
+    #[serde(default)]
+    pub description: String,
+    pub event: EventDto,
+    pub expected: RuleTestExpectationDto,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct RuleTestExpectationDto {
+    pub matched: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extracted_vars: Option<HashMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<ActionDto>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -26,14 +46,59 @@ pub struct ConstraintDto {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 pub struct ExtractorDto {
     pub from: String,
-    pub regex: ExtractorRegexDto,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<ExtractorRegexDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<ExtractorJsonDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_value: Option<ExtractorKeyValueDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jmespath: Option<ExtractorJmespathDto>,
+    #[serde(default)]
+    pub modifiers_post: Vec<ModifierDto>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 pub struct ExtractorRegexDto {
     #[serde(rename = "match")]
     pub regex: String,
-    pub group_match_idx: u16,
+    #[serde(default)]
+    pub group_match_idx: Option<u16>,
+    #[serde(default)]
+    pub all_matches: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ExtractorJsonDto {}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ExtractorKeyValueDto {
+    #[serde(default)]
+    pub pair_separator: Option<String>,
+    #[serde(default)]
+    pub key_value_separator: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ExtractorJmespathDto {
+    pub expression: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+#[serde(tag = "type")]
+pub enum ModifierDto {
+    #[serde(rename = "trim")]
+    Trim,
+    #[serde(rename = "lowercase")]
+    Lowercase,
+    #[serde(rename = "uppercase")]
+    Uppercase,
+    #[serde(rename = "to_number")]
+    ToNumber,
+    #[serde(rename = "replace")]
+    Replace { find: String, replace: String },
+    #[serde(rename = "default")]
+    Default { value: Value },
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -43,26 +108,118 @@ pub enum OperatorDto {
     And { operators: Vec<OperatorDto> },
     #[serde(rename = "OR")]
     Or { operators: Vec<OperatorDto> },
+    #[serde(rename = "NOT")]
+    Not { operator: Box<OperatorDto> },
+    #[serde(rename = "any")]
+    Any { target: String, operator: Box<OperatorDto> },
+    #[serde(rename = "all")]
+    All { target: String, operator: Box<OperatorDto> },
+    #[serde(rename = "between")]
+    Between {
+        target: String,
+        min: Value,
+        max: Value,
+        #[serde(default)]
+        exclusive_min: bool,
+        #[serde(default)]
+        exclusive_max: bool,
+    },
     #[serde(rename = "contain")]
     Contain { first: Value, second: Value },
+    #[serde(rename = "containsIgnoreCase")]
+    ContainsIgnoreCase { first: Value, second: Value },
+    #[serde(rename = "custom")]
+    Custom {
+        name: String,
+        #[serde(default)]
+        args: HashMap<String, Value>,
+    },
+    #[serde(rename = "endsWith")]
+    EndsWith { first: Value, second: Value },
     #[serde(rename = "equal")]
     Equal { first: Value, second: Value },
+    #[serde(rename = "equalsIgnoreCase")]
+    EqualsIgnoreCase { first: Value, second: Value },
+    #[serde(rename = "exists")]
+    Exists { target: String },
     #[serde(rename = "ge")]
     GreaterEqualThan { first: Value, second: Value },
     #[serde(rename = "gt")]
     GreaterThan { first: Value, second: Value },
+    #[serde(rename = "in")]
+    In { first: Value, second: Value },
+    #[serde(rename = "ipInRange")]
+    IpInRange { ranges: Vec<String>, target: String },
+    #[serde(rename = "isNull")]
+    IsNull { target: String },
+    #[serde(rename = "isType")]
+    IsType { target: String, value_type: ValueTypeDto },
     #[serde(rename = "le")]
     LessEqualThan { first: Value, second: Value },
+    #[serde(rename = "length")]
+    Length {
+        target: String,
+        #[serde(default)]
+        min: Option<Value>,
+        #[serde(default)]
+        max: Option<Value>,
+        #[serde(default)]
+        exclusive_min: bool,
+        #[serde(default)]
+        exclusive_max: bool,
+    },
     #[serde(rename = "lt")]
     LessThan { first: Value, second: Value },
     #[serde(rename = "regex")]
-    Regex { regex: String, target: String },
+    Regex {
+        regex: String,
+        target: String,
+        #[serde(default)]
+        flags: Option<String>,
+    },
+    #[serde(rename = "regexAny")]
+    RegexAny {
+        regexes: Vec<String>,
+        target: String,
+        #[serde(default)]
+        flags: Option<String>,
+        #[serde(default)]
+        variable: Option<String>,
+    },
+    #[serde(rename = "startsWith")]
+    StartsWith { first: Value, second: Value },
+    #[serde(rename = "timeWindow")]
+    TimeWindow {
+        timezone: String,
+        #[serde(default)]
+        days: Vec<String>,
+        from: String,
+        to: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum ValueTypeDto {
+    #[serde(rename = "text")]
+    Text,
+    #[serde(rename = "number")]
+    Number,
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "array")]
+    Array,
+    #[serde(rename = "map")]
+    Map,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 pub struct ActionDto {
     pub id: String,
     pub payload: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<OperatorDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -78,3 +235,15 @@ pub enum MatcherConfigDto {
     Filter { name: String, filter: FilterDto, nodes: Vec<MatcherConfigDto> },
     Ruleset { name: String, rules: Vec<RuleDto> },
 }
+
+/// The new order of the Rules of a Ruleset, expressed by rule name.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct RulesOrderDto {
+    pub rules: Vec<String>,
+}
+
+/// Enables or disables a Rule or a Filter.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ActiveStatusDto {
+    pub active: bool,
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use typescript_definitions::TypeScriptify;
+
+/// The body of the response of a failed API call.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ApiErrorDto {
+    pub code: String,
+    pub message: String,
+}
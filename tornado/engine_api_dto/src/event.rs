@@ -16,7 +16,7 @@ pub enum ProcessType {
     SkipActions,
 }
 
-#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 pub struct EventDto {
     #[serde(rename = "type")]
     pub event_type: String,
@@ -60,6 +60,7 @@ pub struct ProcessedRuleDto {
     pub name: String,
     pub status: ProcessedRuleStatusDto,
     pub actions: Vec<ActionDto>,
+    pub skipped_actions: Vec<String>,
     pub message: Option<String>,
 }
 
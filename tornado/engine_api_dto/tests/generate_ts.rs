@@ -93,13 +93,22 @@ export type Value = any;"#;
 /* -------------- */"#,
     );
     push_ts(&mut ts_code, &config::ActionDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ActiveStatusDto::type_script_ify());
     push_ts(&mut ts_code, &config::ConstraintDto::type_script_ify());
     push_ts(&mut ts_code, &config::ExtractorDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ExtractorJmespathDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ExtractorJsonDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ExtractorKeyValueDto::type_script_ify());
     push_ts(&mut ts_code, &config::ExtractorRegexDto::type_script_ify());
     push_ts(&mut ts_code, &config::FilterDto::type_script_ify());
     push_ts(&mut ts_code, &config::MatcherConfigDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ModifierDto::type_script_ify());
     push_ts(&mut ts_code, &config::OperatorDto::type_script_ify());
     push_ts(&mut ts_code, &config::RuleDto::type_script_ify());
+    push_ts(&mut ts_code, &config::RulesOrderDto::type_script_ify());
+    push_ts(&mut ts_code, &config::RuleTestDto::type_script_ify());
+    push_ts(&mut ts_code, &config::RuleTestExpectationDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ValueTypeDto::type_script_ify());
 
     // Push 'event' ts types
     push_ts(
@@ -120,6 +129,16 @@ export type Value = any;"#;
     push_ts(&mut ts_code, &event::ProcessedRuleStatusDto::type_script_ify());
     push_ts(&mut ts_code, &event::SendEventRequestDto::type_script_ify());
 
+    // Push 'error' ts types
+    push_ts(
+        &mut ts_code,
+        r#"
+/* ------------- */
+/* 'error' types */
+/* ------------- */"#,
+    );
+    push_ts(&mut ts_code, &error::ApiErrorDto::type_script_ify());
+
     ts_code
 }
 
@@ -17,13 +17,22 @@ export type Value = any;
 /* 'config' types */
 /* -------------- */
 
-export type ActionDto = { id: string; payload: Value };
+export type ActionDto = {     id: string; payload: Value; when: OperatorDto | null; template: string     | null };
+
+// Enables or disables a Rule or a Filter.
+export type ActiveStatusDto = { active: boolean };
 
 export type ConstraintDto = { WHERE: OperatorDto | null; WITH: { [key: string]: ExtractorDto } };
 
-export type ExtractorDto = { from: string; regex: ExtractorRegexDto };
+export type ExtractorDto = {     from: string; regex: ExtractorRegexDto | null; json: ExtractorJsonDto |     null; key_value: ExtractorKeyValueDto | null; jmespath:     ExtractorJmespathDto | null; modifiers_post: ModifierDto [] };
+
+export type ExtractorJmespathDto = { expression: string };
+
+export type ExtractorJsonDto = {};
+
+export type ExtractorKeyValueDto = { pair_separator: string | null; key_value_separator: string | null };
 
-export type ExtractorRegexDto = { match: string; group_match_idx: number };
+export type ExtractorRegexDto = { match: string; group_match_idx: number | null; all_matches: boolean };
 
 export type FilterDto = { description: string; active: boolean; filter: OperatorDto | null };
 
@@ -31,18 +40,52 @@ export type MatcherConfigDto =
  | {     type: "Filter"; name: string; filter: FilterDto; nodes:     MatcherConfigDto [] } 
  | { type: "Ruleset"; name: string; rules: RuleDto [] };
 
+export type ModifierDto = 
+ | { type: "trim" } 
+ | { type: "lowercase" } 
+ | { type: "uppercase" } 
+ | { type: "to_number" } 
+ | { type: "replace"; find: string; replace: string } 
+ | { type: "default"; value: Value };
+
 export type OperatorDto = 
  | { type: "AND"; operators: OperatorDto [] } 
  | { type: "OR"; operators: OperatorDto [] } 
+ | { type: "NOT"; operator: OperatorDto } 
+ | { type: "any"; target: string; operator: OperatorDto } 
+ | { type: "all"; target: string; operator: OperatorDto } 
+ | {     type: "between"; target: string; min: Value; max: Value; exclusive_min: boolean; exclusive_max: boolean } 
  | { type: "contain"; first: Value; second: Value } 
+ | { type: "containsIgnoreCase"; first: Value; second: Value } 
+ | { type: "custom"; name: string; args: { [key: string]: Value } } 
+ | { type: "endsWith"; first: Value; second: Value } 
  | { type: "equal"; first: Value; second: Value } 
+ | { type: "equalsIgnoreCase"; first: Value; second: Value } 
+ | { type: "exists"; target: string } 
  | { type: "ge"; first: Value; second: Value } 
  | { type: "gt"; first: Value; second: Value } 
+ | { type: "in"; first: Value; second: Value } 
+ | { type: "ipInRange"; ranges: string []; target: string } 
+ | { type: "isNull"; target: string } 
+ | { type: "isType"; target: string; value_type: ValueTypeDto } 
  | { type: "le"; first: Value; second: Value } 
+ | {     type: "length"; target: string; min: Value | null; max: Value | null;     exclusive_min: boolean; exclusive_max: boolean } 
  | { type: "lt"; first: Value; second: Value } 
- | { type: "regex"; regex: string; target: string };
+ | { type: "regex"; regex: string; target: string; flags: string | null } 
+ | {     type: "regexAny"; regexes: string []; target: string; flags: string |     null; variable: string | null } 
+ | { type: "startsWith"; first: Value; second: Value } 
+ | {     type: "timeWindow"; timezone: string; days: string []; from: string;     to: string };
+
+export type RuleDto = {     name: string; description: string; continue: boolean; active: boolean;     constraint: ConstraintDto; actions: ActionDto []; tests: RuleTestDto [] };
+
+// The new order of the Rules of a Ruleset, expressed by rule name.
+export type RulesOrderDto = { rules: string [] };
 
-export type RuleDto = {     name: string; description: string; continue: boolean; active:     boolean; constraint: ConstraintDto; actions: ActionDto [] };
+export type RuleTestDto = { description: string; event: EventDto; expected: RuleTestExpectationDto };
+
+export type RuleTestExpectationDto = {     matched: boolean; extracted_vars: { [key: string]: Value } | null;     actions: ActionDto [] | null };
+
+export enum ValueTypeDto {     text = "text", number = "number", bool = "bool", array = "array", map =     "map" };
 
 
 /* ------------- */
@@ -63,10 +106,18 @@ export type ProcessedNodeDto =
  | {     type: "Filter"; name: string; filter: ProcessedFilterDto; nodes:     ProcessedNodeDto [] } 
  | { type: "Ruleset"; name: string; rules: ProcessedRulesDto };
 
-export type ProcessedRuleDto = {     name: string; status: ProcessedRuleStatusDto; actions: ActionDto [];     message: string | null };
+export type ProcessedRuleDto = {     name: string; status: ProcessedRuleStatusDto; actions: ActionDto [];     skipped_actions: string []; message: string | null };
 
 export type ProcessedRulesDto = { rules: ProcessedRuleDto []; extracted_vars: { [key: string]: Value } };
 
 export enum ProcessedRuleStatusDto {     Matched = "Matched", PartiallyMatched = "PartiallyMatched", NotMatched =     "NotMatched", NotProcessed = "NotProcessed" };
 
-export type SendEventRequestDto = { process_type: ProcessType; event: EventDto };
\ No newline at end of file
+export type SendEventRequestDto = { process_type: ProcessType; event: EventDto };
+
+
+/* ------------- */
+/* 'error' types */
+/* ------------- */
+
+// The body of the response of a failed API call.
+export type ApiErrorDto = { code: string; message: string };
\ No newline at end of file